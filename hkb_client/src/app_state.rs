@@ -1,42 +1,32 @@
-use std::fmt::Display;
-
+use hkb_core::dtos::reminders::ReminderData;
 use parking_lot::{MappedMutexGuard, Mutex, MutexGuard};
 
 use crate::terminal;
 
 static GLOBAL_APP_STATE: Mutex<Option<AppState>> = parking_lot::const_mutex(None);
 
-#[derive(Debug, Clone, Copy)]
-pub enum AppView {
-    Main,
-    Reminders,
-}
-
-impl Display for AppView {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let text = match self {
-            Self::Main => "Main",
-            Self::Reminders => "Reminders",
-        };
-
-        write!(f, "{}", text)
-    }
-}
+/// Id an app is registered with. Ex: "reminders"
+pub type AppId = &'static str;
 
+#[derive(Default)]
 pub struct AppState {
-    view: AppView,
+    view: Option<AppId>,
+    // the selected sub view of the current view, if it has any
+    sub_view: Option<AppId>,
     editing: bool,
+    // incremented every time editing mode flips
+    editing_changes: usize,
+    // incremented every time reminders are created, updated or deleted
+    reminders_changes: usize,
+    // incremented every time the daemon fires a reminder
+    reminders_fired: usize,
+    // the reminder the daemon fired last
+    last_fired_reminder: Option<ReminderData>,
     ignore_navigation_events: bool,
-}
-
-impl Default for AppState {
-    fn default() -> Self {
-        Self {
-            editing: false,
-            view: AppView::Main,
-            ignore_navigation_events: false,
-        }
-    }
+    // a form has typed input that would be lost when quitting
+    unsaved_input: bool,
+    // last yanked reminder, pasted as a new one
+    register: Option<ReminderData>,
 }
 
 impl AppState {
@@ -47,14 +37,22 @@ impl AppState {
     }
 }
 
-pub fn set_view(view: AppView) {
-    AppState::get_global().view = view;
+pub fn set_view(view: AppId) {
+    AppState::get_global().view = Some(view);
 }
 
-pub fn get_view() -> AppView {
+pub fn get_view() -> Option<AppId> {
     AppState::get_global().view
 }
 
+pub fn set_sub_view(sub_view: Option<AppId>) {
+    AppState::get_global().sub_view = sub_view;
+}
+
+pub fn get_sub_view() -> Option<AppId> {
+    AppState::get_global().sub_view
+}
+
 pub fn set_editing(editing: bool) {
     if editing {
         terminal::set_cursor_steady_bar();
@@ -62,13 +60,68 @@ pub fn set_editing(editing: bool) {
         terminal::set_cursor_to_default();
     }
 
-    AppState::get_global().editing = editing;
+    let mut app_state = AppState::get_global();
+
+    if app_state.editing != editing {
+        app_state.editing = editing;
+        app_state.editing_changes += 1;
+    }
 }
 
 pub fn is_editing() -> bool {
     AppState::get_global().editing
 }
 
+/// Tracks editing mode changes, so that we only react when the mode flips
+/// instead of checking it on every frame
+pub struct EditingObserver {
+    last_seen_change: usize,
+}
+
+impl EditingObserver {
+    pub fn new() -> Self {
+        Self {
+            last_seen_change: AppState::get_global().editing_changes,
+        }
+    }
+
+    /// Returns the new editing mode if it changed since the last call
+    pub fn changed(&mut self) -> Option<bool> {
+        let app_state = AppState::get_global();
+
+        if app_state.editing_changes == self.last_seen_change {
+            return None;
+        }
+
+        self.last_seen_change = app_state.editing_changes;
+
+        Some(app_state.editing)
+    }
+}
+
+pub fn notify_reminders_changed() {
+    AppState::get_global().reminders_changes += 1;
+}
+
+pub fn get_reminders_changes() -> usize {
+    AppState::get_global().reminders_changes
+}
+
+pub fn notify_reminder_fired(reminder: ReminderData) {
+    let mut app_state = AppState::get_global();
+
+    app_state.reminders_fired += 1;
+    app_state.last_fired_reminder = Some(reminder);
+}
+
+pub fn get_reminders_fired() -> usize {
+    AppState::get_global().reminders_fired
+}
+
+pub fn get_last_fired_reminder() -> Option<ReminderData> {
+    AppState::get_global().last_fired_reminder.clone()
+}
+
 pub fn disable_navigation_events() {
     AppState::get_global().ignore_navigation_events = true;
 }
@@ -80,3 +133,49 @@ pub fn enable_navigation_events() {
 pub fn should_ignore_navigation_events() -> bool {
     AppState::get_global().ignore_navigation_events
 }
+
+/// Views with inputs report whether any of them was edited and not saved yet,
+/// so quitting can warn before the edits are lost
+pub fn set_unsaved_input(unsaved_input: bool) {
+    AppState::get_global().unsaved_input = unsaved_input;
+}
+
+pub fn has_unsaved_input() -> bool {
+    AppState::get_global().unsaved_input
+}
+
+/// Keeps a copy of the reminder, replacing the one yanked before
+pub fn yank_reminder(reminder: ReminderData) {
+    AppState::get_global().register = Some(reminder);
+}
+
+pub fn get_yanked_reminder() -> Option<ReminderData> {
+    AppState::get_global().register.clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn it_notifies_the_observer_once_per_editing_transition() {
+        set_editing(false);
+
+        let mut observer = EditingObserver::new();
+
+        assert_eq!(None, observer.changed());
+
+        set_editing(true);
+        set_editing(true);
+
+        assert_eq!(Some(true), observer.changed());
+        assert_eq!(None, observer.changed());
+
+        set_editing(false);
+
+        assert_eq!(Some(false), observer.changed());
+        assert_eq!(None, observer.changed());
+    }
+}
@@ -4,6 +4,10 @@ use image::DynamicImage;
 use image::ImageReader;
 use ratatui::prelude::{Frame, Rect};
 
+use crate::app_state::AppId;
+
+use super::App;
+
 pub struct MainApp {
     image: DynamicImage,
 }
@@ -19,8 +23,16 @@ impl MainApp {
     }
 }
 
-impl MainApp {
-    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+impl App for MainApp {
+    fn id(&self) -> AppId {
+        "main"
+    }
+
+    fn title(&self) -> String {
+        "Main".to_string()
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
         let area_width = area.width as u32;
         let area_height = area.height as u32;
 
@@ -1,5 +1,230 @@
+use hkb_core::logger::error;
+use hkb_date::date::SimpleDate;
+use ratatui::prelude::{Alignment, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::app_state::AppId;
+use crate::components::NavigationTab;
+use crate::terminal;
+
 pub use main::MainApp;
-pub use reminders::RemindersApp;
+pub use reminders::{RemindersApp, REMINDERS_APP_ID};
+pub use today::TodayApp;
 
 mod main;
 mod reminders;
+mod today;
+
+/// A top level view, listed in the navigation
+pub trait App {
+    fn id(&self) -> AppId;
+
+    fn title(&self) -> String;
+
+    /// Views within the app, listed under its tab while it is selected.
+    /// The app keeps `app_state::get_sub_view` in sync with the view it shows.
+    fn sub_views(&self) -> Vec<NavigationTab> {
+        vec![]
+    }
+
+    /// Handles the events of the current frame, called before every render
+    fn update(&mut self) {}
+
+    fn render(&mut self, frame: &mut Frame, area: Rect);
+
+    /// Called on every loop tick with the current time, while the app is shown
+    fn on_tick(&mut self, _now: SimpleDate) {}
+
+    /// Whether the visible state changed since the last draw
+    fn is_dirty(&self) -> bool {
+        false
+    }
+
+    fn mark_clean(&mut self) {}
+}
+
+/// Apps in the order they are shown in the navigation
+#[derive(Default)]
+pub struct AppRegistry {
+    apps: Vec<Box<dyn App>>,
+}
+
+impl AppRegistry {
+    pub fn register(&mut self, app: impl App + 'static) {
+        self.apps.push(Box::new(app));
+    }
+
+    pub fn navigation_tabs(&self) -> Vec<NavigationTab> {
+        self.apps
+            .iter()
+            .map(|app| NavigationTab {
+                id: app.id(),
+                title: app.title(),
+                children: app.sub_views(),
+            })
+            .collect()
+    }
+
+    pub fn first_id(&self) -> Option<AppId> {
+        self.apps.first().map(|app| app.id())
+    }
+
+    fn get_mut(&mut self, id: AppId) -> Option<&mut Box<dyn App>> {
+        self.apps.iter_mut().find(|app| app.id() == id)
+    }
+
+    pub fn on_tick(&mut self, id: AppId, now: SimpleDate) {
+        if let Some(app) = self.get_mut(id) {
+            app.on_tick(now);
+        }
+    }
+
+    pub fn update(&mut self, id: AppId) {
+        if let Some(app) = self.get_mut(id) {
+            app.update();
+        }
+    }
+
+    /// Renders the app with the id, returns false if no such app is registered.
+    /// An app that panics while rendering is replaced by an error message, instead of closing the client.
+    pub fn render(&mut self, id: AppId, frame: &mut Frame, area: Rect) -> bool {
+        let Some(app) = self.get_mut(id) else {
+            return false;
+        };
+
+        if let Err(e) = terminal::catch_panic(|| app.render(frame, area)) {
+            error!(target: "CLIENT_APPS", "Failed to render {}: {e}", app.title());
+            render_error_placeholder(frame, area, &app.title());
+        }
+
+        true
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.apps.iter().any(|app| app.is_dirty())
+    }
+
+    pub fn mark_clean(&mut self) {
+        for app in self.apps.iter_mut() {
+            app.mark_clean();
+        }
+    }
+}
+
+fn render_error_placeholder(frame: &mut Frame, area: Rect, title: &str) {
+    // whatever the app drew before panicking is cleared
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(format!("{title} failed to render. Check the logs!"))
+            .style(Style::default().fg(Color::Red))
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::ALL)),
+        area,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    struct GreetingApp;
+
+    impl App for GreetingApp {
+        fn id(&self) -> AppId {
+            "greeting"
+        }
+
+        fn title(&self) -> String {
+            "Greeting".to_string()
+        }
+
+        fn render(&mut self, frame: &mut Frame, area: Rect) {
+            frame.render_widget(Paragraph::new("Hello"), area);
+        }
+    }
+
+    #[test]
+    fn it_lists_and_renders_registered_apps() {
+        let mut registry = AppRegistry::default();
+        registry.register(GreetingApp);
+
+        assert_eq!(
+            vec![NavigationTab {
+                id: "greeting",
+                title: "Greeting".to_string(),
+                children: vec![],
+            }],
+            registry.navigation_tabs()
+        );
+        assert_eq!(Some("greeting"), registry.first_id());
+
+        let mut terminal = Terminal::new(TestBackend::new(5, 1)).unwrap();
+        let mut rendered = (false, false);
+        terminal
+            .draw(|frame| {
+                rendered = (
+                    registry.render("greeting", frame, frame.size()),
+                    registry.render("missing", frame, frame.size()),
+                );
+            })
+            .unwrap();
+
+        assert_eq!((true, false), rendered);
+        terminal.backend().assert_buffer_lines(["Hello"]);
+    }
+
+    struct BrokenApp;
+
+    impl App for BrokenApp {
+        fn id(&self) -> AppId {
+            "broken"
+        }
+
+        fn title(&self) -> String {
+            "Broken".to_string()
+        }
+
+        fn render(&mut self, frame: &mut Frame, area: Rect) {
+            frame.render_widget(Paragraph::new("Half drawn"), area);
+
+            panic!("Failed to draw");
+        }
+    }
+
+    #[test]
+    fn it_shows_an_error_instead_of_a_panicking_app() {
+        let mut registry = AppRegistry::default();
+        registry.register(BrokenApp);
+        registry.register(GreetingApp);
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 3)).unwrap();
+        terminal
+            .draw(|frame| {
+                assert!(registry.render("broken", frame, frame.size()));
+            })
+            .unwrap();
+
+        let text = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect::<String>();
+
+        assert!(text.contains("Broken failed to render"));
+        assert!(!text.contains("Half drawn"));
+
+        // the other apps keep rendering
+        terminal
+            .draw(|frame| {
+                registry.render("greeting", frame, frame.size());
+            })
+            .unwrap();
+
+        assert_eq!("H", terminal.backend().buffer().get(0, 0).symbol());
+    }
+}
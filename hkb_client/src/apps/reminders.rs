@@ -1,79 +1,195 @@
 use hkb_core::database::services;
-use hkb_core::database::services::reminders::CreateReminderData;
+use hkb_core::database::services::reminders::{
+    CreateReminderData, ReminderId, ReminderQueryOptions, UpdateReminderData,
+};
 use hkb_core::logger::{debug, error, info};
 use hkb_daemon_core::frame::Event as FrameEvent;
+use hkb_date::clock::Clock;
+use hkb_date::date::SimpleDate;
 use ratatui::prelude::{Frame, Rect};
+use std::rc::Rc;
+
+use crate::app_state::{self, AppId};
+use crate::components::NavigationTab;
 
 use self::reminders_create::RemindersCreate;
+use self::reminders_detail::RemindersDetail;
 use self::reminders_list::RemindersList;
+use super::App;
 
 mod reminders_create;
+mod reminders_detail;
 mod reminders_list;
 
 trait RemindersView {
     fn init(&mut self);
     fn update(&mut self) -> Option<Message>;
     fn render(&mut self, frame: &mut Frame, area: Rect);
+    fn on_tick(&mut self, _now: SimpleDate) {}
+
+    /// Whether the view has to be redrawn without any new events
+    fn is_dirty(&self) -> bool {
+        false
+    }
 }
 
 enum View {
     List,
     Create,
+    CreateFrom(CreateReminderData),
+    Detail(ReminderId),
 }
 
-impl From<View> for Box<dyn RemindersView> {
-    fn from(val: View) -> Self {
-        match val {
-            View::List => Box::new(RemindersList::default()),
-            View::Create => Box::new(RemindersCreate::default()),
+const LIST_SUB_VIEW: AppId = "reminders_list";
+const CREATE_SUB_VIEW: AppId = "reminders_create";
+
+impl View {
+    fn from_sub_view(sub_view: AppId) -> Option<Self> {
+        match sub_view {
+            LIST_SUB_VIEW => Some(View::List),
+            CREATE_SUB_VIEW => Some(View::Create),
+            _ => None,
+        }
+    }
+
+    /// The sub view listed in the navigation, details are reached from the list
+    fn sub_view(&self) -> AppId {
+        match self {
+            View::List | View::Detail(_) => LIST_SUB_VIEW,
+            View::Create | View::CreateFrom(_) => CREATE_SUB_VIEW,
+        }
+    }
+
+    fn into_view(self, clock: &Rc<dyn Clock>) -> Box<dyn RemindersView> {
+        match self {
+            View::List => Box::new(RemindersList::new(clock.clone())),
+            View::Create => match services::drafts::fetch_draft(reminders_create::DRAFT_VIEW) {
+                Ok(Some(draft)) => Box::new(RemindersCreate::from_draft(draft)),
+                _ => Box::new(RemindersCreate::default()),
+            },
+            View::CreateFrom(data) => Box::new(RemindersCreate::with_data(data)),
+            View::Detail(id) => Box::new(RemindersDetail::new(id, clock.clone())),
         }
     }
 }
 
 enum Message {
     ChangeView(View),
-    DeleteReminder(i64),
+    DeleteReminder(ReminderId),
+    DeletePastReminders,
+    PinReminder(ReminderId, bool),
+    TagReminders(Vec<ReminderId>, String),
+    AssignCategory(Vec<ReminderId>, Option<String>),
+    SetAllDay(ReminderId, bool),
+    MoveReminder(ReminderId, SimpleDate),
+    SnoozeReminder(ReminderId, SimpleDate),
+    AcknowledgeAllDue,
     CreateReminder(CreateReminderData),
+    OpenAttachment(String),
+    CopyToClipboard(String),
 }
 
+pub const REMINDERS_APP_ID: AppId = "reminders";
+
 pub struct RemindersApp {
+    clock: Rc<dyn Clock>,
     current_view: Box<dyn RemindersView>,
+    current_sub_view: AppId,
 }
 
 impl RemindersApp {
-    pub fn new() -> Self {
-        let mut current_view: Box<dyn RemindersView> = View::List.into();
+    pub fn new(clock: Rc<dyn Clock>) -> Self {
+        // an unfinished reminder from the last session is reopened, so it is not lost
+        let has_draft = services::drafts::fetch_draft(reminders_create::DRAFT_VIEW)
+            .is_ok_and(|draft| draft.is_some());
+        let view = if has_draft { View::Create } else { View::List };
+        let current_sub_view = view.sub_view();
+        let mut current_view = view.into_view(&clock);
         current_view.init();
 
-        Self { current_view }
+        Self {
+            clock,
+            current_view,
+            current_sub_view,
+        }
+    }
+
+    fn change_view(&mut self, view: View) {
+        self.current_sub_view = view.sub_view();
+        self.current_view = view.into_view(&self.clock);
+        self.current_view.init();
+        app_state::set_sub_view(Some(self.current_sub_view));
+    }
+
+    /// Follows the sub view picked in the navigation, or reports ours if none was picked yet
+    fn sync_sub_view(&mut self) {
+        match app_state::get_sub_view() {
+            Some(sub_view) if sub_view == self.current_sub_view => {}
+            Some(sub_view) => {
+                if let Some(view) = View::from_sub_view(sub_view) {
+                    self.change_view(view);
+                }
+            }
+            None => app_state::set_sub_view(Some(self.current_sub_view)),
+        }
     }
 }
 
-impl RemindersApp {
-    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+impl App for RemindersApp {
+    fn id(&self) -> AppId {
+        REMINDERS_APP_ID
+    }
+
+    fn title(&self) -> String {
+        "Reminders".to_string()
+    }
+
+    fn sub_views(&self) -> Vec<NavigationTab> {
+        [(LIST_SUB_VIEW, "List"), (CREATE_SUB_VIEW, "New")]
+            .into_iter()
+            .map(|(id, title)| NavigationTab {
+                id,
+                title: title.to_string(),
+                children: vec![],
+            })
+            .collect()
+    }
+
+    fn on_tick(&mut self, now: SimpleDate) {
+        self.current_view.on_tick(now);
+    }
+
+    fn update(&mut self) {
+        self.sync_sub_view();
+
         if let Some(m) = self.current_view.update() {
             match m {
-                Message::ChangeView(view) => {
-                    self.current_view = view.into();
-                    self.current_view.init();
-                }
+                Message::ChangeView(view) => self.change_view(view),
                 Message::CreateReminder(reminder) => {
                     info!(target: "CLIENT_REMINDERS", "Creating a reminder.");
                     debug!(target: "CLIENT_REMINDERS", "Received a message to create a reminder with {reminder:?}");
 
                     if let Ok(reminder) = services::reminders::create_reminder(reminder) {
                         crate::singleton::send_server_msg(FrameEvent::ReminderCreated(reminder));
+                        crate::app_state::notify_reminders_changed();
+
+                        if let Err(e) = services::drafts::clear_draft(reminders_create::DRAFT_VIEW)
+                        {
+                            error!(target: "CLIENT_REMINDERS", "Failed to clear draft: {e}");
+                        }
                     }
 
-                    self.current_view = View::List.into();
-                    self.current_view.init();
+                    self.change_view(View::List);
                 }
                 Message::DeleteReminder(reminder_id) => {
                     info!(target: "CLIENT_REMINDERS", "Deleting a reminder.");
                     debug!(target: "CLIENT_REMINDERS", "Received a message to delete a reminder with id {reminder_id}");
 
                     if services::reminders::delete_reminder(reminder_id).is_ok() {
-                        crate::singleton::send_server_msg(FrameEvent::ReminderDeleted(reminder_id));
+                        crate::singleton::send_server_msg(FrameEvent::ReminderDeleted(
+                            reminder_id.into(),
+                        ));
+                        crate::app_state::notify_reminders_changed();
 
                         // reinitialize view, as we just deleted a reminder
                         self.current_view.init();
@@ -81,9 +197,145 @@ impl RemindersApp {
                         error!(target: "CLIENT_REMINDERS", "Failed to delete a reminder with id {reminder_id}!");
                     }
                 }
+                Message::DeletePastReminders => {
+                    info!(target: "CLIENT_REMINDERS", "Deleting past reminders.");
+
+                    let option = ReminderQueryOptions::RemindAtLe {
+                        date: self.clock.now(),
+                    };
+
+                    if services::reminders::delete_reminders(option).is_ok() {
+                        crate::app_state::notify_reminders_changed();
+
+                        // reinitialize view, as we just deleted reminders
+                        self.current_view.init();
+                    } else {
+                        error!(target: "CLIENT_REMINDERS", "Failed to delete past reminders!");
+                    }
+                }
+                Message::PinReminder(reminder_id, pinned) => {
+                    info!(target: "CLIENT_REMINDERS", "Pinning a reminder.");
+                    debug!(target: "CLIENT_REMINDERS", "Received a message to set pinned of reminder {reminder_id} to {pinned}");
+
+                    if services::reminders::pin_reminder(reminder_id, pinned).is_ok() {
+                        crate::app_state::notify_reminders_changed();
+
+                        // reinitialize view, so the reminder moves to its new place
+                        self.current_view.init();
+                    } else {
+                        error!(target: "CLIENT_REMINDERS", "Failed to pin a reminder with id {reminder_id}!");
+                    }
+                }
+                Message::TagReminders(reminder_ids, tag) => {
+                    debug!(target: "CLIENT_REMINDERS", "Received a message to tag reminders {reminder_ids:?} with {tag}");
+
+                    if services::tags::tag_reminders(&reminder_ids, &tag).is_ok() {
+                        crate::app_state::notify_reminders_changed();
+
+                        // reinitialize view, as we just changed reminders
+                        self.current_view.init();
+                    } else {
+                        error!(target: "CLIENT_REMINDERS", "Failed to tag reminders {reminder_ids:?} with {tag}!");
+                    }
+                }
+                Message::AssignCategory(reminder_ids, category) => {
+                    debug!(target: "CLIENT_REMINDERS", "Received a message to set the category of reminders {reminder_ids:?} to {category:?}");
+
+                    if services::reminders::assign_category(&reminder_ids, category.as_deref())
+                        .is_ok()
+                    {
+                        crate::app_state::notify_reminders_changed();
+
+                        // reinitialize view, as we just changed reminders
+                        self.current_view.init();
+                    } else {
+                        error!(target: "CLIENT_REMINDERS", "Failed to set the category of reminders {reminder_ids:?}!");
+                    }
+                }
+                Message::SetAllDay(reminder_id, all_day) => {
+                    debug!(target: "CLIENT_REMINDERS", "Received a message to set all day of reminder {reminder_id} to {all_day}");
+
+                    if services::reminders::set_all_day(reminder_id, all_day).is_ok() {
+                        crate::app_state::notify_reminders_changed();
+
+                        // the reminder can move to the start of the day
+                        self.current_view.init();
+                    } else {
+                        error!(target: "CLIENT_REMINDERS", "Failed to set all day of a reminder with id {reminder_id}!");
+                    }
+                }
+                Message::MoveReminder(reminder_id, remind_at) => {
+                    info!(target: "CLIENT_REMINDERS", "Moving a reminder.");
+                    debug!(target: "CLIENT_REMINDERS", "Received a message to move reminder {reminder_id} to {remind_at}");
+
+                    let result = services::reminders::update_reminder(UpdateReminderData {
+                        id: reminder_id,
+                        note: None,
+                        body: None,
+                        remind_at: Some(remind_at),
+                        attachment: None,
+                        recurrence: None,
+                        miss_policy: None,
+                    });
+
+                    if result.is_ok() {
+                        crate::app_state::notify_reminders_changed();
+
+                        // reinitialize view, so the reminder moves to its new day
+                        self.current_view.init();
+                    } else {
+                        error!(target: "CLIENT_REMINDERS", "Failed to move a reminder with id {reminder_id}!");
+                    }
+                }
+                Message::SnoozeReminder(reminder_id, until) => {
+                    info!(target: "CLIENT_REMINDERS", "Snoozing a reminder.");
+                    debug!(target: "CLIENT_REMINDERS", "Received a message to snooze reminder {reminder_id} until {until}");
+
+                    if services::reminders::snooze_reminder(reminder_id, until).is_ok() {
+                        crate::app_state::notify_reminders_changed();
+
+                        // reinitialize view, so the reminder moves to its new time
+                        self.current_view.init();
+                    } else {
+                        error!(target: "CLIENT_REMINDERS", "Failed to snooze a reminder with id {reminder_id}!");
+                    }
+                }
+                Message::AcknowledgeAllDue => {
+                    info!(target: "CLIENT_REMINDERS", "Acknowledging due reminders.");
+
+                    match services::reminders::acknowledge_all_due(self.clock.now()) {
+                        Ok(acknowledged) => {
+                            debug!(target: "CLIENT_REMINDERS", "Acknowledged {acknowledged} reminders.");
+                            crate::app_state::notify_reminders_changed();
+                        }
+                        Err(e) => {
+                            error!(target: "CLIENT_REMINDERS", "Failed to acknowledge due reminders: {e}");
+                        }
+                    }
+                }
+                Message::OpenAttachment(attachment) => {
+                    info!(target: "CLIENT_REMINDERS", "Opening attachment {attachment}.");
+
+                    if let Err(e) = crate::utils::open_with_default_program(&attachment) {
+                        error!(target: "CLIENT_REMINDERS", "Failed to open attachment {attachment}: {e}");
+                    }
+                }
+                Message::CopyToClipboard(text) => {
+                    info!(target: "CLIENT_REMINDERS", "Copying {text} to the clipboard.");
+
+                    if let Err(e) = crate::utils::copy_to_clipboard(&text) {
+                        error!(target: "CLIENT_REMINDERS", "Failed to copy {text} to the clipboard: {e}");
+                    }
+                }
             }
         };
+    }
 
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
         self.current_view.render(frame, area);
     }
+
+    fn is_dirty(&self) -> bool {
+        self.current_view.is_dirty()
+    }
 }
@@ -1,26 +1,177 @@
 use crossterm::event::KeyCode;
-use hkb_core::database::services::reminders::CreateReminderData;
-use hkb_core::logger::{debug, info};
+use hkb_core::database::services::drafts::{self, DraftData};
+use hkb_core::database::services::reminders::{
+    CreateReminderData, MissPolicy, Recurrence, ReminderData,
+};
+use hkb_core::logger::{debug, error, info};
 use hkb_date::date::SimpleDate;
+use hkb_date::duration::Duration;
 use hkb_date::HumanDateParser;
 use ratatui::prelude::{Constraint, Direction, Frame, Layout, Rect};
 use ratatui::style::{Color, Style};
 use ratatui::widgets::{Block, Borders, Padding, Paragraph};
+use std::time::{Duration as STDDuration, Instant};
 
 use crate::components::{Button, ButtonState, Input, InputState, StatefulComponent};
+use crate::focus::{FocusManager, Focusable};
 use crate::utils::centered_layout;
-use crate::{app_state, events, focus::Focusable};
+use crate::utils::debounced_writer::DebouncedWriter;
+use crate::{app_state, config, events};
 
 use super::{Message, RemindersView};
 
-#[derive(Default)]
+const PREFILLED_DATE_FORMAT: &str = "%d-%m-%Y %H:%M";
+
+/// Key of the create form draft, restored when the app restarts mid-entry
+pub const DRAFT_VIEW: &str = "reminders_create";
+const DRAFT_QUIET_PERIOD: STDDuration = STDDuration::from_millis(500);
+
+// note and raw date input of the form
+type DraftFields = (String, String);
+
+fn write_draft((note, date): DraftFields) {
+    let result = if note.is_empty() && date.is_empty() {
+        drafts::clear_draft(DRAFT_VIEW)
+    } else {
+        drafts::save_draft(DRAFT_VIEW, note, date).map(|_| ())
+    };
+
+    if let Err(e) = result {
+        error!(target: "CLIENT_REMINDERS_CREATE", "Failed to save draft: {e}");
+    }
+}
+
+/// Copies a reminder as a starting point for a new one, shifted by the offset
+pub fn duplicate_reminder(reminder: &ReminderData, offset: Duration) -> CreateReminderData {
+    CreateReminderData {
+        note: reminder.note.clone(),
+        body: reminder.body.clone(),
+        remind_at: reminder
+            .remind_at
+            .add_duration(offset)
+            .unwrap_or(reminder.remind_at),
+        attachment: reminder.attachment.clone(),
+        recurrence: reminder.recurrence,
+        miss_policy: reminder.miss_policy,
+    }
+}
+
+// message, date and attachment inputs and the submit button
+const FOCUSABLES_COUNT: usize = 4;
+// the attachment input, pressing Enter on it or after it submits the form
+const LAST_FIELD_INDEX: usize = 2;
+
 pub struct RemindersCreate {
     error_message: Option<String>,
-    focused_component: usize,
+    focus_manager: FocusManager,
     message_input: InputState,
     reminder_date_input: InputState,
+    attachment_input: InputState,
     submit_button: ButtonState,
     parsed_date: Option<SimpleDate>,
+    prefilled_date: Option<SimpleDate>,
+    // the form has no body input, a prefilled body is kept as is
+    body: Option<String>,
+    recurrence: Option<Recurrence>,
+    miss_policy: MissPolicy,
+    draft_fields: DraftFields,
+    draft_writer: DebouncedWriter<DraftFields, fn(DraftFields)>,
+}
+
+impl Default for RemindersCreate {
+    fn default() -> Self {
+        Self {
+            error_message: None,
+            focus_manager: FocusManager::new(FOCUSABLES_COUNT),
+            message_input: InputState::default(),
+            reminder_date_input: InputState::default(),
+            attachment_input: InputState::default(),
+            submit_button: ButtonState::default(),
+            parsed_date: None,
+            prefilled_date: None,
+            body: None,
+            recurrence: None,
+            miss_policy: MissPolicy::default(),
+            draft_fields: DraftFields::default(),
+            draft_writer: DebouncedWriter::new(DRAFT_QUIET_PERIOD, write_draft),
+        }
+    }
+}
+
+impl RemindersCreate {
+    /// Prefills the form, the reminder is only created once the form is submitted
+    pub fn with_data(data: CreateReminderData) -> Self {
+        let mut create = Self::default();
+
+        create.message_input.load(data.note);
+        create
+            .reminder_date_input
+            .load(data.remind_at.format(PREFILLED_DATE_FORMAT));
+        create
+            .attachment_input
+            .load(data.attachment.unwrap_or_default());
+        create.prefilled_date = Some(data.remind_at);
+        create.body = data.body;
+        create.recurrence = data.recurrence;
+        create.miss_policy = data.miss_policy;
+
+        create
+    }
+
+    /// Refills the form with the note and date typed before the app was closed
+    pub fn from_draft(draft: DraftData) -> Self {
+        let mut create = Self::default();
+
+        create.message_input.buffer = draft.note;
+        create.reminder_date_input.buffer = draft.date;
+        create.draft_fields = (
+            create.message_input.buffer.clone(),
+            create.reminder_date_input.buffer.clone(),
+        );
+
+        create
+    }
+
+    fn save_draft(&mut self, now: Instant) {
+        let fields = (
+            self.message_input.buffer.clone(),
+            self.reminder_date_input.buffer.clone(),
+        );
+
+        if fields != self.draft_fields {
+            self.draft_fields = fields.clone();
+            self.draft_writer.update(fields, now);
+        }
+
+        self.draft_writer.tick(now);
+    }
+
+    /// Whether quitting now would lose anything typed in the form.
+    /// Prefilled values are not counted until they are edited.
+    fn has_unsaved_input(&self) -> bool {
+        [
+            &self.message_input,
+            &self.reminder_date_input,
+            &self.attachment_input,
+        ]
+        .iter()
+        .any(|input| input.is_dirty())
+    }
+
+    fn mark_saved(&mut self) {
+        self.message_input.mark_saved();
+        self.reminder_date_input.mark_saved();
+        self.attachment_input.mark_saved();
+        app_state::set_unsaved_input(false);
+    }
+
+    fn discard_draft(&mut self) {
+        self.draft_writer.cancel();
+
+        if let Err(e) = drafts::clear_draft(DRAFT_VIEW) {
+            error!(target: "CLIENT_REMINDERS_CREATE", "Failed to clear draft: {e}");
+        }
+    }
 }
 
 // Create rendering
@@ -28,11 +179,25 @@ impl RemindersCreate {
     fn render_inputs(&mut self, frame: &mut Frame, area: Rect) {
         let input_layout = Layout::new(
             Direction::Vertical,
-            [Constraint::Length(3), Constraint::Length(3)],
+            [
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Length(3),
+            ],
         )
         .split(area);
-        Input::new("Title").render(frame, &mut self.message_input, input_layout[0]);
+        let snippets = config::get_snippets();
+        Input::new("Title").snippets(&snippets).render(
+            frame,
+            &mut self.message_input,
+            input_layout[0],
+        );
         Input::new("Reminder Date").render(frame, &mut self.reminder_date_input, input_layout[1]);
+        Input::new("Attachment (optional)").render(
+            frame,
+            &mut self.attachment_input,
+            input_layout[2],
+        );
     }
 
     fn render_error_messages(&mut self, frame: &mut Frame, area: Rect) {
@@ -82,6 +247,15 @@ impl RemindersCreate {
             self.error_message = Some("Title Input is required!".to_owned());
         } else if self.reminder_date_input.buffer.is_empty() {
             self.error_message = Some("Remidner Date Input is required!".to_owned());
+        } else if let Some(date) = self
+            .prefilled_date
+            .filter(|date| date.format(PREFILLED_DATE_FORMAT) == self.reminder_date_input.buffer)
+        {
+            // the prefilled date is not a human date, so we use it as is when left untouched
+            self.parsed_date = Some(date);
+            self.error_message = None;
+
+            return true;
         } else {
             let parser = HumanDateParser::new(SimpleDate::local());
 
@@ -107,17 +281,53 @@ impl RemindersView for RemindersCreate {
     fn init(&mut self) {
         info!(target: "CLIENT_REMINDERS_CREATE", "Create reminder view initialized.");
 
-        app_state::set_editing(true);
+        let edit_on_open = config::should_edit_on_open();
+
+        // typing right away goes to the note, otherwise insert mode is entered with `i`
+        if edit_on_open {
+            self.focus_manager = FocusManager::new(FOCUSABLES_COUNT);
+            self.message_input.focus();
+        }
+
+        app_state::set_editing(edit_on_open);
         app_state::disable_navigation_events();
     }
 
     fn update(&mut self) -> Option<Message> {
-        if self.submit_button.is_clicked() || events::has_key_event!(KeyCode::Enter) {
+        if !app_state::is_editing() && events::has_key_event!(KeyCode::Char(c) if c == 'q') {
+            self.discard_draft();
+            app_state::set_unsaved_input(false);
+
+            return Some(Message::ChangeView(super::View::List));
+        }
+
+        let mut should_submit = self.submit_button.is_clicked();
+
+        // Enter moves to the next field and submits only from the last one
+        if events::has_key_event!(KeyCode::Enter) {
+            if self.focus_manager.focused() >= LAST_FIELD_INDEX {
+                should_submit = true;
+            } else {
+                self.focus_manager.next();
+            }
+        }
+
+        if should_submit {
             if self.validate() {
+                let attachment = self.attachment_input.buffer.trim();
                 let data = CreateReminderData {
                     note: self.message_input.buffer.to_owned(),
+                    body: self.body.clone(),
                     remind_at: self.parsed_date.take().unwrap(),
+                    attachment: (!attachment.is_empty()).then(|| attachment.to_owned()),
+                    recurrence: self.recurrence,
+                    miss_policy: self.miss_policy,
                 };
+
+                // the draft is kept until the reminder is created
+                self.draft_writer.flush();
+                self.mark_saved();
+
                 return Some(Message::CreateReminder(data));
             }
 
@@ -127,31 +337,19 @@ impl RemindersView for RemindersCreate {
         let mut focusables: Vec<&mut dyn Focusable> = vec![
             &mut self.message_input,
             &mut self.reminder_date_input,
+            &mut self.attachment_input,
             &mut self.submit_button,
         ];
 
         if events::has_key_event!(KeyCode::Tab) {
-            let last_focused_input = self.focused_component;
-            self.focused_component += 1;
-
-            if self.focused_component >= focusables.len() {
-                self.focused_component = 0;
-            }
-
-            focusables[last_focused_input].blur();
+            self.focus_manager.next();
         } else if events::has_key_event!(KeyCode::BackTab) {
-            let last_focused_input = self.focused_component;
-
-            if self.focused_component == 0 {
-                self.focused_component = focusables.len() - 1;
-            } else {
-                self.focused_component -= 1;
-            }
-
-            focusables[last_focused_input].blur();
+            self.focus_manager.previous();
         }
 
-        focusables[self.focused_component].focus();
+        self.focus_manager.apply(&mut focusables);
+        self.save_draft(Instant::now());
+        app_state::set_unsaved_input(self.has_unsaved_input());
 
         None
     }
@@ -176,3 +374,206 @@ impl RemindersView for RemindersCreate {
         self.render_form(frame, block_area);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{Event, KeyEvent, KeyModifiers};
+    use hkb_core::dtos::reminders::fakes;
+    use serial_test::serial;
+
+    #[test]
+    fn it_duplicates_a_reminder_with_a_shifted_time() {
+        let mut reminder = fakes::create_reminder();
+        reminder.note = "Water the plants".to_owned();
+        reminder.remind_at =
+            SimpleDate::parse_from_str("2024-04-30 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        reminder.recurrence = Some(Recurrence::Weekly);
+
+        let duplicate = duplicate_reminder(&reminder, Duration::Day(1));
+
+        assert_eq!(
+            CreateReminderData {
+                note: "Water the plants".to_owned(),
+                body: None,
+                remind_at: SimpleDate::parse_from_str("2024-05-01 08:00:00", "%Y-%m-%d %H:%M:%S")
+                    .unwrap(),
+                attachment: None,
+                recurrence: Some(Recurrence::Weekly),
+                miss_policy: MissPolicy::Keep,
+            },
+            duplicate
+        );
+        assert_eq!(
+            SimpleDate::parse_from_str("2024-04-30 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap(),
+            reminder.remind_at
+        );
+    }
+
+    #[test]
+    fn it_uses_the_prefilled_date_when_left_untouched() {
+        let mut create = RemindersCreate::with_data(duplicate_reminder(
+            &fakes::create_reminder(),
+            Duration::Day(1),
+        ));
+
+        assert!(create.validate());
+        assert_eq!(create.prefilled_date, create.parsed_date);
+
+        create.reminder_date_input.buffer = "tomorrow at 10:00".to_owned();
+
+        assert!(create.validate());
+        assert_ne!(create.prefilled_date, create.parsed_date);
+    }
+
+    #[test]
+    fn it_restores_the_form_from_a_draft() {
+        let mut create = RemindersCreate::from_draft(DraftData {
+            view: DRAFT_VIEW.to_owned(),
+            note: "Water the plants".to_owned(),
+            date: "tomorrow at".to_owned(),
+            updated_at: SimpleDate::local(),
+        });
+
+        assert_eq!("Water the plants", create.message_input.buffer);
+        assert_eq!("tomorrow at", create.reminder_date_input.buffer);
+
+        // an untouched restored draft is not written again
+        create.save_draft(Instant::now());
+
+        assert!(!create.draft_writer.has_pending());
+
+        create.reminder_date_input.buffer.push_str(" 10:00");
+        create.save_draft(Instant::now());
+
+        assert!(create.draft_writer.has_pending());
+    }
+
+    fn push_enter() {
+        events::push(Event::Key(KeyEvent::new(
+            KeyCode::Enter,
+            KeyModifiers::NONE,
+        )));
+    }
+
+    #[test]
+    #[serial]
+    fn it_starts_editing_the_note_when_opened() {
+        let mut create = RemindersCreate::default();
+
+        config::set_edit_on_open(true);
+        app_state::set_editing(false);
+        create.init();
+
+        assert!(app_state::is_editing());
+        assert_eq!(0, create.focus_manager.focused());
+        assert!(create.message_input.is_focused());
+
+        let mut create = RemindersCreate::default();
+
+        config::set_edit_on_open(false);
+        create.init();
+
+        assert!(!app_state::is_editing());
+        assert!(!create.message_input.is_focused());
+
+        config::set_edit_on_open(true);
+        app_state::enable_navigation_events();
+    }
+
+    #[test]
+    #[serial]
+    fn it_moves_focus_to_the_next_field_on_enter() {
+        let mut create = RemindersCreate::default();
+        create.message_input.buffer = "Water the plants".to_owned();
+
+        events::clear();
+        push_enter();
+
+        assert!(create.update().is_none());
+        assert_eq!(1, create.focus_manager.focused());
+
+        push_enter();
+
+        assert!(create.update().is_none());
+        assert_eq!(LAST_FIELD_INDEX, create.focus_manager.focused());
+    }
+
+    #[test]
+    #[serial]
+    fn it_reports_unsaved_input_until_the_form_is_left() {
+        let mut create = RemindersCreate::default();
+        create.message_input.buffer = "   ".to_owned();
+
+        events::clear();
+
+        assert!(create.update().is_none());
+        assert!(!app_state::has_unsaved_input());
+
+        create.message_input.buffer = "Water the plants".to_owned();
+
+        assert!(create.update().is_none());
+        assert!(app_state::has_unsaved_input());
+
+        app_state::set_editing(false);
+        events::push(Event::Key(KeyEvent::new(
+            KeyCode::Char('q'),
+            KeyModifiers::NONE,
+        )));
+
+        assert!(matches!(
+            create.update(),
+            Some(Message::ChangeView(super::super::View::List))
+        ));
+        assert!(!app_state::has_unsaved_input());
+        events::clear();
+    }
+
+    #[test]
+    #[serial]
+    fn it_only_reports_edited_prefilled_values_as_unsaved() {
+        let mut reminder = fakes::create_reminder();
+        reminder.note = "Water the plants".to_owned();
+        let mut create =
+            RemindersCreate::with_data(duplicate_reminder(&reminder, Duration::Day(1)));
+
+        events::clear();
+
+        assert!(create.update().is_none());
+        assert!(!app_state::has_unsaved_input());
+
+        create.message_input.buffer.push_str(" and the garden");
+
+        assert!(create.update().is_none());
+        assert!(app_state::has_unsaved_input());
+
+        create.focus_manager.next();
+        create.focus_manager.next();
+        push_enter();
+
+        assert!(matches!(
+            create.update(),
+            Some(Message::CreateReminder(data)) if data.note == "Water the plants and the garden"
+        ));
+        assert!(!app_state::has_unsaved_input());
+        assert!(!create.has_unsaved_input());
+    }
+
+    #[test]
+    #[serial]
+    fn it_submits_on_enter_in_the_last_field() {
+        let mut create = RemindersCreate::default();
+        create.message_input.buffer = "Water the plants".to_owned();
+        create.reminder_date_input.buffer = "in 5 minutes".to_owned();
+        create.focus_manager.next();
+        create.focus_manager.next();
+
+        events::clear();
+        push_enter();
+
+        assert!(matches!(
+            create.update(),
+            Some(Message::CreateReminder(data)) if data.note == "Water the plants"
+        ));
+    }
+}
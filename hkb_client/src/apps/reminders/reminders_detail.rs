@@ -0,0 +1,302 @@
+use crossterm::event::KeyCode;
+use hkb_core::database::services::reminders::{self, AttachmentData, ReminderData, ReminderId};
+use hkb_core::database::services::subtasks::{self, SubtaskData};
+use hkb_core::logger::{error, info};
+use hkb_date::clock::Clock;
+use hkb_date::duration::HumanizedDuration;
+use ratatui::prelude::{Frame, Rect};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Padding, Paragraph, Wrap};
+use std::rc::Rc;
+
+use crate::utils::{format_date, format_reminder_time};
+use crate::{app_state, events};
+
+use super::{Message, RemindersView};
+
+fn load_reminder(id: ReminderId) -> Option<(ReminderData, Vec<AttachmentData>)> {
+    match reminders::fetch_reminder(id) {
+        Ok(reminder) => {
+            let attachments = reminders::list_attachments(id).unwrap_or_default();
+
+            Some((reminder, attachments))
+        }
+        Err(e) => {
+            error!(target: "CLIENT_REMINDERS_DETAIL", "Failed to fetch reminder {id}: {e}");
+
+            None
+        }
+    }
+}
+
+pub struct RemindersDetail {
+    id: ReminderId,
+    clock: Rc<dyn Clock>,
+    scroll: u16,
+    reminder: Option<ReminderData>,
+    attachments: Vec<AttachmentData>,
+    subtasks: Vec<SubtaskData>,
+}
+
+impl RemindersDetail {
+    pub fn new(id: ReminderId, clock: Rc<dyn Clock>) -> Self {
+        Self {
+            id,
+            clock,
+            scroll: 0,
+            reminder: None,
+            attachments: vec![],
+            subtasks: vec![],
+        }
+    }
+
+    fn format_remind_at(&self, reminder: &ReminderData) -> String {
+        let duration = reminder.remind_at - self.clock.now();
+        let date = format!(
+            "{} {}",
+            format_date(&reminder.remind_at, "%d-%m-%Y"),
+            format_reminder_time(reminder)
+        );
+
+        if duration.is_zero() {
+            format!("{date} (already due)")
+        } else {
+            format!("{date} (in {})", duration.to_human_string())
+        }
+    }
+
+    fn create_lines(&self, reminder: &ReminderData) -> Vec<Line<'static>> {
+        let mut lines = vec![
+            Line::from(format!("Remind at: {}", self.format_remind_at(reminder))),
+            Line::from(format!(
+                "Created at: {}",
+                format_date(&reminder.created_at, "%d-%m-%Y %H:%M")
+            )),
+        ];
+
+        if let Some(recurrence) = reminder.recurrence {
+            lines.push(Line::from(format!("Repeats: {recurrence}")));
+        }
+
+        if let Some(attachment) = reminder.attachment.as_ref() {
+            lines.push(Line::from(format!("Attachment: {attachment}")));
+        }
+
+        for attachment in self.attachments.iter() {
+            lines.push(Line::from(format!("Attachment: {}", attachment.path)));
+        }
+
+        lines.push(Line::from(""));
+        lines.extend(
+            reminder
+                .display_note()
+                .lines()
+                .map(|line| Line::from(line.to_owned())),
+        );
+
+        if let Some(body) = reminder.body.as_ref() {
+            lines.push(Line::from(""));
+            lines.extend(body.lines().map(|line| Line::from(line.to_owned())));
+        }
+
+        if !self.subtasks.is_empty() {
+            lines.push(Line::from(""));
+        }
+
+        for subtask in self.subtasks.iter() {
+            let checkbox = if subtask.done { "x" } else { " " };
+
+            lines.push(Line::from(format!("[{checkbox}] {}", subtask.text)));
+        }
+
+        lines
+    }
+}
+
+impl RemindersView for RemindersDetail {
+    fn init(&mut self) {
+        info!(target: "CLIENT_REMINDERS_DETAIL", "Reminder detail view initialized.");
+        app_state::set_editing(false);
+        app_state::enable_navigation_events();
+
+        if let Some((reminder, attachments)) = load_reminder(self.id) {
+            self.reminder = Some(reminder);
+            self.attachments = attachments;
+            self.subtasks = subtasks::fetch_subtasks(self.id).unwrap_or_default();
+        }
+    }
+
+    fn update(&mut self) -> Option<Message> {
+        if events::has_key_event!(KeyCode::Esc)
+            || events::has_key_event!(KeyCode::Char(c) if c == 'q')
+        {
+            return Some(Message::ChangeView(super::View::List));
+        }
+
+        if events::has_key_event!(KeyCode::Enter | KeyCode::Char('o')) {
+            if let Some(attachment) = self
+                .reminder
+                .as_ref()
+                .and_then(|reminder| reminder.attachment.clone())
+            {
+                return Some(Message::OpenAttachment(attachment));
+            }
+        }
+
+        if events::has_key_event!(KeyCode::Char(c) if c == 'j') {
+            self.scroll = self.scroll.saturating_add(1);
+        } else if events::has_key_event!(KeyCode::Char(c) if c == 'k') {
+            self.scroll = self.scroll.saturating_sub(1);
+        }
+
+        None
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .padding(Padding::symmetric(1, 1));
+
+        let Some(reminder) = self.reminder.as_ref() else {
+            frame.render_widget(
+                Paragraph::new("Reminder not found!").block(block.title("Reminder")),
+                area,
+            );
+
+            return;
+        };
+
+        frame.render_widget(
+            Paragraph::new(self.create_lines(reminder))
+                .wrap(Wrap { trim: false })
+                .scroll((self.scroll, 0))
+                .block(
+                    block.title(
+                        reminder
+                            .display_note()
+                            .lines()
+                            .next()
+                            .unwrap_or_default()
+                            .to_owned(),
+                    ),
+                ),
+            area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::testing::{inject, key_event};
+    use hkb_core::database::init_database;
+    use hkb_core::database::services::reminders::{CreateReminderData, MissPolicy};
+    use hkb_date::clock::MockClock;
+    use hkb_date::date::SimpleDate;
+    use serial_test::serial;
+
+    fn create_reminder(note: &str) -> ReminderData {
+        reminders::create_reminder(CreateReminderData {
+            note: note.to_owned(),
+            body: None,
+            remind_at: SimpleDate::parse_from_str("2024-04-05 08:00:00", "%Y-%m-%d %H:%M:%S")
+                .unwrap(),
+            attachment: None,
+            recurrence: None,
+            miss_policy: MissPolicy::Keep,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    #[serial]
+    fn it_loads_the_reminder_by_id() {
+        init_database(":memory:", vec![crate::CORE_MIGRATIONS]).unwrap();
+
+        create_reminder("Buy milk");
+        let reminder = create_reminder("Call the bank");
+        let attachment = reminders::add_attachment(reminder.id, "/home/user/iban.txt").unwrap();
+        create_reminder("Water the plants");
+
+        let (loaded_reminder, attachments) = load_reminder(reminder.id).unwrap();
+
+        assert_eq!(reminder, loaded_reminder);
+        assert_eq!(vec![attachment], attachments);
+        assert!(load_reminder(ReminderId(reminder.id.0 + 100)).is_none());
+    }
+
+    #[test]
+    #[serial]
+    fn it_renders_the_subtasks_with_checkboxes() {
+        init_database(":memory:", vec![crate::CORE_MIGRATIONS]).unwrap();
+
+        let reminder = create_reminder("Pack for the trip");
+        let passport = subtasks::add_subtask(reminder.id, "Passport").unwrap();
+        subtasks::add_subtask(reminder.id, "Charger").unwrap();
+        subtasks::toggle_subtask(passport.id).unwrap();
+        let mut detail =
+            RemindersDetail::new(reminder.id, Rc::new(MockClock::new(reminder.created_at)));
+
+        detail.init();
+
+        assert_eq!(
+            vec![
+                Line::from(""),
+                Line::from("[x] Passport"),
+                Line::from("[ ] Charger"),
+            ],
+            detail.create_lines(&reminder)[4..]
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn it_opens_the_attachment_with_enter() {
+        init_database(":memory:", vec![crate::CORE_MIGRATIONS]).unwrap();
+
+        let reminder = reminders::create_reminder(CreateReminderData {
+            note: "Pay the bill".to_owned(),
+            body: None,
+            remind_at: SimpleDate::parse_from_str("2024-04-05 08:00:00", "%Y-%m-%d %H:%M:%S")
+                .unwrap(),
+            attachment: Some("/home/user/bill.pdf".to_owned()),
+            recurrence: None,
+            miss_policy: MissPolicy::Keep,
+        })
+        .unwrap();
+        let mut detail =
+            RemindersDetail::new(reminder.id, Rc::new(MockClock::new(reminder.created_at)));
+
+        detail.init();
+        inject([key_event(KeyCode::Enter)]);
+
+        assert!(matches!(
+            detail.update(),
+            Some(Message::OpenAttachment(attachment)) if attachment == "/home/user/bill.pdf"
+        ));
+    }
+
+    #[test]
+    #[serial]
+    fn it_shows_the_time_left_from_the_clock() {
+        init_database(":memory:", vec![crate::CORE_MIGRATIONS]).unwrap();
+
+        let reminder = create_reminder("Buy milk");
+        let clock = Rc::new(MockClock::new(
+            SimpleDate::parse_from_str("2024-04-05 07:45:00", "%Y-%m-%d %H:%M:%S").unwrap(),
+        ));
+        let detail = RemindersDetail::new(reminder.id, clock.clone());
+
+        assert_eq!(
+            "05-04-2024 08:00 (in 15 minutes)",
+            detail.format_remind_at(&reminder)
+        );
+
+        clock.set(SimpleDate::parse_from_str("2024-04-05 09:00:00", "%Y-%m-%d %H:%M:%S").unwrap());
+
+        assert_eq!(
+            "05-04-2024 08:00 (already due)",
+            detail.format_remind_at(&reminder)
+        );
+    }
+}
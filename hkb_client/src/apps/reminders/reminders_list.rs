@@ -1,19 +1,225 @@
-use crossterm::event::KeyCode;
+use crossterm::event::{Event, KeyCode, KeyModifiers};
 use hkb_core::database::services::reminders;
-use hkb_core::database::services::reminders::{ReminderData, ReminderQueryOptions};
+use hkb_core::database::services::reminders::{
+    OrderDirection, ReminderColor, ReminderData, ReminderId, ReminderOrderColumn,
+    ReminderQueryOptions,
+};
 use hkb_core::logger::info;
-use hkb_date::date::SimpleDate;
-use hkb_date::duration::HumanizedDuration;
-use ratatui::prelude::{Constraint, Direction, Frame, Layout, Rect};
-use ratatui::style::{Modifier, Style};
-use ratatui::widgets::{Block, Borders, List, ListState};
+use hkb_date::clock::Clock;
+use hkb_date::date::{DateResult, SimpleDate};
+use hkb_date::duration::{Duration, HumanizedDuration};
+use ratatui::prelude::{Alignment, Constraint, Direction, Frame, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListState, Paragraph};
+use std::ops::Range;
+use std::rc::Rc;
+use std::time::Instant;
 
+use crate::components::{
+    Command, CommandPalette, KeyBinding, SnoozeMenu, SnoozeMenuAction, WhichKey,
+};
+use crate::config::{self, ReminderListColumn};
 use crate::utils::bounded_value::BoundedValue;
+use crate::utils::highlight::highlight_matches;
+use crate::utils::{format_date, format_reminder_time};
 use crate::{app_state, events};
 
+use super::reminders_create::duplicate_reminder;
 use super::{Message, RemindersView};
 
+/// Upcoming reminders are fetched in pages of this size, as the list is scrolled
+const UPCOMING_PAGE_SIZE: usize = 100;
+/// The next page is fetched once the selection is this close to the last loaded reminder
+const PAGINATE_BUFFER: usize = 10;
+
+const EMPTY_LIST_MESSAGE: &str = "No reminders yet — press 'a' to create one";
+
+const KEY_BINDINGS: &[KeyBinding] = &[
+    KeyBinding {
+        keys: "dd",
+        description: "delete the reminder",
+    },
+    KeyBinding {
+        keys: "yi",
+        description: "copy the reminder id",
+    },
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ListCommand {
+    CreateReminder,
+    DeleteReminder,
+    DeleteOverdue,
+    AcknowledgeDue,
+    FilterByNote,
+    JumpToDate,
+    SnoozeReminder,
+    PasteReminder,
+    PinReminder,
+    MarkReminder,
+    TagMarked,
+    SetMarkedCategory,
+    ToggleMostRelevantFirst,
+}
+
+/// What the value typed for the marked reminders is assigned as
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BulkAssign {
+    Tag,
+    Category,
+}
+
+const COMMANDS: &[Command<ListCommand>] = &[
+    Command {
+        binding: KeyBinding {
+            keys: "a",
+            description: "Create reminder",
+        },
+        action: ListCommand::CreateReminder,
+    },
+    Command {
+        binding: KeyBinding {
+            keys: "dd",
+            description: "Delete the reminder",
+        },
+        action: ListCommand::DeleteReminder,
+    },
+    Command {
+        binding: KeyBinding {
+            keys: "X",
+            description: "Delete all overdue",
+        },
+        action: ListCommand::DeleteOverdue,
+    },
+    Command {
+        binding: KeyBinding {
+            keys: "c",
+            description: "Acknowledge due reminders",
+        },
+        action: ListCommand::AcknowledgeDue,
+    },
+    Command {
+        binding: KeyBinding {
+            keys: "/",
+            description: "Filter by note",
+        },
+        action: ListCommand::FilterByNote,
+    },
+    Command {
+        binding: KeyBinding {
+            keys: "g",
+            description: "Jump to date",
+        },
+        action: ListCommand::JumpToDate,
+    },
+    Command {
+        binding: KeyBinding {
+            keys: "s",
+            description: "Snooze the reminder",
+        },
+        action: ListCommand::SnoozeReminder,
+    },
+    Command {
+        binding: KeyBinding {
+            keys: "p",
+            description: "Paste the yanked reminder",
+        },
+        action: ListCommand::PasteReminder,
+    },
+    Command {
+        binding: KeyBinding {
+            keys: "P",
+            description: "Pin or unpin the reminder",
+        },
+        action: ListCommand::PinReminder,
+    },
+    Command {
+        binding: KeyBinding {
+            keys: "v",
+            description: "Mark or unmark the reminder",
+        },
+        action: ListCommand::MarkReminder,
+    },
+    Command {
+        binding: KeyBinding {
+            keys: "T",
+            description: "Tag the marked reminders",
+        },
+        action: ListCommand::TagMarked,
+    },
+    Command {
+        binding: KeyBinding {
+            keys: "C",
+            description: "Set the category of the marked reminders",
+        },
+        action: ListCommand::SetMarkedCategory,
+    },
+    Command {
+        binding: KeyBinding {
+            keys: "o",
+            description: "Toggle most relevant first",
+        },
+        action: ListCommand::ToggleMostRelevantFirst,
+    },
+];
+
+/// Dot in front of a reminder labeled with a color
+fn color_marker(color: ReminderColor) -> Span<'static> {
+    let color = match color {
+        ReminderColor::Red => Color::Red,
+        ReminderColor::Orange => Color::Rgb(255, 165, 0),
+        ReminderColor::Yellow => Color::Yellow,
+        ReminderColor::Green => Color::Green,
+        ReminderColor::Blue => Color::Blue,
+        ReminderColor::Purple => Color::Magenta,
+    };
+
+    Span::styled("● ", Style::default().fg(color))
+}
+
+/// Parses the date typed after pressing `g`, a date without a time is its start
+fn parse_jump_date(query: &str) -> DateResult<SimpleDate> {
+    SimpleDate::parse_flexible(query)
+        .or_else(|_| SimpleDate::parse_flexible(format!("{} 00:00", query.trim())))
+}
+
+/// Rows that fit in `height`, scrolled just enough to keep the selection in view.
+/// Only these are turned into lines, so a long list costs as much to render as a short one.
+fn visible_rows(state: &mut ListState, height: usize, len: usize) -> Range<usize> {
+    let height = height.max(1);
+    let mut offset = state.offset();
+
+    if let Some(selected) = state.selected() {
+        if selected < offset {
+            offset = selected;
+        } else if selected >= offset + height {
+            offset = selected + 1 - height;
+        }
+    }
+
+    offset = offset.min(len);
+    *state.offset_mut() = offset;
+
+    offset..(offset + height).min(len)
+}
+
+// pinned reminders are listed first, the rest by their time
+fn pinned_first_order<'a>() -> [ReminderQueryOptions<'a>; 2] {
+    [
+        ReminderQueryOptions::OrderBy {
+            column: ReminderOrderColumn::Pinned,
+            direction: OrderDirection::Desc,
+        },
+        ReminderQueryOptions::OrderBy {
+            column: ReminderOrderColumn::RemindAt,
+            direction: OrderDirection::Asc,
+        },
+    ]
+}
+
 pub struct RemindersList {
+    clock: Rc<dyn Clock>,
     selected: BoundedValue,
 
     today_reminders: Vec<ReminderData>,
@@ -21,11 +227,43 @@ pub struct RemindersList {
 
     upcoming_reminders: Vec<ReminderData>,
     upcoming_reminders_state: ListState,
+    // false once a page comes back short, as there is nothing left to fetch
+    has_more_upcoming: bool,
+
+    // amount of past reminders waiting for a confirmation to be deleted
+    pending_delete: Option<usize>,
+
+    // query being typed after pressing `/`
+    filter_query: Option<String>,
+    // only reminders with a note containing it are listed
+    note_filter: Option<String>,
+
+    // date being typed after pressing `g`, with why the last submitted one could not be jumped to
+    jump_query: Option<String>,
+    jump_error: Option<String>,
+
+    which_key: WhichKey,
+    command_palette: CommandPalette<ListCommand>,
+
+    // picked up reminder, with the day it will be dropped on
+    moving: Option<(ReminderId, SimpleDate)>,
+
+    // reminder being snoozed, with the menu of presets
+    snoozing: Option<(ReminderId, SnoozeMenu)>,
+
+    // reminders marked with `v`, tagged or given a category all at once
+    marked: Vec<ReminderId>,
+    // tag or category being typed for the marked reminders, after pressing `T` or `C`
+    bulk_query: Option<(BulkAssign, String)>,
+
+    // today's reminders are listed by how close they are to now, instead of pinned first
+    most_relevant_first: bool,
 }
 
-impl Default for RemindersList {
-    fn default() -> Self {
+impl RemindersList {
+    pub fn new(clock: Rc<dyn Clock>) -> Self {
         Self {
+            clock,
             selected: BoundedValue::new(0, 0, 0),
 
             today_reminders: vec![],
@@ -33,42 +271,648 @@ impl Default for RemindersList {
 
             upcoming_reminders: vec![],
             upcoming_reminders_state: ListState::default().with_selected(None),
+            has_more_upcoming: false,
+
+            pending_delete: None,
+
+            filter_query: None,
+            note_filter: None,
+
+            jump_query: None,
+            jump_error: None,
+
+            which_key: WhichKey::new(KEY_BINDINGS),
+            command_palette: CommandPalette::new(COMMANDS),
+
+            moving: None,
+
+            snoozing: None,
+
+            marked: vec![],
+            bulk_query: None,
+
+            most_relevant_first: false,
         }
     }
 }
 
 impl RemindersList {
-    fn format_reminder(&self, reminder: &ReminderData) -> String {
-        let duration = {
-            let duration = reminder.remind_at - SimpleDate::local();
+    fn format_column(&self, reminder: &ReminderData, column: ReminderListColumn) -> String {
+        match column {
+            ReminderListColumn::Note if reminder.pinned => {
+                format!("[pinned] {}", reminder.display_note())
+            }
+            ReminderListColumn::Note => reminder.display_note().into_owned(),
+            ReminderListColumn::Due => {
+                let duration = reminder.remind_at - self.clock.now();
 
-            if duration.is_zero() {
-                "already due".to_string()
-            } else {
-                duration.to_human_string()
+                if duration.is_zero() {
+                    "already due".to_string()
+                } else {
+                    duration.to_human_string()
+                }
             }
+            ReminderListColumn::Date => format_date(&reminder.remind_at, "%d-%m-%Y"),
+            ReminderListColumn::Time => format_reminder_time(reminder),
+            ReminderListColumn::Attachment => reminder
+                .attachment
+                .as_ref()
+                .map(|attachment| format!("[{attachment}]"))
+                .unwrap_or_default(),
+        }
+    }
+
+    fn format_reminder(&self, reminder: &ReminderData, columns: &[ReminderListColumn]) -> String {
+        columns
+            .iter()
+            .map(|column| self.format_column(reminder, *column))
+            .filter(|value| !value.is_empty())
+            .collect::<Vec<String>>()
+            .join(" | ")
+    }
+
+    /// Same as `format_reminder`, with the matches of the note filter highlighted
+    fn format_reminder_line(
+        &self,
+        reminder: &ReminderData,
+        columns: &[ReminderListColumn],
+    ) -> Line<'static> {
+        let Some(filter) = self.note_filter.as_deref() else {
+            return Line::raw(self.format_reminder(reminder, columns));
         };
-        let date = reminder.remind_at.format("%d-%m-%Y");
+        let mut spans = vec![];
+
+        for column in columns {
+            let value = self.format_column(reminder, *column);
+
+            if value.is_empty() {
+                continue;
+            }
+
+            if !spans.is_empty() {
+                spans.push(Span::raw(" | "));
+            }
+
+            if *column == ReminderListColumn::Note {
+                let note = reminder.display_note();
+                let pin_len = value.len() - note.len();
+
+                if pin_len > 0 {
+                    spans.push(Span::raw(value[..pin_len].to_owned()));
+                }
+
+                spans.extend(highlight_matches(&note, filter));
+            } else {
+                spans.push(Span::raw(value));
+            }
+        }
+
+        Line::from(spans)
+    }
 
-        format!("{} - {} ({})", reminder.note, duration, date)
+    fn selected_reminder(&self) -> Option<&ReminderData> {
+        if self.selected >= self.today_reminders.len() {
+            self.upcoming_reminders
+                .get(self.selected.get_val() - self.today_reminders.len())
+        } else {
+            self.today_reminders.get(self.selected.get_val())
+        }
     }
 
     fn create_reminder_list<'a>(&self, reminders: &[ReminderData], title: &'a str) -> List<'a> {
+        let columns = config::get_reminder_list_columns();
         let notes = reminders
             .iter()
-            .map(|reminder| self.format_reminder(reminder))
-            .collect::<Vec<String>>();
+            .map(|reminder| {
+                let mut line = self.format_reminder_line(reminder, &columns);
+
+                if let Some(color) = reminder.color {
+                    line.spans.insert(0, color_marker(color));
+                }
+
+                if self.marked.contains(&reminder.id) {
+                    line.spans.insert(0, Span::raw("+ "));
+                }
+
+                line
+            })
+            .collect::<Vec<Line>>();
 
         List::new(notes)
             .highlight_style(
                 Style::default()
-                    .bg(ratatui::style::Color::Black)
-                    .fg(ratatui::style::Color::White)
+                    .bg(Color::Black)
+                    .fg(Color::White)
                     .add_modifier(Modifier::ITALIC),
             )
             .block(Block::default().borders(Borders::ALL).title(title))
     }
 
+    // a filter with no matches is not an empty list, the filter line explains it
+    fn is_empty(&self) -> bool {
+        self.note_filter.is_none()
+            && self.today_reminders.is_empty()
+            && self.upcoming_reminders.is_empty()
+    }
+
+    fn render_empty_state(&self, frame: &mut Frame, area: Rect) {
+        let message_area = Rect::new(area.x, area.y + area.height / 2, area.width, 1);
+
+        frame.render_widget(Block::default().borders(Borders::ALL), area);
+        frame.render_widget(
+            Paragraph::new(EMPTY_LIST_MESSAGE).alignment(Alignment::Center),
+            message_area,
+        );
+    }
+
+    fn delete_past_reminders(&mut self) -> Option<Message> {
+        let option = ReminderQueryOptions::RemindAtLe {
+            date: self.clock.now(),
+        };
+
+        match reminders::preview_delete(option) {
+            Ok(0) | Err(_) => None,
+            Ok(rows) if reminders::requires_delete_confirmation(rows) => {
+                self.pending_delete = Some(rows);
+
+                None
+            }
+            Ok(_) => Some(Message::DeletePastReminders),
+        }
+    }
+
+    fn update_pending_delete(&mut self) -> Option<Message> {
+        if events::has_key_event!(KeyCode::Char(c) if c == 'y') {
+            self.pending_delete = None;
+
+            return Some(Message::DeletePastReminders);
+        }
+
+        if events::has_key_event!(KeyCode::Esc)
+            || events::has_key_event!(KeyCode::Char(c) if c == 'n')
+        {
+            self.pending_delete = None;
+        }
+
+        None
+    }
+
+    fn update_on_filtering(&mut self) {
+        // Escape exits editing mode, which cancels the filter
+        if !app_state::is_editing() {
+            self.filter_query = None;
+
+            return;
+        }
+
+        let Some(mut query) = self.filter_query.take() else {
+            return;
+        };
+        let mut should_filter = false;
+
+        events::consume_key_event!(
+            KeyCode::Char(c) => {
+                query.push(c);
+            }
+            KeyCode::Backspace => {
+                query.pop();
+            }
+            KeyCode::Enter => {
+                should_filter = true;
+            }
+        );
+
+        if should_filter {
+            self.note_filter = Some(query).filter(|query| !query.is_empty());
+            self.selected.set_val(0);
+            self.init();
+        } else {
+            self.filter_query = Some(query);
+        }
+    }
+
+    fn update_on_bulk_assigning(&mut self) -> Option<Message> {
+        // Escape exits editing mode, which cancels the assignment but keeps the marks
+        if !app_state::is_editing() {
+            self.bulk_query = None;
+
+            return None;
+        }
+
+        let (assign, mut query) = self.bulk_query.take()?;
+        let mut should_assign = false;
+
+        events::consume_key_event!(
+            KeyCode::Char(c) => {
+                query.push(c);
+            }
+            KeyCode::Backspace => {
+                query.pop();
+            }
+            KeyCode::Enter => {
+                should_assign = true;
+            }
+        );
+
+        if !should_assign {
+            self.bulk_query = Some((assign, query));
+
+            return None;
+        }
+
+        let value = query.trim().to_owned();
+        let ids = std::mem::take(&mut self.marked);
+        app_state::set_editing(false);
+
+        match assign {
+            // an empty category clears it, while an empty tag is nothing to add
+            BulkAssign::Category => Some(Message::AssignCategory(
+                ids,
+                Some(value).filter(|value| !value.is_empty()),
+            )),
+            BulkAssign::Tag if value.is_empty() => None,
+            BulkAssign::Tag => Some(Message::TagReminders(ids, value)),
+        }
+    }
+
+    fn start_bulk_assigning(&mut self, assign: BulkAssign) {
+        if self.marked.is_empty() {
+            return;
+        }
+
+        self.bulk_query = Some((assign, String::new()));
+        app_state::set_editing(true);
+    }
+
+    fn update_on_jumping(&mut self) {
+        // Escape exits editing mode, which cancels the jump
+        if !app_state::is_editing() {
+            self.jump_query = None;
+            self.jump_error = None;
+
+            return;
+        }
+
+        let Some(mut query) = self.jump_query.take() else {
+            return;
+        };
+        let mut should_jump = false;
+
+        events::consume_key_event!(
+            KeyCode::Char(c) => {
+                query.push(c);
+            }
+            KeyCode::Backspace => {
+                query.pop();
+            }
+            KeyCode::Enter => {
+                should_jump = true;
+            }
+        );
+
+        if !should_jump {
+            self.jump_query = Some(query);
+
+            return;
+        }
+
+        let error = match parse_jump_date(&query) {
+            Ok(date) if self.jump_to(date) => None,
+            Ok(date) => Some(format!(
+                "No reminders on or after {}",
+                format_date(&date, "%d-%m-%Y")
+            )),
+            Err(_) => Some(format!("Invalid date: {query}")),
+        };
+
+        if error.is_none() {
+            app_state::set_editing(false);
+        } else {
+            self.jump_query = Some(query);
+        }
+
+        self.jump_error = error;
+    }
+
+    /// Selects the earliest reminder due on or after `date`.
+    /// The upcoming one is looked up in the database, so only the pages up to it are loaded.
+    /// False when there is no such reminder.
+    fn jump_to(&mut self, date: SimpleDate) -> bool {
+        let today_target = self
+            .today_reminders
+            .iter()
+            .enumerate()
+            .filter(|(_, reminder)| !reminder.remind_at.is_before(&date))
+            .min_by_key(|(_, reminder)| reminder.remind_at - date)
+            .map(|(i, reminder)| (i, reminder.remind_at));
+        let upcoming_target = self.first_upcoming_from(date);
+
+        let index = match (today_target, upcoming_target) {
+            (Some((_, today_remind_at)), Some(upcoming))
+                if upcoming.remind_at.is_before(&today_remind_at) =>
+            {
+                self.upcoming_index_of(&upcoming)
+            }
+            (Some((index, _)), _) => Some(index),
+            (None, Some(upcoming)) => self.upcoming_index_of(&upcoming),
+            (None, None) => None,
+        };
+
+        let Some(index) = index else {
+            return false;
+        };
+
+        self.selected.set_val(index);
+        self.sync_selection();
+
+        true
+    }
+
+    // the earliest upcoming reminder due on or after `date`, wherever it is listed
+    fn first_upcoming_from(&self, date: SimpleDate) -> Option<ReminderData> {
+        let mut options = self.upcoming_filter_options();
+        options.extend([
+            ReminderQueryOptions::RemindAtGe { date },
+            ReminderQueryOptions::OrderBy {
+                column: ReminderOrderColumn::RemindAt,
+                direction: OrderDirection::Asc,
+            },
+            ReminderQueryOptions::Limit { limit: 1 },
+        ]);
+
+        reminders::fetch_reminders(Some(options))
+            .unwrap_or_default()
+            .into_iter()
+            .next()
+    }
+
+    /// Index in the list of an upcoming reminder, loading the pages up to it.
+    /// Reminders due at the same time are equally early, so any of them can be the one selected.
+    fn upcoming_index_of(&mut self, reminder: &ReminderData) -> Option<usize> {
+        // pinned reminders are listed first, the rest by their time, see `pinned_first_order`
+        let mut listed_until = self.upcoming_filter_options();
+        listed_until.extend([
+            ReminderQueryOptions::Pinned {
+                pinned: reminder.pinned,
+            },
+            ReminderQueryOptions::RemindAtLe {
+                date: reminder.remind_at,
+            },
+        ]);
+        let mut listed = reminders::count_reminders(Some(listed_until)).ok()?;
+
+        if !reminder.pinned {
+            let mut pinned = self.upcoming_filter_options();
+            pinned.push(ReminderQueryOptions::Pinned { pinned: true });
+            listed += reminders::count_reminders(Some(pinned)).ok()?;
+        }
+
+        let offset = usize::try_from(listed).ok()?.checked_sub(1)?;
+        self.load_pages_until(offset);
+
+        (offset < self.upcoming_reminders.len()).then(|| self.today_reminders.len() + offset)
+    }
+
+    fn update_moving(&mut self) -> Option<Message> {
+        let (reminder_id, day) = self.moving.as_mut()?;
+        let reminder_id = *reminder_id;
+        let mut should_drop = false;
+        let mut should_cancel = false;
+
+        events::consume_key_event!(
+            KeyCode::Char(c) if c == 'h' || c == 'l' => {
+                let moved_day = if c == 'h' {
+                    day.sub_duration(Duration::Day(1))
+                } else {
+                    day.add_duration(Duration::Day(1))
+                };
+
+                if let Ok(moved_day) = moved_day {
+                    *day = moved_day;
+                }
+            }
+            KeyCode::Enter => {
+                should_drop = true;
+            }
+            KeyCode::Esc => {
+                should_cancel = true;
+            }
+        );
+
+        if should_cancel {
+            self.moving = None;
+
+            return None;
+        }
+
+        if !should_drop {
+            return None;
+        }
+
+        let day = *day;
+        self.moving = None;
+        let reminder = self.find_reminder(reminder_id)?;
+
+        reminder
+            .remind_at
+            .moved_to_day(day)
+            .ok()
+            .map(|remind_at| Message::MoveReminder(reminder_id, remind_at))
+    }
+
+    fn should_open_command_palette(&self) -> bool {
+        let opening_events = events::consume_if(|event| match event {
+            Event::Key(key) => match key.code {
+                KeyCode::Char(':') => true,
+                KeyCode::Char('p') => key.modifiers.contains(KeyModifiers::CONTROL),
+                _ => false,
+            },
+            _ => false,
+        });
+
+        !opening_events.is_empty()
+    }
+
+    fn run_command(&mut self, command: ListCommand) -> Option<Message> {
+        match command {
+            ListCommand::CreateReminder => Some(Message::ChangeView(super::View::Create)),
+            ListCommand::DeleteReminder => self
+                .selected_reminder()
+                .map(|reminder| Message::DeleteReminder(reminder.id)),
+            ListCommand::DeleteOverdue => self.delete_past_reminders(),
+            ListCommand::AcknowledgeDue => Some(Message::AcknowledgeAllDue),
+            ListCommand::FilterByNote => {
+                self.filter_query = Some(String::new());
+                app_state::set_editing(true);
+
+                None
+            }
+            ListCommand::JumpToDate => {
+                self.jump_query = Some(String::new());
+                app_state::set_editing(true);
+
+                None
+            }
+            ListCommand::SnoozeReminder => {
+                if let Some(reminder) = self.selected_reminder() {
+                    self.snoozing =
+                        Some((reminder.id, SnoozeMenu::new(config::get_snooze_presets())));
+                }
+
+                None
+            }
+            // the copy is due at the same time as the yanked reminder
+            ListCommand::PasteReminder => app_state::get_yanked_reminder().map(|reminder| {
+                Message::CreateReminder(duplicate_reminder(&reminder, Duration::Day(0)))
+            }),
+            ListCommand::PinReminder => self
+                .selected_reminder()
+                .map(|reminder| Message::PinReminder(reminder.id, !reminder.pinned)),
+            ListCommand::MarkReminder => {
+                let reminder_id = self.selected_reminder()?.id;
+
+                match self.marked.iter().position(|id| *id == reminder_id) {
+                    Some(index) => {
+                        self.marked.remove(index);
+                    }
+                    None => self.marked.push(reminder_id),
+                }
+
+                None
+            }
+            ListCommand::TagMarked => {
+                self.start_bulk_assigning(BulkAssign::Tag);
+
+                None
+            }
+            ListCommand::SetMarkedCategory => {
+                self.start_bulk_assigning(BulkAssign::Category);
+
+                None
+            }
+            ListCommand::ToggleMostRelevantFirst => {
+                self.most_relevant_first = !self.most_relevant_first;
+                self.init();
+
+                None
+            }
+        }
+    }
+
+    fn update_snoozing(&mut self) -> Option<Message> {
+        let (reminder_id, menu) = self.snoozing.as_mut()?;
+        let reminder_id = *reminder_id;
+
+        match menu.update()? {
+            SnoozeMenuAction::Pick(preset) => {
+                self.snoozing = None;
+
+                preset
+                    .remind_at(self.clock.now())
+                    .ok()
+                    .map(|remind_at| Message::SnoozeReminder(reminder_id, remind_at))
+            }
+            SnoozeMenuAction::Cancel => {
+                self.snoozing = None;
+
+                None
+            }
+        }
+    }
+
+    fn find_reminder(&self, id: ReminderId) -> Option<&ReminderData> {
+        self.today_reminders
+            .iter()
+            .chain(self.upcoming_reminders.iter())
+            .find(|reminder| reminder.id == id)
+    }
+
+    fn update_which_key(&mut self) {
+        for event in events::peek() {
+            let Event::Key(key) = event else {
+                continue;
+            };
+
+            match key.code {
+                KeyCode::Char(c) => self.which_key.press(c, Instant::now()),
+                KeyCode::Esc if self.which_key.pending().is_some() => {
+                    self.which_key.cancel();
+                    events::reset_key_press();
+                }
+                _ => self.which_key.cancel(),
+            }
+        }
+    }
+
+    // the reminders listed as upcoming, before they are ordered and paged
+    fn upcoming_filter_options(&self) -> Vec<ReminderQueryOptions<'_>> {
+        let mut options = vec![ReminderQueryOptions::RemindAtGe {
+            date: self.clock.now().end_of_day().unwrap(),
+        }];
+        options.extend(
+            self.note_filter
+                .as_deref()
+                .map(|text| ReminderQueryOptions::NoteLike { text }),
+        );
+
+        options
+    }
+
+    fn fetch_upcoming(&self, offset: usize, limit: usize) -> Vec<ReminderData> {
+        let mut options = self.upcoming_filter_options();
+        options.extend(pinned_first_order());
+        options.push(ReminderQueryOptions::Limit {
+            limit: limit as i64,
+        });
+        options.push(ReminderQueryOptions::Offset {
+            offset: offset as i64,
+        });
+
+        reminders::fetch_reminders(Some(options)).unwrap_or_default()
+    }
+
+    /// Fetches the next page of upcoming reminders once the selection gets close to the last loaded one.
+    /// Pages are appended, so the selected reminder stays the same.
+    fn paginate(&mut self) {
+        let upcoming_selected = self
+            .selected
+            .get_val()
+            .saturating_sub(self.today_reminders.len());
+
+        if !self.has_more_upcoming
+            || upcoming_selected + PAGINATE_BUFFER < self.upcoming_reminders.len()
+        {
+            return;
+        }
+
+        self.load_next_page();
+    }
+
+    fn load_next_page(&mut self) {
+        self.load_pages(UPCOMING_PAGE_SIZE);
+    }
+
+    // loads the pages up to the one holding the upcoming reminder at `offset`, in one fetch
+    fn load_pages_until(&mut self, offset: usize) {
+        let loaded = self.upcoming_reminders.len();
+
+        if offset < loaded || !self.has_more_upcoming {
+            return;
+        }
+
+        self.load_pages((offset + 1 - loaded).div_ceil(UPCOMING_PAGE_SIZE) * UPCOMING_PAGE_SIZE);
+    }
+
+    fn load_pages(&mut self, limit: usize) {
+        let page = self.fetch_upcoming(self.upcoming_reminders.len(), limit);
+        self.has_more_upcoming = page.len() == limit;
+        self.upcoming_reminders.extend(page);
+
+        self.selected.set_max(
+            (self.today_reminders.len() + self.upcoming_reminders.len()).saturating_sub(1),
+        );
+    }
+
     fn update_selected_reminder(&mut self) {
         if events::has_key_event!(KeyCode::Char(c) if c == 'j') {
             self.selected += 1;
@@ -76,6 +920,12 @@ impl RemindersList {
             self.selected -= 1;
         }
 
+        self.paginate();
+        self.sync_selection();
+    }
+
+    // selects the reminder in the list it is shown in, deselecting the other list
+    fn sync_selection(&mut self) {
         if self.selected >= self.today_reminders.len() {
             let upcoming_selected = self.selected.get_val() - self.today_reminders.len();
 
@@ -98,18 +948,33 @@ impl RemindersView for RemindersList {
         app_state::set_editing(false);
         app_state::enable_navigation_events();
 
-        let end_date = SimpleDate::local().end_of_day().unwrap();
-        let today_reminders_query_options = vec![ReminderQueryOptions::RemindAtBetween {
+        let now = self.clock.now();
+        let end_date = now.end_of_day().unwrap();
+        let note_filter = self.note_filter.clone();
+        let note_filter_option = || {
+            note_filter
+                .as_deref()
+                .map(|text| ReminderQueryOptions::NoteLike { text })
+        };
+        let mut today_reminders_query_options = vec![ReminderQueryOptions::RemindAtBetween {
             end_date,
-            start_date: SimpleDate::local().start_of_day().unwrap(),
+            start_date: now.start_of_day().unwrap(),
         }];
+        today_reminders_query_options.extend(note_filter_option());
+
+        if self.most_relevant_first {
+            today_reminders_query_options.push(ReminderQueryOptions::ClosestTo { now });
+        } else {
+            today_reminders_query_options.extend(pinned_first_order());
+        }
+
         self.today_reminders =
             reminders::fetch_reminders(Some(today_reminders_query_options)).unwrap_or_default();
 
-        let rest_of_reminders_query_options =
-            vec![ReminderQueryOptions::RemindAtGe { date: end_date }];
-        self.upcoming_reminders =
-            reminders::fetch_reminders(Some(rest_of_reminders_query_options)).unwrap_or_default();
+        // pages loaded while scrolling are loaded again, so the selection does not jump back
+        let upcoming_limit = self.upcoming_reminders.len().max(UPCOMING_PAGE_SIZE);
+        self.upcoming_reminders = self.fetch_upcoming(0, upcoming_limit);
+        self.has_more_upcoming = self.upcoming_reminders.len() == upcoming_limit;
 
         self.selected.set_max(
             (self.today_reminders.len() + self.upcoming_reminders.len()).saturating_sub(1),
@@ -117,28 +982,159 @@ impl RemindersView for RemindersList {
     }
 
     fn update(&mut self) -> Option<Message> {
+        if self.pending_delete.is_some() {
+            return self.update_pending_delete();
+        }
+
+        if self.filter_query.is_some() {
+            self.update_on_filtering();
+
+            return None;
+        }
+
+        if self.jump_query.is_some() {
+            self.update_on_jumping();
+
+            return None;
+        }
+
+        if self.bulk_query.is_some() {
+            return self.update_on_bulk_assigning();
+        }
+
+        if self.moving.is_some() {
+            return self.update_moving();
+        }
+
+        if self.snoozing.is_some() {
+            return self.update_snoozing();
+        }
+
+        if self.command_palette.is_open() {
+            return self
+                .command_palette
+                .update()
+                .and_then(|command| self.run_command(command));
+        }
+
+        if self.should_open_command_palette() {
+            self.command_palette.open();
+
+            return None;
+        }
+
+        if events::has_key_event!(KeyCode::Char(c) if c == '/') {
+            self.filter_query = Some(String::new());
+            app_state::set_editing(true);
+
+            return None;
+        }
+
+        if events::has_key_event!(KeyCode::Char(c) if c == 'g') {
+            return self.run_command(ListCommand::JumpToDate);
+        }
+
+        // read before the keys are pressed, as a completed sequence is no longer pending
+        let pending_sequence = self.which_key.pending().map(str::to_owned);
+        self.update_which_key();
+
+        if pending_sequence.as_deref() == Some("y")
+            && events::has_key_event!(KeyCode::Char(c) if c == 'i')
+        {
+            if let Some(reminder) = self.selected_reminder() {
+                return Some(Message::CopyToClipboard(reminder.id.to_string()));
+            }
+        }
+
+        if events::has_key_event!(KeyCode::Char(c) if c == 'X') {
+            return self.delete_past_reminders();
+        }
+
         if events::has_key_event!(KeyCode::Char(c) if c == 'a' || c == 'A') {
             return Some(Message::ChangeView(super::View::Create));
         }
 
+        if events::has_key_event!(KeyCode::Char(c) if c == 'D') {
+            if let Some(reminder) = self.selected_reminder() {
+                let data = duplicate_reminder(reminder, Duration::Day(1));
+
+                return Some(Message::ChangeView(super::View::CreateFrom(data)));
+            }
+        }
+
+        if events::has_key_event!(KeyCode::Char(c) if c == 'c') {
+            return Some(Message::AcknowledgeAllDue);
+        }
+
+        if events::has_key_event!(KeyCode::Char(c) if c == 'y') {
+            if let Some(reminder) = self.selected_reminder() {
+                app_state::yank_reminder(reminder.clone());
+
+                return None;
+            }
+        }
+
+        if events::has_key_event!(KeyCode::Char(c) if c == 'p') {
+            if let Some(message) = self.run_command(ListCommand::PasteReminder) {
+                return Some(message);
+            }
+        }
+
+        if events::has_key_event!(KeyCode::Char(c) if c == 'P') {
+            if let Some(message) = self.run_command(ListCommand::PinReminder) {
+                return Some(message);
+            }
+        }
+
+        if events::has_key_event!(KeyCode::Char(c) if c == 'v') {
+            return self.run_command(ListCommand::MarkReminder);
+        }
+
+        if events::has_key_event!(KeyCode::Char(c) if c == 'T') {
+            return self.run_command(ListCommand::TagMarked);
+        }
+
+        if events::has_key_event!(KeyCode::Char(c) if c == 'C') {
+            return self.run_command(ListCommand::SetMarkedCategory);
+        }
+
+        if events::has_key_event!(KeyCode::Char(c) if c == 'm') {
+            if let Some(reminder) = self.selected_reminder() {
+                self.moving = Some((reminder.id, reminder.remind_at));
+
+                return None;
+            }
+        }
+
+        if events::has_key_event!(KeyCode::Char(c) if c == 's') {
+            return self.run_command(ListCommand::SnoozeReminder);
+        }
+
+        if events::has_key_event!(KeyCode::Char(c) if c == 'o') {
+            return self.run_command(ListCommand::ToggleMostRelevantFirst);
+        }
+
+        if events::has_key_event!(KeyCode::Char(c) if c == 'W') {
+            if let Some(reminder) = self.selected_reminder() {
+                return Some(Message::SetAllDay(reminder.id, !reminder.all_day));
+            }
+        }
+
         if (events::has_key_event!(KeyCode::Backspace)
             || events::has_key_event!(KeyCode::Char(c) if c == 'd'))
             && events::is_pressed_at_least('d', 2)
-            && (!self.today_reminders.is_empty() || !self.upcoming_reminders.is_empty())
         {
-            events::reset_key_press();
+            if let Some(reminder) = self.selected_reminder() {
+                events::reset_key_press();
 
-            let reminder = {
-                if self.selected >= self.today_reminders.len() {
-                    self.upcoming_reminders
-                        .get(self.selected.get_val() - self.today_reminders.len())
-                        .unwrap()
-                } else {
-                    self.today_reminders.get(self.selected.get_val()).unwrap()
-                }
-            };
+                return Some(Message::DeleteReminder(reminder.id));
+            }
+        }
 
-            return Some(Message::DeleteReminder(reminder.id));
+        if events::has_key_event!(KeyCode::Enter) {
+            if let Some(reminder) = self.selected_reminder() {
+                return Some(Message::ChangeView(super::View::Detail(reminder.id)));
+            }
         }
 
         self.update_selected_reminder();
@@ -156,24 +1152,736 @@ impl RemindersView for RemindersList {
             ],
         )
         .split(area);
-        let vertical_split_layout = Layout::new(
-            Direction::Vertical,
-            [Constraint::Percentage(30), Constraint::Percentage(70)],
+
+        if let Some(query) = self.jump_query.as_ref() {
+            let prompt = match self.jump_error.as_ref() {
+                Some(error) => format!("Jump to date: {query} ({error})"),
+                None => format!("Jump to date: {query}"),
+            };
+
+            frame.render_widget(Paragraph::new(prompt), layout[0]);
+        } else if let Some((assign, query)) = self.bulk_query.as_ref() {
+            let prompt = match assign {
+                BulkAssign::Tag => format!("Tag {} marked reminders: {query}", self.marked.len()),
+                BulkAssign::Category => {
+                    format!(
+                        "Category of {} marked reminders: {query}",
+                        self.marked.len()
+                    )
+                }
+            };
+
+            frame.render_widget(Paragraph::new(prompt), layout[0]);
+        } else if let Some(query) = self.filter_query.as_ref() {
+            frame.render_widget(Paragraph::new(format!("/{query}")), layout[0]);
+        } else if let Some(filter) = self.note_filter.as_ref() {
+            frame.render_widget(
+                Paragraph::new(format!("Filtered by \"{filter}\" (/ to change)")),
+                layout[0],
+            );
+        }
+
+        if self.is_empty() {
+            self.render_empty_state(frame, layout[1]);
+        } else {
+            let vertical_split_layout = Layout::new(
+                Direction::Vertical,
+                [Constraint::Percentage(30), Constraint::Percentage(70)],
+            )
+            .split(layout[1]);
+
+            let area = vertical_split_layout[0];
+            let rows = visible_rows(
+                &mut self.today_reminders_state,
+                area.height.saturating_sub(2) as usize,
+                self.today_reminders.len(),
+            );
+            let list =
+                self.create_reminder_list(&self.today_reminders[rows.clone()], "Today Reminders");
+            let selected = self.today_reminders_state.selected();
+            frame.render_stateful_widget(
+                list,
+                area,
+                &mut ListState::default()
+                    .with_selected(selected.map(|selected| selected.saturating_sub(rows.start))),
+            );
+
+            let area = vertical_split_layout[1];
+            let rows = visible_rows(
+                &mut self.upcoming_reminders_state,
+                area.height.saturating_sub(2) as usize,
+                self.upcoming_reminders.len(),
+            );
+            let list = self
+                .create_reminder_list(&self.upcoming_reminders[rows.clone()], "Upcoming Reminders");
+            let selected = self.upcoming_reminders_state.selected();
+            frame.render_stateful_widget(
+                list,
+                area,
+                &mut ListState::default()
+                    .with_selected(selected.map(|selected| selected.saturating_sub(rows.start))),
+            );
+        }
+
+        if let Some((_, day)) = self.moving {
+            frame.render_widget(
+                Paragraph::new(format!(
+                    "Move to {} (h/l to change the day, Enter to drop, Esc to cancel)",
+                    format_date(&day, "%d-%m-%Y")
+                )),
+                layout[2],
+            );
+        }
+
+        if let Some(rows) = self.pending_delete {
+            frame.render_widget(
+                Paragraph::new(format!("Delete {rows} past reminders? (y/n)")),
+                layout[2],
+            );
+        }
+
+        self.which_key.render(frame, layout[1]);
+        self.command_palette.render(frame, layout[1]);
+
+        if let Some((_, menu)) = self.snoozing.as_mut() {
+            menu.render(frame, layout[1]);
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.which_key.is_dirty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::testing::{buffer_lines, render_to_buffer};
+    use crate::events::testing::{inject, key_event};
+    use crate::utils::highlight::highlight_style;
+    use hkb_core::database::init_database;
+    use hkb_core::database::services::reminders::{CreateReminderData, MissPolicy};
+    use hkb_core::dtos::reminders::fakes;
+    use hkb_date::clock::MockClock;
+    use serial_test::serial;
+
+    fn date(date: &str) -> SimpleDate {
+        SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    fn create_list(now: &str) -> RemindersList {
+        RemindersList::new(Rc::new(MockClock::new(date(now))))
+    }
+
+    fn render_to_text(list: &mut RemindersList) -> String {
+        buffer_lines(&render_to_buffer(Rect::new(0, 0, 60, 12), |frame, area| {
+            list.render(frame, area)
+        }))
+        .concat()
+    }
+
+    #[test]
+    #[serial]
+    fn it_shows_a_placeholder_only_when_there_are_no_reminders() {
+        init_database(":memory:", vec![crate::CORE_MIGRATIONS]).unwrap();
+        let mut list = create_list("2024-04-05 07:00:00");
+
+        list.init();
+
+        assert!(render_to_text(&mut list).contains(EMPTY_LIST_MESSAGE));
+
+        reminders::create_reminder(CreateReminderData {
+            note: "Testing".to_owned(),
+            body: None,
+            remind_at: date("2024-04-05 08:00:00"),
+            attachment: None,
+            recurrence: None,
+            miss_policy: MissPolicy::Keep,
+        })
+        .unwrap();
+        list.init();
+
+        let text = render_to_text(&mut list);
+
+        assert!(!text.contains(EMPTY_LIST_MESSAGE));
+        assert!(text.contains("Testing"));
+    }
+
+    #[test]
+    #[serial]
+    fn it_renders_today_and_upcoming_reminders_in_their_own_lists() {
+        let mut list = create_list("2024-04-05 07:00:00");
+        let reminder = |note: &str, remind_at: &str| {
+            let mut reminder = fakes::create_reminder();
+            reminder.note = note.to_owned();
+            reminder.remind_at = date(remind_at);
+            reminder.attachment = None;
+
+            reminder
+        };
+        list.today_reminders = vec![reminder("Water the plants", "2024-04-05 09:00:00")];
+        list.upcoming_reminders = vec![reminder("Call mom", "2024-04-06 18:00:00")];
+        list.selected.set_max(1);
+        let columns = config::get_reminder_list_columns();
+        config::set_reminder_list_columns(vec![ReminderListColumn::Note, ReminderListColumn::Date]);
+
+        let lines = buffer_lines(&render_to_buffer(Rect::new(0, 0, 40, 12), |frame, area| {
+            list.render(frame, area)
+        }));
+
+        config::set_reminder_list_columns(columns);
+
+        assert_eq!(
+            vec![
+                "                                        ",
+                "┌Today Reminders───────────────────────┐",
+                "│Water the plants | 05-04-2024         │",
+                "└──────────────────────────────────────┘",
+                "┌Upcoming Reminders────────────────────┐",
+                "│Call mom | 06-04-2024                 │",
+            ],
+            lines[..6]
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn it_marks_reminders_labeled_with_a_color() {
+        let mut list = create_list("2024-04-05 07:00:00");
+        let mut labeled = fakes::create_reminder();
+        labeled.color = Some(ReminderColor::Green);
+        list.today_reminders = vec![labeled, fakes::create_reminder()];
+        // the highlight of the selected row would cover the color
+        list.today_reminders_state.select(Some(1));
+
+        let buffer = render_to_buffer(Rect::new(0, 0, 40, 20), |frame, area| {
+            list.render(frame, area)
+        });
+
+        assert_eq!("●", buffer.get(1, 2).symbol());
+        assert_eq!(Color::Green, buffer.get(1, 2).fg);
+        assert_eq!("T", buffer.get(1, 3).symbol());
+    }
+
+    #[test]
+    #[serial]
+    fn it_fetches_the_next_page_when_scrolling_past_the_loaded_reminders() {
+        init_database(":memory:", vec![crate::CORE_MIGRATIONS]).unwrap();
+        let start = date("2024-04-06 08:00:00");
+        reminders::create_reminders(
+            (0..UPCOMING_PAGE_SIZE as u32 + 5)
+                .map(|i| CreateReminderData {
+                    note: format!("Reminder {i}"),
+                    body: None,
+                    remind_at: start.add_duration(Duration::Minute(i)).unwrap(),
+                    attachment: None,
+                    recurrence: None,
+                    miss_policy: MissPolicy::Keep,
+                })
+                .collect(),
         )
-        .split(layout[1]);
+        .unwrap();
+        let mut list = create_list("2024-04-05 07:00:00");
+
+        list.init();
+
+        assert_eq!(UPCOMING_PAGE_SIZE, list.upcoming_reminders.len());
+
+        list.selected
+            .set_val(UPCOMING_PAGE_SIZE - PAGINATE_BUFFER - 1);
+        inject([key_event(KeyCode::Char('j'))]);
+        list.update();
+
+        let expected_note = format!("Reminder {}", UPCOMING_PAGE_SIZE - PAGINATE_BUFFER);
+
+        assert_eq!(UPCOMING_PAGE_SIZE + 5, list.upcoming_reminders.len());
+        assert!(!list.has_more_upcoming);
+        assert_eq!(
+            Some(&expected_note),
+            list.selected_reminder().map(|reminder| &reminder.note)
+        );
+
+        // refreshing keeps the loaded pages and the selection
+        list.init();
+
+        assert_eq!(UPCOMING_PAGE_SIZE + 5, list.upcoming_reminders.len());
+        assert_eq!(
+            Some(&expected_note),
+            list.selected_reminder().map(|reminder| &reminder.note)
+        );
+    }
+
+    #[test]
+    fn it_renders_only_the_rows_around_the_selection() {
+        let mut state = ListState::default().with_selected(Some(25));
+
+        assert_eq!(16..26, visible_rows(&mut state, 10, 1000));
+        assert_eq!(16, state.offset());
 
-        let list = self.create_reminder_list(&self.today_reminders, "Today Reminders");
-        frame.render_stateful_widget(
-            list,
-            vertical_split_layout[0],
-            &mut self.today_reminders_state,
+        state.select(Some(20));
+
+        assert_eq!(16..26, visible_rows(&mut state, 10, 1000));
+
+        state.select(Some(3));
+
+        assert_eq!(3..8, visible_rows(&mut state, 10, 8));
+    }
+
+    #[test]
+    fn it_renders_the_configured_columns_in_order() {
+        let list = create_list("2024-04-05 07:00:00");
+        let mut reminder = fakes::create_reminder();
+        reminder.remind_at = date("2024-04-05 08:00:00");
+        reminder.attachment = Some("agenda.pdf".to_owned());
+
+        assert_eq!(
+            "08:00 | Testing",
+            list.format_reminder(
+                &reminder,
+                &[ReminderListColumn::Time, ReminderListColumn::Note]
+            )
+        );
+        assert_eq!(
+            "Testing | 05-04-2024 | [agenda.pdf]",
+            list.format_reminder(
+                &reminder,
+                &[
+                    ReminderListColumn::Note,
+                    ReminderListColumn::Date,
+                    ReminderListColumn::Attachment
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn it_marks_pinned_reminders() {
+        let list = create_list("2024-04-05 07:00:00");
+        let mut reminder = fakes::create_reminder();
+        reminder.pinned = true;
+
+        assert_eq!(
+            "[pinned] Testing",
+            list.format_reminder(&reminder, &[ReminderListColumn::Note])
         );
+    }
+
+    #[test]
+    fn it_renders_all_day_reminders_without_a_time() {
+        let list = create_list("2024-04-05 07:00:00");
+        let mut reminder = fakes::create_reminder();
+        reminder.remind_at = date("2024-04-05 00:00:00");
+        reminder.all_day = true;
+
+        assert_eq!(
+            "all day | Testing",
+            list.format_reminder(
+                &reminder,
+                &[ReminderListColumn::Time, ReminderListColumn::Note]
+            )
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn it_moves_a_picked_up_reminder_to_another_day_keeping_its_time() {
+        let mut list = create_list("2024-04-05 07:00:00");
+        let mut reminder = fakes::create_reminder();
+        reminder.remind_at = date("2024-04-05 15:30:00");
+        list.upcoming_reminders = vec![reminder.clone()];
+        list.moving = Some((reminder.id, reminder.remind_at));
+
+        inject([
+            key_event(KeyCode::Char('l')),
+            key_event(KeyCode::Char('l')),
+            key_event(KeyCode::Char('h')),
+        ]);
+        assert!(list.update_moving().is_none());
+
+        inject([key_event(KeyCode::Enter)]);
+
+        assert!(matches!(
+            list.update_moving(),
+            Some(Message::MoveReminder(id, remind_at))
+                if id == reminder.id && remind_at == date("2024-04-06 15:30:00")
+        ));
+        assert!(list.moving.is_none());
+    }
+
+    #[test]
+    #[serial]
+    fn it_runs_the_command_picked_in_the_command_palette() {
+        let mut list = create_list("2024-04-05 07:00:00");
+
+        inject([Event::Key(crossterm::event::KeyEvent::new(
+            KeyCode::Char('p'),
+            KeyModifiers::CONTROL,
+        ))]);
+        assert!(list.update().is_none());
+        assert!(list.command_palette.is_open());
+
+        inject(
+            "ack"
+                .chars()
+                .map(|c| key_event(KeyCode::Char(c)))
+                .chain([key_event(KeyCode::Enter)]),
+        );
+
+        assert!(matches!(list.update(), Some(Message::AcknowledgeAllDue)));
+        assert!(!list.command_palette.is_open());
+
+        inject([key_event(KeyCode::Char(':'))]);
+        assert!(list.update().is_none());
+
+        inject(
+            "filter"
+                .chars()
+                .map(|c| key_event(KeyCode::Char(c)))
+                .chain([key_event(KeyCode::Enter)]),
+        );
+
+        assert!(list.update().is_none());
+        assert_eq!(Some(String::new()), list.filter_query);
+        assert!(app_state::is_editing());
+        app_state::set_editing(false);
+    }
+
+    #[test]
+    #[serial]
+    fn it_jumps_to_the_first_reminder_on_or_after_a_date() {
+        init_database(":memory:", vec![crate::CORE_MIGRATIONS]).unwrap();
+        let create = |remind_at: &str, pinned: bool| {
+            let reminder = reminders::create_reminder(CreateReminderData {
+                note: "Testing".to_owned(),
+                body: None,
+                remind_at: date(remind_at),
+                attachment: None,
+                recurrence: None,
+                miss_policy: MissPolicy::Keep,
+            })
+            .unwrap();
+
+            reminders::pin_reminder(reminder.id, pinned).unwrap();
+        };
+        create("2024-04-05 09:00:00", false);
+        create("2024-04-20 08:00:00", true);
+        create("2024-04-08 18:00:00", false);
+        create("2024-04-10 08:00:00", false);
+        create("2024-04-12 08:00:00", false);
+        let mut list = create_list("2024-04-05 07:00:00");
+        list.init();
+        let mut jump = |query: &str| {
+            inject([key_event(KeyCode::Char('g'))]);
+            list.update();
+            inject(
+                query
+                    .chars()
+                    .map(|c| key_event(KeyCode::Char(c)))
+                    .chain([key_event(KeyCode::Enter)]),
+            );
+            list.update();
+
+            let jumped = (list.selected.get_val(), list.jump_error.clone());
+            list.jump_query = None;
+            list.jump_error = None;
+            app_state::set_editing(false);
+
+            jumped
+        };
+
+        assert_eq!((3, None), jump("2024-04-09"));
+        assert_eq!((2, None), jump("2024-04-08 12:30"));
+        assert_eq!((1, None), jump("2024-04-13"));
+        assert_eq!(
+            (1, Some("No reminders on or after 21-04-2024".to_owned())),
+            jump("2024-04-21")
+        );
+        assert_eq!(
+            (1, Some("Invalid date: next friday".to_owned())),
+            jump("next friday")
+        );
+        assert_eq!(Some(0), list.upcoming_reminders_state.selected());
+    }
+
+    #[test]
+    #[serial]
+    fn it_loads_only_the_pages_up_to_the_reminder_jumped_to() {
+        init_database(":memory:", vec![crate::CORE_MIGRATIONS]).unwrap();
+        let start = date("2024-04-06 08:00:00");
+        reminders::create_reminders(
+            (0..UPCOMING_PAGE_SIZE as u32 * 3)
+                .map(|i| CreateReminderData {
+                    note: format!("Reminder {i}"),
+                    body: None,
+                    remind_at: start.add_duration(Duration::Hour(i)).unwrap(),
+                    attachment: None,
+                    recurrence: None,
+                    miss_policy: MissPolicy::Keep,
+                })
+                .collect(),
+        )
+        .unwrap();
+        let mut list = create_list("2024-04-05 07:00:00");
+        list.init();
 
-        let list = self.create_reminder_list(&self.upcoming_reminders, "Upcoming Reminders");
-        frame.render_stateful_widget(
-            list,
-            vertical_split_layout[1],
-            &mut self.upcoming_reminders_state,
+        let jump_to = start
+            .add_duration(Duration::Hour(UPCOMING_PAGE_SIZE as u32 + 20))
+            .unwrap();
+
+        assert!(list.jump_to(jump_to.sub_duration(Duration::Minute(30)).unwrap()));
+        assert_eq!(UPCOMING_PAGE_SIZE * 2, list.upcoming_reminders.len());
+        assert!(list.has_more_upcoming);
+        assert_eq!(
+            Some(&format!("Reminder {}", UPCOMING_PAGE_SIZE + 20)),
+            list.selected_reminder().map(|reminder| &reminder.note)
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn it_snoozes_the_selected_reminder_with_a_preset() {
+        let mut list = create_list("2024-04-05 07:00:00");
+        let mut reminder = fakes::create_reminder();
+        reminder.remind_at = date("2024-04-05 07:00:00");
+        list.today_reminders = vec![reminder.clone()];
+        list.selected.set_max(0);
+
+        inject([key_event(KeyCode::Char('s'))]);
+        assert!(list.update().is_none());
+        assert!(list.snoozing.is_some());
+
+        inject([key_event(KeyCode::Char('2'))]);
+
+        assert!(matches!(
+            list.update(),
+            Some(Message::SnoozeReminder(id, remind_at))
+                if id == reminder.id && remind_at == date("2024-04-05 07:15:00")
+        ));
+        assert!(list.snoozing.is_none());
+    }
+
+    #[test]
+    #[serial]
+    fn it_pastes_the_yanked_reminder_as_a_new_one() {
+        let mut list = create_list("2024-04-05 07:00:00");
+        let mut reminder = fakes::create_reminder();
+        reminder.note = "Water the plants".to_owned();
+        reminder.remind_at = date("2024-04-05 18:00:00");
+        list.today_reminders = vec![reminder.clone()];
+        list.selected.set_max(0);
+
+        inject([key_event(KeyCode::Char('y'))]);
+
+        assert!(list.update().is_none());
+        assert_eq!(Some(reminder.clone()), app_state::get_yanked_reminder());
+
+        // the register is shared, so the reminder can be pasted after the list is left
+        let mut list = create_list("2024-04-05 07:00:00");
+
+        inject([key_event(KeyCode::Char('p'))]);
+
+        assert!(matches!(
+            list.update(),
+            Some(Message::CreateReminder(data))
+                if data == duplicate_reminder(&reminder, Duration::Day(0))
+                    && data.note == "Water the plants"
+                    && data.remind_at == reminder.remind_at
+        ));
+    }
+
+    #[test]
+    #[serial]
+    fn it_pins_the_selected_reminder_with_shift_p() {
+        let mut list = create_list("2024-04-05 07:00:00");
+        let reminder = fakes::create_reminder();
+        list.today_reminders = vec![reminder.clone()];
+        list.selected.set_max(0);
+
+        inject([key_event(KeyCode::Char('P'))]);
+
+        assert!(matches!(
+            list.update(),
+            Some(Message::PinReminder(id, true)) if id == reminder.id
+        ));
+    }
+
+    #[test]
+    #[serial]
+    fn it_tags_and_sets_the_category_of_the_marked_reminders() {
+        app_state::set_editing(false);
+        let mut list = create_list("2024-04-05 07:00:00");
+        let reminders = (1..=3)
+            .map(|id| {
+                let mut reminder = fakes::create_reminder();
+                reminder.id = ReminderId(id);
+
+                reminder
+            })
+            .collect::<Vec<_>>();
+        list.today_reminders = reminders.clone();
+        list.selected.set_max(2);
+        let assign = |list: &mut RemindersList, key: char, value: &str| {
+            inject([key_event(KeyCode::Char(key))]);
+            list.update();
+            inject(
+                value
+                    .chars()
+                    .map(|c| key_event(KeyCode::Char(c)))
+                    .chain([key_event(KeyCode::Enter)]),
+            );
+
+            list.update()
+        };
+
+        // nothing is marked yet, so there is nothing to tag
+        inject([key_event(KeyCode::Char('T'))]);
+
+        assert!(list.update().is_none());
+        assert!(list.bulk_query.is_none());
+        assert!(!app_state::is_editing());
+
+        for key in ['v', 'j', 'j', 'v'] {
+            inject([key_event(KeyCode::Char(key))]);
+            list.update();
+        }
+
+        assert_eq!(vec![reminders[0].id, reminders[2].id], list.marked);
+        assert!(matches!(
+            assign(&mut list, 'T', "work"),
+            Some(Message::TagReminders(ids, tag))
+                if ids == [reminders[0].id, reminders[2].id] && tag == "work"
+        ));
+        assert!(list.marked.is_empty());
+        assert!(!app_state::is_editing());
+
+        inject([key_event(KeyCode::Char('v'))]);
+        list.update();
+
+        assert!(matches!(
+            assign(&mut list, 'C', "errands"),
+            Some(Message::AssignCategory(ids, Some(category)))
+                if ids == [reminders[2].id] && category == "errands"
+        ));
+    }
+
+    #[test]
+    #[serial]
+    fn it_copies_the_id_of_the_selected_reminder() {
+        let mut list = create_list("2024-04-05 07:00:00");
+        let mut reminder = fakes::create_reminder();
+        reminder.id = ReminderId(42);
+        list.today_reminders = vec![reminder];
+        list.selected.set_max(0);
+
+        inject([key_event(KeyCode::Char('y'))]);
+        assert!(list.update().is_none());
+
+        inject([key_event(KeyCode::Char('i'))]);
+
+        assert!(matches!(
+            list.update(),
+            Some(Message::CopyToClipboard(text)) if text == "42"
+        ));
+        assert!(list.which_key.pending().is_none());
+    }
+
+    #[test]
+    #[serial]
+    fn it_lists_today_closest_to_now_first_when_toggled() {
+        init_database(":memory:", vec![crate::CORE_MIGRATIONS]).unwrap();
+        let mut list = create_list("2024-04-05 11:00:00");
+
+        for (note, remind_at) in [
+            ("Breakfast", "2024-04-05 07:00:00"),
+            ("Standup", "2024-04-05 10:30:00"),
+            ("Dinner", "2024-04-05 19:00:00"),
+            ("Lunch", "2024-04-05 12:00:00"),
+        ] {
+            reminders::create_reminder(CreateReminderData {
+                note: note.to_owned(),
+                body: None,
+                remind_at: date(remind_at),
+                attachment: None,
+                recurrence: None,
+                miss_policy: MissPolicy::Keep,
+            })
+            .unwrap();
+        }
+
+        let notes = |list: &RemindersList| {
+            list.today_reminders
+                .iter()
+                .map(|reminder| reminder.note.clone())
+                .collect::<Vec<String>>()
+        };
+
+        list.init();
+
+        assert_eq!(
+            vec!["Breakfast", "Standup", "Lunch", "Dinner"],
+            notes(&list)
+        );
+
+        inject([key_event(KeyCode::Char('o'))]);
+        assert!(list.update().is_none());
+
+        assert_eq!(
+            vec!["Standup", "Lunch", "Breakfast", "Dinner"],
+            notes(&list)
+        );
+
+        inject([key_event(KeyCode::Char('o'))]);
+        assert!(list.update().is_none());
+
+        assert_eq!(
+            vec!["Breakfast", "Standup", "Lunch", "Dinner"],
+            notes(&list)
+        );
+    }
+
+    #[test]
+    fn it_shows_the_time_left_until_due_from_the_clock() {
+        let clock = Rc::new(MockClock::new(date("2024-04-05 06:30:00")));
+        let list = RemindersList::new(clock.clone());
+        let mut reminder = fakes::create_reminder();
+        reminder.remind_at = date("2024-04-05 08:00:00");
+
+        assert_eq!(
+            "1 hour 30 minutes",
+            list.format_reminder(&reminder, &[ReminderListColumn::Due])
+        );
+
+        clock.advance(Duration::Hour(2));
+
+        assert_eq!(
+            "already due",
+            list.format_reminder(&reminder, &[ReminderListColumn::Due])
+        );
+    }
+
+    #[test]
+    fn it_highlights_the_note_filter_matches() {
+        let mut list = create_list("2024-04-05 07:00:00");
+        let mut reminder = fakes::create_reminder();
+        reminder.note = "Test the tests".to_owned();
+        reminder.pinned = true;
+        reminder.remind_at = date("2024-04-05 08:00:00");
+        list.note_filter = Some("TEST".to_owned());
+
+        assert_eq!(
+            Line::from(vec![
+                Span::raw("08:00"),
+                Span::raw(" | "),
+                Span::raw("[pinned] "),
+                Span::styled("Test", highlight_style()),
+                Span::raw(" the "),
+                Span::styled("test", highlight_style()),
+                Span::raw("s"),
+            ]),
+            list.format_reminder_line(
+                &reminder,
+                &[ReminderListColumn::Time, ReminderListColumn::Note]
+            )
         );
     }
 }
@@ -0,0 +1,153 @@
+use hkb_core::database::services::reminders;
+use hkb_core::database::services::reminders::{ReminderData, ReminderQueryOptions};
+use hkb_core::logger::debug;
+use hkb_date::clock::Clock;
+use hkb_date::date::SimpleDate;
+use hkb_date::duration::HumanizedDuration;
+use ratatui::prelude::{Frame, Rect};
+use ratatui::widgets::{Block, Borders, List};
+use std::rc::Rc;
+
+use crate::app_state::AppId;
+use crate::utils::format_reminder_time;
+
+use super::App;
+
+// refetching on every tick is wasteful, as reminders rarely change
+const REFRESH_INTERVAL_IN_SECONDS: u64 = 2;
+
+fn fetch_today_reminders(now: SimpleDate) -> Vec<ReminderData> {
+    let (Ok(start_date), Ok(end_date)) = (now.start_of_day(), now.end_of_day()) else {
+        return vec![];
+    };
+    let query_options = vec![ReminderQueryOptions::RemindAtBetween {
+        start_date,
+        end_date,
+    }];
+
+    reminders::fetch_reminders(Some(query_options)).unwrap_or_default()
+}
+
+pub struct TodayApp {
+    clock: Rc<dyn Clock>,
+    reminders: Vec<ReminderData>,
+    last_fetched_at: Option<SimpleDate>,
+    dirty: bool,
+}
+
+impl TodayApp {
+    pub fn new(clock: Rc<dyn Clock>) -> Self {
+        Self {
+            clock,
+            reminders: vec![],
+            last_fetched_at: None,
+            dirty: true,
+        }
+    }
+}
+
+impl TodayApp {
+    fn format_reminder(&self, reminder: &ReminderData, now: SimpleDate) -> String {
+        let duration = reminder.remind_at - now;
+        let time = format_reminder_time(reminder);
+
+        if duration.is_zero() {
+            format!("{} {} - already due", time, reminder.display_note())
+        } else {
+            format!(
+                "{} {} - {}",
+                time,
+                reminder.display_note(),
+                duration.to_human_string()
+            )
+        }
+    }
+}
+
+impl App for TodayApp {
+    fn id(&self) -> AppId {
+        "today"
+    }
+
+    fn title(&self) -> String {
+        "Today".to_string()
+    }
+
+    fn on_tick(&mut self, now: SimpleDate) {
+        if let Some(last_fetched_at) = self.last_fetched_at {
+            if (now - last_fetched_at).as_secs() < REFRESH_INTERVAL_IN_SECONDS {
+                return;
+            }
+        }
+
+        let reminders = fetch_today_reminders(now);
+        self.dirty = self.dirty || reminders != self.reminders;
+        self.reminders = reminders;
+        self.last_fetched_at = Some(now);
+
+        debug!(target: "CLIENT_TODAY", "Fetched {} reminders for today.", self.reminders.len());
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let now = self.clock.now();
+        let notes = self
+            .reminders
+            .iter()
+            .map(|reminder| self.format_reminder(reminder, now))
+            .collect::<Vec<String>>();
+
+        frame.render_widget(
+            List::new(notes).block(Block::default().borders(Borders::ALL).title("Today")),
+            area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hkb_core::database::init_database;
+    use hkb_core::database::services::reminders::{CreateReminderData, MissPolicy};
+    use hkb_date::clock::MockClock;
+    use serial_test::serial;
+
+    fn create_reminder(remind_at: &str) -> ReminderData {
+        reminders::create_reminder(CreateReminderData {
+            note: "Testing".to_owned(),
+            body: None,
+            remind_at: SimpleDate::parse_from_str(remind_at, "%Y-%m-%d %H:%M:%S").unwrap(),
+            attachment: None,
+            recurrence: None,
+            miss_policy: MissPolicy::Keep,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    #[serial]
+    fn it_shows_only_today_reminders() {
+        init_database(":memory:", vec![crate::CORE_MIGRATIONS]).unwrap();
+
+        let now = SimpleDate::parse_from_str("2024-04-05 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        create_reminder("2024-04-04 23:59:00");
+        let morning_reminder = create_reminder("2024-04-05 08:00:00");
+        let evening_reminder = create_reminder("2024-04-05 21:00:00");
+        create_reminder("2024-04-06 00:01:00");
+
+        let mut today_app = TodayApp::new(Rc::new(MockClock::new(now)));
+        today_app.on_tick(now);
+
+        assert_eq!(
+            vec![morning_reminder, evening_reminder],
+            today_app.reminders
+        );
+    }
+}
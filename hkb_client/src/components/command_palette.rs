@@ -0,0 +1,238 @@
+use crossterm::event::{Event, KeyCode};
+use ratatui::prelude::{Frame, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Clear, List, ListState};
+
+use crate::utils::fuzzy::fuzzy_filter;
+use crate::{app_state, events};
+
+use super::KeyBinding;
+
+/// An action listed in the command palette, described by the binding that runs it directly
+pub struct Command<T: 'static> {
+    pub binding: KeyBinding,
+    pub action: T,
+}
+
+/// Lists the commands matching what is typed, so actions can be run without knowing their keys
+pub struct CommandPalette<T: 'static> {
+    commands: &'static [Command<T>],
+    // the palette is open while there is a query, even an empty one
+    query: Option<String>,
+    selected: usize,
+}
+
+impl<T: Copy> CommandPalette<T> {
+    pub fn new(commands: &'static [Command<T>]) -> Self {
+        Self {
+            commands,
+            query: None,
+            selected: 0,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.query = Some(String::new());
+        self.selected = 0;
+        app_state::set_editing(true);
+    }
+
+    pub fn close(&mut self) {
+        self.query = None;
+        app_state::set_editing(false);
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.query.is_some()
+    }
+
+    /// Commands matching the query, from the best match
+    pub fn matches(&self) -> Vec<&'static Command<T>> {
+        let query = self.query.as_deref().unwrap_or_default();
+
+        fuzzy_filter(self.commands, query, |command| command.binding.description)
+    }
+
+    /// Handles the typed query and returns the action of the picked command
+    pub fn update(&mut self) -> Option<T> {
+        // Escape exits editing mode, which closes the palette
+        if !app_state::is_editing() {
+            self.query = None;
+
+            return None;
+        }
+
+        let mut query = self.query.take()?;
+        let mut selected = self.selected;
+        let mut should_run = false;
+
+        events::consume_key_event!(
+            KeyCode::Char(c) => {
+                query.push(c);
+                selected = 0;
+            }
+            KeyCode::Backspace => {
+                query.pop();
+                selected = 0;
+            }
+            KeyCode::Up => {
+                selected = selected.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                selected += 1;
+            }
+            KeyCode::Enter => {
+                should_run = true;
+            }
+        );
+
+        self.query = Some(query);
+        self.selected = selected.min(self.matches().len().saturating_sub(1));
+
+        if !should_run {
+            return None;
+        }
+
+        let action = self
+            .matches()
+            .get(self.selected)
+            .map(|command| command.action);
+        self.close();
+
+        action
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let Some(query) = self.query.as_ref() else {
+            return;
+        };
+
+        let matches = self.matches();
+        let lines = matches
+            .iter()
+            .map(|command| {
+                Line::raw(format!(
+                    "{}  {}",
+                    command.binding.description, command.binding.keys
+                ))
+            })
+            .collect::<Vec<Line>>();
+        let width = (area.width / 2).max(30).min(area.width);
+        let height = (matches.len() as u16 + 2).max(3).min(area.height);
+        let popup_area = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 3,
+            width,
+            height,
+        );
+        let list = List::new(lines)
+            .highlight_style(
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(":{query}")),
+            );
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_stateful_widget(
+            list,
+            popup_area,
+            &mut ListState::default().with_selected(Some(self.selected)),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::testing::{inject, key_event};
+    use serial_test::serial;
+
+    const COMMANDS: &[Command<u8>] = &[
+        Command {
+            binding: KeyBinding {
+                keys: "a",
+                description: "Create reminder",
+            },
+            action: 1,
+        },
+        Command {
+            binding: KeyBinding {
+                keys: "X",
+                description: "Delete all overdue",
+            },
+            action: 2,
+        },
+        Command {
+            binding: KeyBinding {
+                keys: "dd",
+                description: "Delete the reminder",
+            },
+            action: 3,
+        },
+    ];
+
+    fn descriptions(palette: &CommandPalette<u8>) -> Vec<&'static str> {
+        palette
+            .matches()
+            .iter()
+            .map(|command| command.binding.description)
+            .collect()
+    }
+
+    #[test]
+    #[serial]
+    fn it_filters_the_commands_by_the_query() {
+        let mut palette = CommandPalette::new(COMMANDS);
+        palette.open();
+
+        assert_eq!(
+            vec![
+                "Create reminder",
+                "Delete all overdue",
+                "Delete the reminder"
+            ],
+            descriptions(&palette)
+        );
+
+        inject("dov".chars().map(|c| key_event(KeyCode::Char(c))));
+
+        assert_eq!(None, palette.update());
+        assert_eq!(vec!["Delete all overdue"], descriptions(&palette));
+
+        inject([key_event(KeyCode::Esc)]);
+        app_state::set_editing(false);
+
+        assert_eq!(None, palette.update());
+        assert!(!palette.is_open());
+        events::clear();
+    }
+
+    #[test]
+    #[serial]
+    fn it_runs_the_selected_command() {
+        let mut palette = CommandPalette::new(COMMANDS);
+        palette.open();
+
+        inject(
+            "del"
+                .chars()
+                .map(|c| key_event(KeyCode::Char(c)))
+                .chain([key_event(KeyCode::Down), key_event(KeyCode::Down)]),
+        );
+
+        assert_eq!(None, palette.update());
+
+        inject([key_event(KeyCode::Enter)]);
+
+        // moving down stops at the last match
+        assert_eq!(Some(3), palette.update());
+        assert!(!palette.is_open());
+        assert!(!app_state::is_editing());
+    }
+}
@@ -1,6 +1,7 @@
-use crossterm::event::{Event, KeyCode};
+use crossterm::event::{Event, KeyCode, KeyModifiers};
 use ratatui::{
     prelude::Rect,
+    text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
@@ -8,17 +9,51 @@ use ratatui::{
 use crate::{
     app_state, events,
     focus::Focusable,
-    utils::bounded_value::{BoundValueType, BoundedValue},
+    utils::{
+        bounded_value::{BoundValueType, BoundedValue},
+        search_history,
+    },
 };
 
 use super::StatefulComponent;
 
+// offsets in the buffer are in bytes, these keep them from landing inside a multibyte char
+
+/// The char boundary at or after the offset
+fn ceil_char_boundary(buffer: &str, offset: usize) -> usize {
+    (offset..buffer.len())
+        .find(|offset| buffer.is_char_boundary(*offset))
+        .unwrap_or(buffer.len())
+}
+
+/// The char boundary at or before the offset
+fn floor_char_boundary(buffer: &str, offset: usize) -> usize {
+    (0..=offset.min(buffer.len()))
+        .rev()
+        .find(|offset| buffer.is_char_boundary(*offset))
+        .unwrap_or(0)
+}
+
+// older changes are dropped, so a long session does not keep every version of the buffer
+const MAX_UNDO_ENTRIES: usize = 100;
+
+/// The buffer and the offset of the cursor in it, before a change
+struct UndoEntry {
+    buffer: String,
+    offset: usize,
+}
+
 pub struct InputState {
     pub buffer: String,
     focused: bool,
     cursor_offset: BoundedValue,
     visible_buffer_offset: usize,
     last_render_width: u16,
+    search_query: Option<String>,
+    last_search: String,
+    undo_stack: Vec<UndoEntry>,
+    // the buffer as it was loaded or last saved, edits are compared against it
+    saved_buffer: String,
 }
 
 impl Default for InputState {
@@ -29,10 +64,36 @@ impl Default for InputState {
             cursor_offset: BoundedValue::new(0, 0, 0),
             visible_buffer_offset: 0,
             buffer: String::with_capacity(512),
+            search_query: None,
+            last_search: String::new(),
+            undo_stack: vec![],
+            saved_buffer: String::new(),
         }
     }
 }
 
+impl InputState {
+    /// Fills the buffer with a value that is already saved, so it does not count as an edit
+    pub fn load(&mut self, value: impl Into<String>) {
+        self.buffer = value.into();
+        self.mark_saved();
+    }
+
+    /// Whether the buffer was edited since it was loaded or saved, ignoring surrounding whitespace
+    pub fn is_dirty(&self) -> bool {
+        self.buffer.trim() != self.saved_buffer.trim()
+    }
+
+    pub fn mark_saved(&mut self) {
+        self.saved_buffer.clone_from(&self.buffer);
+    }
+
+    #[allow(dead_code)]
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+}
+
 impl Focusable for InputState {
     fn focus(&mut self) {
         self.focused = true;
@@ -45,19 +106,63 @@ impl Focusable for InputState {
 
 pub struct Input<'a> {
     title: &'a str,
+    prefix: &'a str,
+    suffix: &'a str,
+    snippets: &'a [(String, String)],
 }
 
 impl<'a> Input<'a> {
     pub fn new(title: &'a str) -> Self {
-        Self { title }
+        Self {
+            title,
+            prefix: "",
+            suffix: "",
+            snippets: &[],
+        }
+    }
+
+    /// Text rendered before the buffer, ex: an icon. It is not editable.
+    #[allow(dead_code)]
+    pub fn prefix(mut self, prefix: &'a str) -> Self {
+        self.prefix = prefix;
+
+        self
+    }
+
+    /// Text rendered after the buffer, ex: a unit. It is not editable.
+    #[allow(dead_code)]
+    pub fn suffix(mut self, suffix: &'a str) -> Self {
+        self.suffix = suffix;
+
+        self
+    }
+
+    /// Triggers that expand to their text once a whitespace is typed after them. Ex: `;addr`
+    pub fn snippets(mut self, snippets: &'a [(String, String)]) -> Self {
+        self.snippets = snippets;
+
+        self
     }
 }
 
 impl<'a> Input<'a> {
-    fn trimmed_buffer(&self, state: &'a InputState, area: &Rect) -> &str {
-        let area_width = area.width as usize;
+    fn prefix_width(&self) -> u16 {
+        Span::raw(self.prefix).width() as u16
+    }
+
+    /// Width left for the buffer, once the adornments are rendered
+    fn editable_width(&self, area: &Rect) -> u16 {
+        let suffix_width = Span::raw(self.suffix).width() as u16;
+
+        area.width
+            .saturating_sub(self.prefix_width())
+            .saturating_sub(suffix_width)
+    }
+
+    fn trimmed_buffer(&self, state: &'a InputState) -> &str {
+        let area_width = state.last_render_width as usize;
         let offset_end =
-            std::cmp::min(state.buffer.len(), area_width + state.visible_buffer_offset);
+            floor_char_boundary(&state.buffer, area_width + state.visible_buffer_offset);
 
         &state.buffer[state.visible_buffer_offset..offset_end]
     }
@@ -65,27 +170,41 @@ impl<'a> Input<'a> {
     fn get_max_right_cursor_pos(&self, state: &InputState) -> BoundValueType {
         std::cmp::min(
             state.buffer.len(),
-            (state.last_render_width - 1) as BoundValueType,
+            state.last_render_width.saturating_sub(1) as BoundValueType,
         )
     }
 
     fn go_left(&self, state: &mut InputState) {
+        let offset = self.get_buffer_update_offset(state);
+        let Some(c) = state.buffer[..offset].chars().next_back() else {
+            return;
+        };
+
+        // both offsets are on char boundaries, so the char before the cursor is either
+        // fully visible or fully scrolled out
         if state.cursor_offset != 0 {
-            state.cursor_offset -= 1;
-        } else if state.visible_buffer_offset != 0 {
-            state.visible_buffer_offset -= 1;
+            state.cursor_offset -= c.len_utf8();
+        } else {
+            state.visible_buffer_offset -= c.len_utf8();
         }
     }
 
     fn go_right(&self, state: &mut InputState) {
         let visible_buffer_len = (state.last_render_width as usize) + state.visible_buffer_offset;
         let max_right_pos = self.get_max_right_cursor_pos(state);
+        let offset = self.get_buffer_update_offset(state);
+        let Some(c) = state
+            .buffer
+            .get(offset..)
+            .and_then(|rest| rest.chars().next())
+        else {
+            return;
+        };
 
-        if state.cursor_offset < max_right_pos {
-            state.cursor_offset.set_max(max_right_pos);
-            state.cursor_offset += 1;
-        } else if visible_buffer_len < state.buffer.len() {
-            state.visible_buffer_offset += 1;
+        if state.cursor_offset.get_val() + c.len_utf8() <= max_right_pos
+            || visible_buffer_len < state.buffer.len()
+        {
+            self.move_cursor_to(offset + c.len_utf8(), state);
         }
     }
 
@@ -96,95 +215,194 @@ impl<'a> Input<'a> {
 
     fn go_far_right(&self, state: &mut InputState) {
         let buffer_len = state.buffer.len();
+
+        // one more cell for the cursor after the last char
+        state.visible_buffer_offset = ceil_char_boundary(
+            &state.buffer,
+            (buffer_len + 1).saturating_sub(state.last_render_width as usize),
+        );
         state
             .cursor_offset
-            .set_val(self.get_max_right_cursor_pos(state));
-
-        state.visible_buffer_offset = buffer_len.saturating_sub(state.last_render_width as usize);
+            .set_max(self.get_max_right_cursor_pos(state));
+        state
+            .cursor_offset
+            .set_val(buffer_len - state.visible_buffer_offset);
     }
 
-    fn get_char_class(&self, pos: usize, chars: &[char]) -> i8 {
-        if pos >= chars.len() {
-            return -1;
-        }
-
-        if chars[pos].is_whitespace() {
+    fn get_char_class(&self, c: char) -> i8 {
+        if c.is_whitespace() {
             0
-        } else if chars[pos].is_ascii_punctuation() {
+        } else if c.is_ascii_punctuation() {
             1
         } else {
             2
         }
     }
 
+    // motions only walk the chars they pass, so they stay fast on long notes
     fn go_end_of_word(&self, state: &mut InputState) {
-        if state.buffer.is_empty() {
+        let offset = self.get_buffer_update_offset(state);
+        let Some(rest) = state.buffer.get(offset..) else {
             return;
+        };
+        let mut chars = rest
+            .char_indices()
+            .skip(1)
+            .skip_while(|(_, c)| c.is_whitespace())
+            .peekable();
+        let Some((mut end, first)) = chars.next() else {
+            return;
+        };
+        let char_class = self.get_char_class(first);
+
+        while let Some((char_offset, _)) =
+            chars.next_if(|(_, c)| self.get_char_class(*c) == char_class)
+        {
+            end = char_offset;
         }
 
-        let chars = state.buffer.chars().collect::<Vec<char>>();
-        let mut current_pos = self.get_buffer_update_offset(state) + 1;
+        self.move_cursor_to(offset + end, state);
+    }
+
+    fn go_back_word(&self, state: &mut InputState) {
+        let offset = self.get_buffer_update_offset(state);
+        let Some(before) = state.buffer.get(..offset) else {
+            return;
+        };
+        let mut chars = before
+            .char_indices()
+            .rev()
+            .skip_while(|(_, c)| c.is_whitespace())
+            .peekable();
+        let Some((mut start, first)) = chars.next() else {
+            self.go_far_left(state);
 
-        while self.get_char_class(current_pos, &chars) == 0 {
-            current_pos += 1;
+            return;
+        };
+        let char_class = self.get_char_class(first);
+
+        while let Some((char_offset, _)) =
+            chars.next_if(|(_, c)| self.get_char_class(*c) == char_class)
+        {
+            start = char_offset;
         }
 
-        let char_class = self.get_char_class(current_pos, &chars);
+        self.move_cursor_to(start, state);
+    }
 
-        if char_class != -1 {
-            while self.get_char_class(current_pos, &chars) == char_class {
-                current_pos += 1;
-            }
+    fn find_matches(&self, state: &InputState) -> Vec<usize> {
+        if state.last_search.is_empty() {
+            return vec![];
         }
 
-        state.cursor_offset.set_val(std::cmp::min(
-            current_pos.saturating_sub(1),
-            self.get_max_right_cursor_pos(state),
-        ));
-        let visible_current_pos = current_pos - state.visible_buffer_offset;
+        // match_indices returns byte offsets that are always on a char boundary
+        state
+            .buffer
+            .match_indices(state.last_search.as_str())
+            .map(|(offset, _)| offset)
+            .collect()
+    }
+
+    fn move_cursor_to(&self, offset: usize, state: &mut InputState) {
+        let max_cursor_pos = (state.last_render_width as usize).saturating_sub(1);
 
-        if visible_current_pos >= self.get_max_right_cursor_pos(state) - 1 {
-            state.visible_buffer_offset = current_pos - state.cursor_offset.get_val() - 1;
+        if offset < state.visible_buffer_offset
+            || offset > state.visible_buffer_offset + max_cursor_pos
+        {
+            state.visible_buffer_offset =
+                ceil_char_boundary(&state.buffer, offset.saturating_sub(max_cursor_pos));
         }
+
+        state
+            .cursor_offset
+            .set_max(self.get_max_right_cursor_pos(state));
+        state
+            .cursor_offset
+            .set_val(offset - state.visible_buffer_offset);
     }
 
-    fn go_back_word(&self, state: &mut InputState) {
-        if state.buffer.is_empty() {
-            return;
+    fn go_next_match(&self, state: &mut InputState) {
+        let matches = self.find_matches(state);
+        let current_offset = self.get_buffer_update_offset(state);
+        let next_match = matches
+            .iter()
+            .find(|offset| **offset > current_offset)
+            .or(matches.first());
+
+        if let Some(offset) = next_match {
+            self.move_cursor_to(*offset, state);
         }
+    }
 
-        let chars = state.buffer.chars().collect::<Vec<char>>();
-        let mut current_pos = self.get_buffer_update_offset(state).saturating_sub(1);
+    fn go_previous_match(&self, state: &mut InputState) {
+        let matches = self.find_matches(state);
+        let current_offset = self.get_buffer_update_offset(state);
+        let previous_match = matches
+            .iter()
+            .rev()
+            .find(|offset| **offset < current_offset)
+            .or(matches.last());
 
-        while self.get_char_class(current_pos, &chars) == 0 {
-            current_pos = current_pos.saturating_sub(1);
+        if let Some(offset) = previous_match {
+            self.move_cursor_to(*offset, state);
+        }
+    }
 
-            if current_pos == 0 {
-                break;
-            }
+    fn search(&self, query: String, state: &mut InputState) {
+        state.last_search = query;
+
+        // we search from the start of the buffer if the cursor is on a match
+        // so that the first match is not skipped
+        let current_offset = self.get_buffer_update_offset(state);
+        let is_on_match = state
+            .buffer
+            .get(current_offset..)
+            .is_some_and(|rest| rest.starts_with(state.last_search.as_str()));
+
+        if !is_on_match {
+            self.go_next_match(state);
         }
+    }
 
-        let char_class = self.get_char_class(current_pos, &chars);
+    fn update_on_searching(&self, state: &mut InputState) {
+        // Escape exits editing mode, which cancels the search
+        if !app_state::is_editing() {
+            state.search_query = None;
 
-        if current_pos != 0 && char_class != -1 {
-            while self.get_char_class(current_pos, &chars) == char_class {
-                current_pos = current_pos.saturating_sub(1);
+            return;
+        }
+
+        let Some(mut query) = state.search_query.take() else {
+            return;
+        };
+        let mut should_search = false;
 
-                if current_pos == 0 {
-                    break;
+        events::consume_key_event!(
+            KeyCode::Char(c) => {
+                query.push(c);
+            }
+            KeyCode::Backspace => {
+                query.pop();
+            }
+            KeyCode::Up => {
+                if let Some(previous_query) = search_history::recall_previous() {
+                    query = previous_query;
                 }
             }
-        }
+            KeyCode::Down => {
+                query = search_history::recall_next().unwrap_or_default();
+            }
+            KeyCode::Enter => {
+                should_search = true;
+            }
+        );
 
-        if state.visible_buffer_offset > current_pos {
-            state.visible_buffer_offset -= current_pos;
-            state.cursor_offset.set_to_min();
+        if should_search {
+            search_history::push_query(query.clone());
+            app_state::set_editing(false);
+            self.search(query, state);
         } else {
-            // TOOD: check why back word is not working correctly
-            // when we have text longer than the visible input
-            state
-                .cursor_offset
-                .set_val(current_pos - state.visible_buffer_offset);
+            state.search_query = Some(query);
         }
     }
 
@@ -198,6 +416,15 @@ impl<'a> Input<'a> {
                     '$' => self.go_far_right(state),
                     'e' => self.go_end_of_word(state),
                     'b' => self.go_back_word(state),
+                    'n' => self.go_next_match(state),
+                    'N' => self.go_previous_match(state),
+                    'u' => self.undo(state),
+                    'x' => self.delete_char(state),
+                    'D' => self.delete_to_end(state),
+                    'd' if events::is_pressed_at_least('d', 2) => {
+                        events::reset_key_press();
+                        self.clear_line(state);
+                    }
                     _ => {}
                 };
 
@@ -213,7 +440,7 @@ impl<'a> Input<'a> {
                         true
                     },
                     'a' => {
-                        state.cursor_offset += 1;
+                        self.go_right(state);
 
                         true
                     },
@@ -222,6 +449,12 @@ impl<'a> Input<'a> {
 
                         true
                     },
+                    '/' => {
+                        state.search_query = Some(String::new());
+                        search_history::reset_recall();
+
+                        true
+                    },
                     _ => false,
                 };
 
@@ -230,45 +463,152 @@ impl<'a> Input<'a> {
         );
     }
 
-    fn get_buffer_update_offset(&self, state: &InputState) -> usize {
-        state.visible_buffer_offset + state.cursor_offset.get_val()
+    /// Moves the cursor to the offset after the buffer shrunk,
+    /// scrolling back if the visible part of the buffer is no longer full
+    fn move_cursor_after_delete(&self, offset: usize, state: &mut InputState) {
+        let max_cursor_pos = (state.last_render_width as usize).saturating_sub(1);
+
+        state.visible_buffer_offset = std::cmp::min(
+            state.visible_buffer_offset,
+            ceil_char_boundary(
+                &state.buffer,
+                state.buffer.len().saturating_sub(max_cursor_pos),
+            ),
+        );
+
+        self.move_cursor_to(offset, state);
     }
 
-    fn on_char(&self, c: char, state: &mut InputState) {
+    fn last_char_offset(&self, state: &InputState) -> usize {
+        state
+            .buffer
+            .char_indices()
+            .last()
+            .map(|(offset, _)| offset)
+            .unwrap_or(0)
+    }
+
+    fn push_undo(&self, state: &mut InputState) {
+        if state.undo_stack.len() >= MAX_UNDO_ENTRIES {
+            state.undo_stack.remove(0);
+        }
+
+        state.undo_stack.push(UndoEntry {
+            buffer: state.buffer.clone(),
+            offset: self.get_buffer_update_offset(state),
+        });
+    }
+
+    /// Restores the buffer as it was before the last expansion or deletion
+    fn undo(&self, state: &mut InputState) {
+        let Some(entry) = state.undo_stack.pop() else {
+            return;
+        };
+
+        state.buffer = entry.buffer;
+        self.move_cursor_after_delete(entry.offset.min(state.buffer.len()), state);
+    }
+
+    fn delete_char(&self, state: &mut InputState) {
+        let offset = self.get_buffer_update_offset(state);
+        let Some(c) = state
+            .buffer
+            .get(offset..)
+            .and_then(|rest| rest.chars().next())
+        else {
+            return;
+        };
+
+        self.push_undo(state);
+
+        state
+            .buffer
+            .replace_range(offset..offset + c.len_utf8(), "");
+
+        let offset = if offset < state.buffer.len() {
+            offset
+        } else {
+            self.last_char_offset(state)
+        };
+
+        self.move_cursor_after_delete(offset, state);
+    }
+
+    fn delete_to_end(&self, state: &mut InputState) {
         let offset = self.get_buffer_update_offset(state);
-        let first_part = &state.buffer[..offset];
-        let second_part = &state.buffer[offset..];
-        let mut buffer = String::with_capacity(first_part.len() + second_part.len() + 1);
 
-        buffer.push_str(first_part);
-        buffer.push(c);
-        buffer.push_str(second_part);
+        if offset >= state.buffer.len() || !state.buffer.is_char_boundary(offset) {
+            return;
+        }
+
+        self.push_undo(state);
+        state.buffer.truncate(offset);
 
-        state.buffer = buffer;
+        let offset = self.last_char_offset(state);
+
+        self.move_cursor_after_delete(offset, state);
+    }
 
-        if (state.cursor_offset.get_val() + 1) >= state.last_render_width as BoundValueType {
-            state.visible_buffer_offset += 1;
+    fn clear_line(&self, state: &mut InputState) {
+        if !state.buffer.is_empty() {
+            self.push_undo(state);
         }
 
-        self.go_right(state);
+        state.buffer.clear();
+        state.cursor_offset.set_max(0);
+        state.cursor_offset.set_val(0);
+        state.visible_buffer_offset = 0;
     }
 
-    fn on_backspace(&self, state: &mut InputState) {
-        if state.buffer.is_empty() {
+    fn get_buffer_update_offset(&self, state: &InputState) -> usize {
+        state.visible_buffer_offset + state.cursor_offset.get_val()
+    }
+
+    /// Replaces the trigger right before the cursor with its snippet
+    fn expand_snippet(&self, state: &mut InputState) {
+        let offset = self.get_buffer_update_offset(state);
+        let Some(before) = state.buffer.get(..offset) else {
             return;
+        };
+        let word_start = before
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace())
+            .map_or(0, |(i, c)| i + c.len_utf8());
+        let word = &before[word_start..];
+        let Some((_, text)) = self.snippets.iter().find(|(trigger, _)| trigger == word) else {
+            return;
+        };
+
+        self.push_undo(state);
+        state.buffer.replace_range(word_start..offset, text);
+        self.move_cursor_to(word_start + text.len(), state);
+    }
+
+    fn on_char(&self, c: char, state: &mut InputState) {
+        if c.is_whitespace() && !self.snippets.is_empty() {
+            self.expand_snippet(state);
         }
 
         let offset = self.get_buffer_update_offset(state);
-        let first_part = &state.buffer[..offset.saturating_sub(1)];
-        let second_part = &state.buffer[offset..];
-        let buffer_capacity = (first_part.len() + second_part.len()).saturating_sub(1);
-        let mut buffer = String::with_capacity(buffer_capacity);
 
-        buffer.push_str(first_part);
-        buffer.push_str(second_part);
+        // inserting in place, rebuilding the buffer on every key press is slow for long notes
+        state.buffer.insert(offset, c);
+
+        self.move_cursor_to(offset + c.len_utf8(), state);
+    }
+
+    fn on_backspace(&self, state: &mut InputState) {
+        let offset = self.get_buffer_update_offset(state);
+        let Some(c) = state.buffer[..offset].chars().next_back() else {
+            return;
+        };
 
-        state.buffer = buffer;
-        state.cursor_offset.sub_val(1);
+        // moving first, the char before the cursor is the one being removed
+        self.go_left(state);
+        state
+            .buffer
+            .replace_range(offset - c.len_utf8()..offset, "");
     }
 
     fn update(&self, state: &mut InputState) {
@@ -276,12 +616,29 @@ impl<'a> Input<'a> {
             .cursor_offset
             .set_max(self.get_max_right_cursor_pos(state));
 
+        if state.search_query.is_some() {
+            self.update_on_searching(state);
+
+            return;
+        }
+
         if !app_state::is_editing() {
             self.update_on_not_editing(state);
 
             return;
         }
 
+        let clear_line_events = events::consume_if(|event| match event {
+            Event::Key(key) => {
+                key.code == KeyCode::Char('u') && key.modifiers.contains(KeyModifiers::CONTROL)
+            }
+            _ => false,
+        });
+
+        if !clear_line_events.is_empty() {
+            self.clear_line(state);
+        }
+
         events::consume_key_event!(
             KeyCode::Char(c) => {
                 self.on_char(c, state);
@@ -306,20 +663,491 @@ impl<'a> StatefulComponent for Input<'a> {
         let block = Block::default().borders(Borders::ALL);
         let block_area = block.inner(area);
 
+        state.last_render_width = self.editable_width(&block_area);
+
+        // no room to edit in, the cursor would end up outside of the input
+        if state.last_render_width == 0 || block_area.height == 0 {
+            frame.render_widget(block.title(self.title.as_ref()), area);
+
+            return;
+        }
+
         if state.focused {
             self.update(state);
 
+            // the cursor offset is in bytes, while the terminal cursor moves by chars
+            let cursor_column = state.buffer[state.visible_buffer_offset..]
+                .get(..state.cursor_offset.get_val())
+                .map_or(0, |before_cursor| before_cursor.chars().count());
+
             frame.set_cursor(
-                state.cursor_offset.get_val() as u16 + block_area.x,
+                cursor_column as u16 + block_area.x + self.prefix_width(),
                 block_area.y,
             );
         }
 
-        state.last_render_width = block_area.width;
+        let line = Line::from(vec![
+            Span::raw(self.prefix),
+            Span::raw(self.trimmed_buffer(state)),
+            Span::raw(self.suffix),
+        ]);
         frame.render_widget(
-            Paragraph::new(self.trimmed_buffer(state, &area))
-                .block(block.title(self.title.as_ref())),
+            Paragraph::new(line).block(block.title(self.title.as_ref())),
             area,
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyEvent;
+    use ratatui::{backend::TestBackend, Terminal};
+    use serial_test::serial;
+    use std::{thread, time::Duration};
+
+    fn create_state(buffer: &str, last_render_width: u16) -> InputState {
+        InputState {
+            buffer: buffer.to_owned(),
+            last_render_width,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn it_is_dirty_only_when_edited_since_loaded_or_saved() {
+        let mut state = InputState::default();
+        state.load("Buy milk");
+
+        assert!(!state.is_dirty());
+
+        state.buffer.push_str(" and eggs");
+
+        assert!(state.is_dirty());
+
+        state.mark_saved();
+
+        assert!(!state.is_dirty());
+
+        state.buffer.push(' ');
+
+        assert!(!state.is_dirty());
+    }
+
+    #[test]
+    fn it_can_search_for_the_first_match() {
+        let input = Input::new("Test");
+        let mut state = create_state("buy milk and more milk", 10);
+
+        input.search("milk".to_owned(), &mut state);
+
+        assert_eq!(4, input.get_buffer_update_offset(&state));
+        assert_eq!(0, state.visible_buffer_offset);
+        assert_eq!(4, state.cursor_offset.get_val());
+    }
+
+    #[test]
+    fn it_can_cycle_through_matches_and_wrap_to_the_start() {
+        let input = Input::new("Test");
+        let mut state = create_state("buy milk and more milk", 10);
+
+        input.search("milk".to_owned(), &mut state);
+        input.go_next_match(&mut state);
+
+        assert_eq!(18, input.get_buffer_update_offset(&state));
+        assert_eq!(9, state.visible_buffer_offset);
+
+        input.go_next_match(&mut state);
+
+        assert_eq!(4, input.get_buffer_update_offset(&state));
+
+        input.go_previous_match(&mut state);
+
+        assert_eq!(18, input.get_buffer_update_offset(&state));
+    }
+
+    #[test]
+    fn it_finds_matches_on_char_boundaries() {
+        let input = Input::new("Test");
+        let mut state = create_state("купи мляко", 30);
+
+        input.search("мляко".to_owned(), &mut state);
+
+        assert_eq!("купи ".len(), input.get_buffer_update_offset(&state));
+    }
+
+    #[test]
+    fn it_deletes_the_char_under_the_cursor() {
+        let input = Input::new("Test");
+        let mut state = create_state("купи мляко", 30);
+        input.move_cursor_to("купи".len(), &mut state);
+
+        input.delete_char(&mut state);
+
+        assert_eq!("купимляко", state.buffer);
+        assert_eq!("купи".len(), input.get_buffer_update_offset(&state));
+
+        input.delete_char(&mut state);
+
+        assert_eq!("купиляко", state.buffer);
+    }
+
+    #[test]
+    fn it_does_nothing_when_deleting_a_char_at_the_end_of_the_buffer() {
+        let input = Input::new("Test");
+        let mut state = create_state("buy milk", 30);
+        input.move_cursor_to(state.buffer.len(), &mut state);
+
+        input.delete_char(&mut state);
+
+        assert_eq!("buy milk", state.buffer);
+        assert_eq!(8, input.get_buffer_update_offset(&state));
+    }
+
+    #[test]
+    fn it_deletes_until_the_end_of_the_buffer() {
+        let input = Input::new("Test");
+        let mut state = create_state("buy milk and more milk", 10);
+        input.go_far_right(&mut state);
+        input.move_cursor_to(15, &mut state);
+
+        input.delete_to_end(&mut state);
+
+        assert_eq!("buy milk and mo", state.buffer);
+        assert_eq!(14, input.get_buffer_update_offset(&state));
+        assert_eq!(6, state.visible_buffer_offset);
+    }
+
+    fn assert_line_is_cleared(state: &InputState) {
+        assert!(state.buffer.is_empty());
+        assert_eq!(0, state.cursor_offset.get_val());
+        assert_eq!(0, state.visible_buffer_offset);
+    }
+
+    #[test]
+    #[serial]
+    fn it_clears_the_line_with_dd() {
+        let input = Input::new("Test");
+        let mut state = create_state("buy milk and more milk", 10);
+        input.search("more".to_owned(), &mut state);
+
+        events::clear();
+        events::reset_key_press();
+        app_state::set_editing(false);
+
+        // key presses are only counted if there is a delay between them
+        for _ in 0..2 {
+            thread::sleep(Duration::from_millis(300));
+            events::push(Event::Key(KeyEvent::new(
+                KeyCode::Char('d'),
+                KeyModifiers::NONE,
+            )));
+        }
+
+        input.update(&mut state);
+
+        assert_line_is_cleared(&state);
+    }
+
+    #[test]
+    #[serial]
+    fn it_clears_the_line_with_ctrl_u() {
+        let input = Input::new("Test");
+        let mut state = create_state("buy milk and more milk", 10);
+        input.search("more".to_owned(), &mut state);
+
+        events::clear();
+        app_state::set_editing(true);
+        events::push(Event::Key(KeyEvent::new(
+            KeyCode::Char('u'),
+            KeyModifiers::CONTROL,
+        )));
+
+        input.update(&mut state);
+        app_state::set_editing(false);
+
+        assert_line_is_cleared(&state);
+    }
+    #[test]
+    #[serial]
+    fn it_renders_adornments_outside_of_the_buffer() {
+        let mut terminal = Terminal::new(TestBackend::new(20, 3)).unwrap();
+        let mut state = InputState::default();
+        state.focus();
+
+        let mut render = |state: &mut InputState| {
+            terminal
+                .draw(|frame| {
+                    Input::new("Duration").prefix("🔔 ").suffix(" mins").render(
+                        frame,
+                        state,
+                        frame.size(),
+                    )
+                })
+                .unwrap();
+        };
+
+        events::clear();
+        app_state::set_editing(true);
+        render(&mut state);
+
+        for c in ['1', '5'] {
+            events::push(Event::Key(KeyEvent::new(
+                KeyCode::Char(c),
+                KeyModifiers::NONE,
+            )));
+            render(&mut state);
+        }
+
+        events::clear();
+        app_state::set_editing(false);
+
+        assert_eq!("15", state.buffer);
+        // 18 inner columns, minus 3 for the prefix and 5 for the suffix
+        assert_eq!(10, state.last_render_width);
+
+        let rendered_line = (6..11)
+            .map(|x| terminal.backend().buffer().get(x, 1).symbol())
+            .collect::<String>();
+
+        assert_eq!(" mins", rendered_line);
+        // the border, the prefix and the two typed chars
+        assert_eq!((6, 1), terminal.get_cursor().unwrap());
+    }
+    #[test]
+    #[serial]
+    fn it_renders_in_areas_too_narrow_to_edit_in() {
+        for width in 0..4 {
+            let mut terminal = Terminal::new(TestBackend::new(width, 3)).unwrap();
+            let mut state = InputState::default();
+            state.focus();
+
+            events::clear();
+            app_state::set_editing(true);
+
+            for c in ['a', 'b'] {
+                events::push(Event::Key(KeyEvent::new(
+                    KeyCode::Char(c),
+                    KeyModifiers::NONE,
+                )));
+                terminal
+                    .draw(|frame| {
+                        Input::new("Note")
+                            .prefix("> ")
+                            .render(frame, &mut state, frame.size())
+                    })
+                    .unwrap();
+            }
+
+            events::clear();
+            app_state::set_editing(false);
+
+            assert_eq!(0, state.last_render_width);
+        }
+    }
+
+    fn snippets() -> Vec<(String, String)> {
+        vec![(";addr".to_owned(), "221B Baker Street".to_owned())]
+    }
+
+    #[test]
+    fn it_expands_a_snippet_once_its_trigger_is_complete() {
+        let snippets = snippets();
+        let input = Input::new("Test").snippets(&snippets);
+        let mut state = create_state("", 40);
+
+        for c in "meet at ;addr now;addr x;addr ".chars() {
+            input.on_char(c, &mut state);
+        }
+
+        assert_eq!("meet at 221B Baker Street now;addr x;addr ", state.buffer);
+        assert_eq!(state.buffer.len(), input.get_buffer_update_offset(&state));
+    }
+
+    #[test]
+    fn it_restores_the_trigger_on_undo() {
+        let snippets = snippets();
+        let input = Input::new("Test").snippets(&snippets);
+        let mut state = create_state("", 40);
+
+        for c in "at ;addr ".chars() {
+            input.on_char(c, &mut state);
+        }
+
+        assert_eq!("at 221B Baker Street ", state.buffer);
+
+        input.undo(&mut state);
+
+        assert_eq!("at ;addr", state.buffer);
+        assert_eq!(8, input.get_buffer_update_offset(&state));
+
+        // nothing left to undo
+        input.undo(&mut state);
+
+        assert_eq!("at ;addr", state.buffer);
+    }
+
+    #[test]
+    fn it_undoes_deletions() {
+        let input = Input::new("Test");
+        let mut state = create_state("buy milk", 30);
+        input.move_cursor_to(3, &mut state);
+
+        input.delete_to_end(&mut state);
+        input.clear_line(&mut state);
+
+        assert!(state.buffer.is_empty());
+
+        input.undo(&mut state);
+
+        assert_eq!("buy", state.buffer);
+
+        input.undo(&mut state);
+
+        assert_eq!("buy milk", state.buffer);
+        assert_eq!(3, input.get_buffer_update_offset(&state));
+    }
+
+    #[test]
+    fn it_edits_the_buffer_in_place() {
+        let input = Input::new("Test");
+        let mut state = create_state("", 40);
+        state.buffer.reserve(20_000);
+        state.buffer.push_str(&"water the plants, ".repeat(500));
+        let allocation = state.buffer.as_ptr();
+
+        input.go_far_left(&mut state);
+
+        for c in "buy milk, ".chars().cycle().take(1_000) {
+            input.on_char(c, &mut state);
+        }
+
+        for _ in 0..500 {
+            input.on_backspace(&mut state);
+        }
+
+        assert_eq!(9_500, state.buffer.len());
+        assert!(state.buffer.starts_with("buy milk, "));
+        assert!(state.buffer.ends_with("water the plants, "));
+
+        // multibyte chars are inserted and removed at their char boundary
+        for c in "купи мляко, ".chars().cycle().take(1_000) {
+            input.on_char(c, &mut state);
+        }
+
+        for _ in 0..500 {
+            input.on_backspace(&mut state);
+        }
+
+        let typed = "купи мляко, ".chars().cycle().take(500).collect::<String>();
+
+        assert_eq!(9_500 + typed.len(), state.buffer.len());
+        assert_eq!(
+            Some(typed.as_str()),
+            state.buffer.get(500..500 + typed.len())
+        );
+        assert!(state.buffer.starts_with("buy milk, "));
+        assert!(state.buffer.ends_with("water the plants, "));
+        assert!(state
+            .buffer
+            .is_char_boundary(input.get_buffer_update_offset(&state)));
+        // growing and shrinking within the capacity never moves the buffer to a new allocation
+        assert_eq!(allocation, state.buffer.as_ptr());
+    }
+
+    #[test]
+    fn it_handles_a_long_buffer() {
+        let input = Input::new("Test");
+        let mut state = create_state("", 40);
+        let note = "water the plants, ".repeat(556);
+
+        for c in note.chars() {
+            input.on_char(c, &mut state);
+        }
+
+        assert_eq!(note, state.buffer);
+        assert_eq!(note.len(), input.get_buffer_update_offset(&state));
+        assert!(input.trimmed_buffer(&state).ends_with("the plants, "));
+
+        input.go_far_left(&mut state);
+
+        let mut words = 0;
+        let mut previous_offset = 0;
+
+        loop {
+            input.go_end_of_word(&mut state);
+
+            let offset = input.get_buffer_update_offset(&state);
+
+            if offset == previous_offset {
+                break;
+            }
+
+            assert!(state.cursor_offset.get_val() < 40);
+            assert!(matches!(&note[offset..offset + 1], "r" | "e" | "s" | ","));
+
+            words += 1;
+            previous_offset = offset;
+        }
+
+        // water, the, plants and the comma
+        assert_eq!(556 * 4, words);
+
+        for _ in 0..words - 1 {
+            input.go_back_word(&mut state);
+        }
+
+        assert_eq!(0, input.get_buffer_update_offset(&state));
+
+        input.go_far_right(&mut state);
+
+        for _ in 0..note.len() {
+            input.on_backspace(&mut state);
+        }
+
+        assert!(state.buffer.is_empty());
+        assert_eq!(0, input.get_buffer_update_offset(&state));
+    }
+
+    #[test]
+    fn it_keeps_the_cursor_on_char_boundaries_with_multibyte_chars() {
+        let input = Input::new("Test");
+        let mut state = create_state("", 10);
+        let note = "éé купи мляко, ".repeat(50);
+
+        for c in note.chars() {
+            input.on_char(c, &mut state);
+        }
+
+        assert_eq!(note, state.buffer);
+        assert!(input.trimmed_buffer(&state).ends_with("ко, "));
+
+        for _ in 0..note.chars().count() {
+            input.go_left(&mut state);
+            assert!(state
+                .buffer
+                .is_char_boundary(input.get_buffer_update_offset(&state)));
+        }
+
+        assert_eq!(0, input.get_buffer_update_offset(&state));
+
+        input.go_right(&mut state);
+        input.on_char('ü', &mut state);
+
+        assert!(state.buffer.starts_with("éüé"));
+
+        input.on_backspace(&mut state);
+        input.on_backspace(&mut state);
+
+        assert!(state.buffer.starts_with("é купи"));
+        assert_eq!(0, input.get_buffer_update_offset(&state));
+
+        input.go_far_right(&mut state);
+
+        for _ in 0..note.chars().count() {
+            input.on_backspace(&mut state);
+        }
+
+        assert!(state.buffer.is_empty());
+    }
+}
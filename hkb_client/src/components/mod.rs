@@ -1,20 +1,106 @@
 pub use button::*;
+pub use command_palette::*;
+use hkb_date::date::SimpleDate;
 pub use image::*;
 pub use input::*;
 pub use navigation::*;
 use ratatui::{prelude::Rect, Frame};
+pub use snooze_menu::*;
+pub use which_key::*;
 
 mod button;
+mod command_palette;
 mod image;
 mod input;
 mod navigation;
+mod snooze_menu;
+mod which_key;
 
 pub trait Component {
     fn render(&mut self, frame: &mut Frame, area: Rect);
+
+    /// Called on every loop tick with the current time
+    fn on_tick(&mut self, _now: SimpleDate) {}
+
+    /// Whether the visible state changed since the last draw
+    fn is_dirty(&self) -> bool {
+        false
+    }
+
+    fn mark_clean(&mut self) {}
 }
 
 pub trait StatefulComponent {
     type State;
 
     fn render(&mut self, frame: &mut Frame, state: &mut Self::State, area: Rect);
+
+    /// Called on every loop tick with the current time
+    #[allow(dead_code)]
+    fn on_tick(&mut self, _state: &mut Self::State, _now: SimpleDate) {}
+
+    /// Whether the visible state changed since the last draw
+    #[allow(dead_code)]
+    fn is_dirty(&self, _state: &Self::State) -> bool {
+        false
+    }
+
+    #[allow(dead_code)]
+    fn mark_clean(&mut self, _state: &mut Self::State) {}
+}
+
+/// Helpers to assert what components render, without a terminal
+#[cfg(test)]
+pub mod testing {
+    use ratatui::{backend::TestBackend, buffer::Buffer, prelude::Rect, Frame, Terminal};
+
+    /// Renders into an in-memory buffer the size of `area`, returning the drawn cells
+    pub fn render_to_buffer(area: Rect, render: impl FnOnce(&mut Frame, Rect)) -> Buffer {
+        let mut terminal =
+            Terminal::new(TestBackend::new(area.x + area.width, area.y + area.height)).unwrap();
+        terminal.draw(|frame| render(frame, area)).unwrap();
+
+        terminal.backend().buffer().clone()
+    }
+
+    /// Each row of the buffer as text, trailing whitespace included
+    pub fn buffer_lines(buffer: &Buffer) -> Vec<String> {
+        let area = buffer.area();
+
+        (area.top()..area.bottom())
+            .map(|y| {
+                (area.left()..area.right())
+                    .map(|x| buffer.get(x, y).symbol())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Clock {
+        last_tick: Option<SimpleDate>,
+    }
+
+    impl Component for Clock {
+        fn render(&mut self, _frame: &mut Frame, _area: Rect) {}
+
+        fn on_tick(&mut self, now: SimpleDate) {
+            self.last_tick = Some(now);
+        }
+    }
+
+    #[test]
+    fn it_passes_the_tick_to_the_component() {
+        let mut clock = Clock::default();
+        let now = SimpleDate::local();
+
+        clock.on_tick(now);
+
+        assert_eq!(Some(now), clock.last_tick);
+    }
 }
@@ -1,4 +1,6 @@
 use crossterm::event::KeyCode;
+use hkb_core::database::services::reminders;
+use hkb_date::date::SimpleDate;
 use ratatui::{
     prelude::{Color, Constraint, Direction, Layout, Rect, Style},
     widgets::{Block, Paragraph, Tabs},
@@ -6,48 +8,190 @@ use ratatui::{
 };
 
 use crate::{
-    app_state::{self, AppView},
+    app_state::{self, AppId},
+    apps::REMINDERS_APP_ID,
     events,
 };
 
 use super::Component;
 
+// the due count changes over time as well, not only when reminders change
+const DUE_COUNT_REFRESH_INTERVAL_IN_SECONDS: u64 = 30;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NavigationTab {
+    pub id: AppId,
+    pub title: String,
+    /// Sub views of the tab, shown as a second row while the tab is selected
+    pub children: Vec<NavigationTab>,
+}
+
 pub struct Navigation {
     title: String,
-    views: Vec<AppView>,
+    tabs: Vec<NavigationTab>,
     selected_tab: usize,
+    due_reminders_count: i64,
+    due_count_fetched_at: Option<SimpleDate>,
+    last_seen_reminders_change: usize,
+    // apps can switch their sub view on their own, so we track what was drawn
+    rendered_sub_view: Option<AppId>,
+    dirty: bool,
 }
 
 impl Navigation {
-    pub fn new(title: String, views: Vec<AppView>) -> Self {
+    pub fn new(title: String, tabs: Vec<NavigationTab>) -> Self {
         Self {
             title,
-            views,
+            tabs,
             selected_tab: 0,
+            due_reminders_count: 0,
+            due_count_fetched_at: None,
+            last_seen_reminders_change: app_state::get_reminders_changes(),
+            rendered_sub_view: None,
+            dirty: true,
+        }
+    }
+
+    /// Index of the selected tab
+    #[allow(dead_code)]
+    pub fn selected(&self) -> usize {
+        self.selected_tab
+    }
+
+    /// Selects the tab and leaves picking the sub view to its app.
+    /// Indexes past the last tab are ignored.
+    pub fn select(&mut self, index: usize) {
+        let Some(tab) = self.tabs.get(index) else {
+            return;
+        };
+
+        self.selected_tab = index;
+        app_state::set_view(tab.id);
+        app_state::set_sub_view(None);
+        self.dirty = true;
+    }
+
+    /// Selects the next tab, wrapping around to the first one
+    pub fn next(&mut self) {
+        if self.tabs.is_empty() {
+            return;
+        }
+
+        self.select((self.selected_tab + 1) % self.tabs.len());
+    }
+
+    /// Selects the previous tab, wrapping around to the last one
+    #[allow(dead_code)]
+    pub fn prev(&mut self) {
+        if self.tabs.is_empty() {
+            return;
+        }
+
+        self.select((self.selected_tab + self.tabs.len() - 1) % self.tabs.len());
+    }
+
+    /// Sub views of the selected tab
+    pub fn sub_views(&self) -> &[NavigationTab] {
+        self.tabs
+            .get(self.selected_tab)
+            .map(|tab| tab.children.as_slice())
+            .unwrap_or_default()
+    }
+
+    fn selected_sub_view(&self) -> Option<usize> {
+        let sub_view = app_state::get_sub_view()?;
+
+        self.sub_views()
+            .iter()
+            .position(|child| child.id == sub_view)
+    }
+
+    fn select_next_sub_view(&mut self) {
+        let sub_views = self.sub_views();
+
+        if sub_views.is_empty() {
+            return;
         }
+
+        let next = self
+            .selected_sub_view()
+            .map_or(0, |index| (index + 1) % sub_views.len());
+
+        app_state::set_sub_view(Some(sub_views[next].id));
+        self.dirty = true;
+    }
+
+    /// Rows needed to render, the sub views take a second one
+    pub fn height(&self) -> u16 {
+        if self.sub_views().is_empty() {
+            1
+        } else {
+            2
+        }
+    }
+
+    fn set_due_reminders_count(&mut self, count: i64) {
+        if self.due_reminders_count != count {
+            self.due_reminders_count = count;
+            self.dirty = true;
+        }
+    }
+
+    fn tab_labels(&self) -> Vec<String> {
+        self.tabs
+            .iter()
+            .map(|tab| {
+                if tab.id == REMINDERS_APP_ID && self.due_reminders_count > 0 {
+                    format!("{} ({})", tab.title, self.due_reminders_count)
+                } else {
+                    tab.title.clone()
+                }
+            })
+            .collect()
     }
 }
 
 impl Component for Navigation {
-    fn render(&mut self, frame: &mut Frame, area: Rect) {
-        if !app_state::should_ignore_navigation_events() && events::has_key_event!(KeyCode::Tab) {
-            self.selected_tab += 1;
-
-            if self.selected_tab >= self.views.len() {
-                self.selected_tab = 0;
+    fn on_tick(&mut self, now: SimpleDate) {
+        let reminders_changes = app_state::get_reminders_changes();
+        let is_stale = match self.due_count_fetched_at {
+            Some(fetched_at) => {
+                (now - fetched_at).as_secs() >= DUE_COUNT_REFRESH_INTERVAL_IN_SECONDS
             }
+            None => true,
+        };
 
-            app_state::set_view(self.views[self.selected_tab]);
+        if !is_stale && reminders_changes == self.last_seen_reminders_change {
+            return;
+        }
+
+        self.set_due_reminders_count(reminders::count_due(now).unwrap_or_default());
+        self.due_count_fetched_at = Some(now);
+        self.last_seen_reminders_change = reminders_changes;
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        if !app_state::should_ignore_navigation_events() {
+            if events::has_key_event!(KeyCode::Tab) {
+                self.next();
+            } else if events::has_key_event!(KeyCode::BackTab) {
+                self.select_next_sub_view();
+            }
         }
 
         let block = Block::new().style(Style::default().bg(Color::Black));
         let block_inner = block.inner(area);
+        let rows = Layout::new(
+            Direction::Vertical,
+            [Constraint::Length(1), Constraint::Min(0)],
+        )
+        .split(block_inner);
         let layout = Layout::new(
             Direction::Horizontal,
             [Constraint::Percentage(30), Constraint::Percentage(70)],
         )
-        .split(block_inner);
-        let tabs = Tabs::new(self.views.iter().map(|view| view.to_string()))
+        .split(rows[0]);
+        let tabs = Tabs::new(self.tab_labels())
             .select(self.selected_tab)
             .style(Style::default().fg(Color::Gray))
             .highlight_style(Style::default().fg(Color::White))
@@ -60,5 +204,174 @@ impl Component for Navigation {
             layout[0],
         );
         frame.render_widget(tabs, layout[1]);
+
+        let sub_view = app_state::get_sub_view();
+
+        if !self.sub_views().is_empty() && rows[1].height > 0 {
+            let sub_layout = Layout::new(
+                Direction::Horizontal,
+                [Constraint::Percentage(30), Constraint::Percentage(70)],
+            )
+            .split(rows[1]);
+            let sub_tabs = Tabs::new(
+                self.sub_views()
+                    .iter()
+                    .map(|child| child.title.clone())
+                    .collect::<Vec<String>>(),
+            )
+            .select(self.selected_sub_view().unwrap_or(usize::MAX))
+            .style(Style::default().fg(Color::DarkGray))
+            .highlight_style(Style::default().fg(Color::White))
+            .divider("")
+            .padding_left(" ".repeat(3));
+
+            frame.render_widget(sub_tabs, sub_layout[1]);
+        }
+
+        self.rendered_sub_view = sub_view;
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty || self.rendered_sub_view != app_state::get_sub_view()
+    }
+
+    fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::events::testing::{inject, key_event};
+    use ratatui::{backend::TestBackend, Terminal};
+    use serial_test::serial;
+
+    fn tab(id: AppId, title: &str) -> NavigationTab {
+        NavigationTab {
+            id,
+            title: title.to_string(),
+            children: vec![],
+        }
+    }
+
+    #[test]
+    fn it_shows_the_due_reminders_count_in_the_reminders_tab() {
+        let mut navigation = Navigation::new(
+            "HKB".to_string(),
+            vec![tab("main", "Main"), tab(REMINDERS_APP_ID, "Reminders")],
+        );
+
+        assert_eq!(vec!["Main", "Reminders"], navigation.tab_labels());
+
+        navigation.due_reminders_count = 3;
+
+        assert_eq!(vec!["Main", "Reminders (3)"], navigation.tab_labels());
+    }
+
+    #[test]
+    #[serial]
+    fn it_shows_the_sub_views_of_the_selected_tab() {
+        let mut reminders_tab = tab(REMINDERS_APP_ID, "Reminders");
+        reminders_tab.children = vec![tab("list", "List"), tab("create", "New")];
+        let mut navigation =
+            Navigation::new("HKB".to_string(), vec![tab("main", "Main"), reminders_tab]);
+        let mut terminal = Terminal::new(TestBackend::new(30, 2)).unwrap();
+
+        navigation.select(0);
+
+        assert!(navigation.sub_views().is_empty());
+        assert_eq!(1, navigation.height());
+
+        inject([key_event(KeyCode::Tab)]);
+        terminal
+            .draw(|frame| navigation.render(frame, frame.size()))
+            .unwrap();
+
+        assert_eq!(Some(REMINDERS_APP_ID), app_state::get_view());
+        assert_eq!(2, navigation.height());
+        assert_eq!(
+            vec!["List", "New"],
+            navigation
+                .sub_views()
+                .iter()
+                .map(|child| child.title.as_str())
+                .collect::<Vec<&str>>()
+        );
+
+        // the app picks its sub view, until the user switches it
+        app_state::set_sub_view(Some("list"));
+        assert!(navigation.is_dirty());
+
+        inject([key_event(KeyCode::BackTab)]);
+        terminal
+            .draw(|frame| navigation.render(frame, frame.size()))
+            .unwrap();
+        navigation.mark_clean();
+
+        assert_eq!(Some("create"), app_state::get_sub_view());
+        assert!(!navigation.is_dirty());
+        assert_eq!(
+            "            List    New       ",
+            terminal.backend().buffer().content[30..]
+                .iter()
+                .map(|cell| cell.symbol())
+                .collect::<String>()
+        );
+
+        inject([key_event(KeyCode::BackTab)]);
+        terminal
+            .draw(|frame| navigation.render(frame, frame.size()))
+            .unwrap();
+
+        assert_eq!(Some("list"), app_state::get_sub_view());
+        app_state::set_sub_view(None);
+        events::clear();
+    }
+
+    #[test]
+    #[serial]
+    fn it_wraps_around_when_selecting_the_next_or_previous_tab() {
+        let mut navigation = Navigation::new(
+            "HKB".to_string(),
+            vec![tab("main", "Main"), tab(REMINDERS_APP_ID, "Reminders")],
+        );
+
+        navigation.select(1);
+        navigation.select(5);
+
+        assert_eq!(1, navigation.selected());
+        assert_eq!(Some(REMINDERS_APP_ID), app_state::get_view());
+
+        navigation.next();
+
+        assert_eq!(0, navigation.selected());
+        assert_eq!(Some("main"), app_state::get_view());
+
+        navigation.prev();
+
+        assert_eq!(1, navigation.selected());
+        assert_eq!(Some(REMINDERS_APP_ID), app_state::get_view());
+    }
+
+    #[test]
+    #[serial]
+    fn it_is_only_dirty_when_its_visible_state_changes() {
+        let mut navigation =
+            Navigation::new("HKB".to_string(), vec![tab(REMINDERS_APP_ID, "Reminders")]);
+        navigation.mark_clean();
+
+        navigation.set_due_reminders_count(0);
+
+        assert!(!navigation.is_dirty());
+
+        navigation.set_due_reminders_count(2);
+
+        assert!(navigation.is_dirty());
+
+        navigation.mark_clean();
+
+        assert!(!navigation.is_dirty());
     }
 }
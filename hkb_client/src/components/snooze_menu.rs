@@ -0,0 +1,290 @@
+use crossterm::event::{Event, KeyCode};
+use hkb_date::date::{DateResult, SimpleDate, Weekday};
+use hkb_date::duration::Duration;
+use ratatui::prelude::{Frame, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Clear, List, ListState};
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::events;
+
+/// Snoozing until tomorrow moves the reminder to this hour of the next day
+pub const NEXT_MORNING_HOUR: u32 = 9;
+
+/// Snoozing until tonight moves the reminder to this hour of the same day
+pub const TONIGHT_HOUR: u32 = 19;
+
+/// A quick snooze choice. Ex: `15m`, `1h`, `2d`, `tonight`, `tomorrow` or `next week`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnoozePreset {
+    Minutes(u32),
+    Hours(u32),
+    Days(u32),
+    /// Tonight, or in an hour when it is already evening
+    Tonight,
+    NextMorning,
+    /// Monday morning of next week
+    NextWeek,
+}
+
+impl SnoozePreset {
+    /// When the reminder is due again, if snoozed at `now`
+    pub fn remind_at(&self, now: SimpleDate) -> DateResult<SimpleDate> {
+        match self {
+            SnoozePreset::Minutes(minutes) => now.add_duration(Duration::Minute(*minutes)),
+            SnoozePreset::Hours(hours) => now.add_duration(Duration::Hour(*hours)),
+            SnoozePreset::Days(days) => now.add_duration(Duration::Day(*days)),
+            SnoozePreset::Tonight if now.hour() >= TONIGHT_HOUR => {
+                now.add_duration(Duration::Hour(1))
+            }
+            SnoozePreset::Tonight => {
+                let mut tonight = now;
+                tonight.set_hms(TONIGHT_HOUR, 0, 0)?;
+
+                Ok(tonight)
+            }
+            SnoozePreset::NextMorning => {
+                let mut next_morning = now.add_duration(Duration::Day(1))?.start_of_day()?;
+                next_morning.set_hour(NEXT_MORNING_HOUR)?;
+
+                Ok(next_morning)
+            }
+            SnoozePreset::NextWeek => {
+                let mut next_week = now.next_weekday(Weekday::Mon)?;
+                next_week.set_hms(NEXT_MORNING_HOUR, 0, 0)?;
+
+                Ok(next_week)
+            }
+        }
+    }
+}
+
+impl Display for SnoozePreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnoozePreset::Minutes(minutes) => write!(f, "{minutes}m"),
+            SnoozePreset::Hours(hours) => write!(f, "{hours}h"),
+            SnoozePreset::Days(days) => write!(f, "{days}d"),
+            SnoozePreset::Tonight => write!(f, "tonight"),
+            SnoozePreset::NextMorning => write!(f, "tomorrow"),
+            SnoozePreset::NextWeek => write!(f, "next week"),
+        }
+    }
+}
+
+impl FromStr for SnoozePreset {
+    type Err = String;
+
+    fn from_str(preset: &str) -> Result<Self, Self::Err> {
+        let preset = preset.trim().to_lowercase();
+
+        match preset.as_str() {
+            "tonight" => return Ok(SnoozePreset::Tonight),
+            "tomorrow" => return Ok(SnoozePreset::NextMorning),
+            "next week" => return Ok(SnoozePreset::NextWeek),
+            _ => {}
+        }
+
+        let invalid_preset = || format!("Unknown snooze preset: {preset}");
+        let (unit_offset, unit) = preset.char_indices().last().ok_or_else(invalid_preset)?;
+        let value = preset[..unit_offset]
+            .parse::<u32>()
+            .ok()
+            .filter(|value| *value > 0)
+            .ok_or_else(invalid_preset)?;
+
+        match unit {
+            'm' => Ok(SnoozePreset::Minutes(value)),
+            'h' => Ok(SnoozePreset::Hours(value)),
+            'd' => Ok(SnoozePreset::Days(value)),
+            _ => Err(invalid_preset()),
+        }
+    }
+}
+
+pub fn default_snooze_presets() -> Vec<SnoozePreset> {
+    vec![
+        SnoozePreset::Minutes(5),
+        SnoozePreset::Minutes(15),
+        SnoozePreset::Hours(1),
+        SnoozePreset::Tonight,
+        SnoozePreset::NextMorning,
+        SnoozePreset::NextWeek,
+    ]
+}
+
+pub enum SnoozeMenuAction {
+    Pick(SnoozePreset),
+    Cancel,
+}
+
+/// Lists the snooze presets, picked with j/k and Enter or by their number
+pub struct SnoozeMenu {
+    presets: Vec<SnoozePreset>,
+    selected: usize,
+}
+
+impl SnoozeMenu {
+    pub fn new(presets: Vec<SnoozePreset>) -> Self {
+        Self {
+            presets,
+            selected: 0,
+        }
+    }
+
+    pub fn update(&mut self) -> Option<SnoozeMenuAction> {
+        let mut action = None;
+        let mut selected = self.selected;
+        let presets = &self.presets;
+
+        events::consume_key_event!(
+            KeyCode::Char(c) if c == 'j' => {
+                selected = (selected + 1).min(presets.len().saturating_sub(1));
+            }
+            KeyCode::Char(c) if c == 'k' => {
+                selected = selected.saturating_sub(1);
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                let index = c.to_digit(10).unwrap_or_default() as usize;
+
+                if let Some(preset) = index.checked_sub(1).and_then(|i| presets.get(i)) {
+                    action = Some(SnoozeMenuAction::Pick(*preset));
+                }
+            }
+            KeyCode::Enter => {
+                action = presets.get(selected).copied().map(SnoozeMenuAction::Pick);
+            }
+            KeyCode::Esc => {
+                action = Some(SnoozeMenuAction::Cancel);
+            }
+        );
+
+        self.selected = selected;
+
+        action
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let lines = self
+            .presets
+            .iter()
+            .enumerate()
+            .map(|(i, preset)| Line::raw(format!("{}  {preset}", i + 1)))
+            .collect::<Vec<Line>>();
+        let width = 20.min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+        let popup_area = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        let list = List::new(lines)
+            .highlight_style(
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .block(Block::default().borders(Borders::ALL).title("Snooze"));
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_stateful_widget(
+            list,
+            popup_area,
+            &mut ListState::default().with_selected(Some(self.selected)),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::testing::{inject, key_event};
+    use serial_test::serial;
+
+    fn date(date: &str) -> SimpleDate {
+        SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn it_maps_each_preset_to_its_remind_date() {
+        let now = date("2024-04-05 22:30:00");
+
+        assert_eq!(
+            vec![
+                date("2024-04-05 22:35:00"),
+                date("2024-04-05 22:45:00"),
+                date("2024-04-05 23:30:00"),
+                date("2024-04-06 09:00:00"),
+                date("2024-04-07 22:30:00"),
+            ],
+            ["5m", "15m", "1h", "tomorrow", "2d"]
+                .into_iter()
+                .map(|preset| SnoozePreset::from_str(preset)
+                    .unwrap()
+                    .remind_at(now)
+                    .unwrap())
+                .collect::<Vec<SimpleDate>>()
+        );
+        assert!(SnoozePreset::from_str("0m").is_err());
+        assert!(SnoozePreset::from_str("next month").is_err());
+        assert!(SnoozePreset::from_str("5s").is_err());
+        assert!(SnoozePreset::from_str("").is_err());
+    }
+
+    #[test]
+    fn it_snoozes_until_tonight_tomorrow_or_next_week() {
+        let afternoon = date("2024-04-05 14:10:00");
+        let evening = date("2024-04-05 20:15:00");
+        let remind_at = |preset: &str, now: SimpleDate| {
+            SnoozePreset::from_str(preset)
+                .unwrap()
+                .remind_at(now)
+                .unwrap()
+        };
+
+        assert_eq!(date("2024-04-05 19:00:00"), remind_at("tonight", afternoon));
+        assert_eq!(date("2024-04-05 21:15:00"), remind_at("tonight", evening));
+        assert_eq!(date("2024-04-06 09:00:00"), remind_at("tomorrow", evening));
+        assert_eq!(
+            date("2024-04-08 09:00:00"),
+            remind_at("next week", afternoon)
+        );
+        assert_eq!(
+            date("2024-04-15 09:00:00"),
+            remind_at("next week", date("2024-04-08 08:00:00"))
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn it_picks_a_preset_by_its_number_or_the_selection() {
+        let mut menu = SnoozeMenu::new(default_snooze_presets());
+
+        inject([key_event(KeyCode::Char('3'))]);
+
+        assert!(matches!(
+            menu.update(),
+            Some(SnoozeMenuAction::Pick(SnoozePreset::Hours(1)))
+        ));
+
+        inject([
+            key_event(KeyCode::Char('j')),
+            key_event(KeyCode::Char('j')),
+            key_event(KeyCode::Char('j')),
+            key_event(KeyCode::Char('j')),
+            key_event(KeyCode::Char('j')),
+            key_event(KeyCode::Char('j')),
+        ]);
+        assert!(menu.update().is_none());
+
+        inject([key_event(KeyCode::Enter)]);
+
+        assert!(matches!(
+            menu.update(),
+            Some(SnoozeMenuAction::Pick(SnoozePreset::NextWeek))
+        ));
+    }
+}
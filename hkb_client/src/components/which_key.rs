@@ -0,0 +1,181 @@
+use ratatui::prelude::{Frame, Rect};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use std::time::{Duration, Instant};
+
+/// How long a sequence has to be pending before its completions are shown
+pub const WHICH_KEY_DELAY: Duration = Duration::from_millis(500);
+
+#[derive(Debug, PartialEq)]
+pub struct KeyBinding {
+    pub keys: &'static str,
+    pub description: &'static str,
+}
+
+impl KeyBinding {
+    fn completes(&self, sequence: &str) -> bool {
+        self.keys.len() > sequence.len() && self.keys.starts_with(sequence)
+    }
+}
+
+/// Tracks a pending multi-key sequence, ex: the first `d` of `dd`,
+/// and shows a popup with its completions once the user hesitates
+pub struct WhichKey {
+    bindings: &'static [KeyBinding],
+    pending: Option<(String, Instant)>,
+    shown: bool,
+}
+
+impl WhichKey {
+    pub fn new(bindings: &'static [KeyBinding]) -> Self {
+        Self {
+            bindings,
+            pending: None,
+            shown: false,
+        }
+    }
+
+    /// Extends the pending sequence with the key.
+    /// The sequence is dropped once no binding continues it.
+    pub fn press(&mut self, c: char, now: Instant) {
+        let mut sequence = self
+            .pending
+            .take()
+            .map(|(sequence, _)| sequence)
+            .unwrap_or_default();
+        sequence.push(c);
+        self.shown = false;
+
+        if self.is_prefix(&sequence) {
+            self.pending = Some((sequence, now));
+        }
+    }
+
+    pub fn cancel(&mut self) {
+        self.pending = None;
+        self.shown = false;
+    }
+
+    pub fn pending(&self) -> Option<&str> {
+        self.pending.as_ref().map(|(sequence, _)| sequence.as_str())
+    }
+
+    fn is_prefix(&self, sequence: &str) -> bool {
+        self.bindings
+            .iter()
+            .any(|binding| binding.completes(sequence))
+    }
+
+    /// Bindings that complete the pending sequence
+    pub fn completions(&self) -> Vec<&'static KeyBinding> {
+        let Some(sequence) = self.pending() else {
+            return vec![];
+        };
+
+        self.bindings
+            .iter()
+            .filter(|binding| binding.completes(sequence))
+            .collect()
+    }
+
+    pub fn should_show(&self, now: Instant) -> bool {
+        self.pending
+            .as_ref()
+            .is_some_and(|(_, pressed_at)| now.duration_since(*pressed_at) >= WHICH_KEY_DELAY)
+    }
+
+    /// Whether the popup is due but was not drawn yet
+    pub fn is_dirty(&self) -> bool {
+        !self.shown && self.should_show(Instant::now())
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        if !self.should_show(Instant::now()) {
+            return;
+        }
+
+        let lines = self
+            .completions()
+            .into_iter()
+            .map(|binding| Line::raw(format!("{}  {}", binding.keys, binding.description)))
+            .collect::<Vec<Line>>();
+        let width = lines
+            .iter()
+            .map(|line| line.width() as u16 + 2)
+            .max()
+            .unwrap_or_default()
+            .min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+        let popup_area = Rect::new(area.right() - width, area.bottom() - height, width, height);
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::default().borders(Borders::ALL)),
+            popup_area,
+        );
+        self.shown = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BINDINGS: &[KeyBinding] = &[
+        KeyBinding {
+            keys: "dw",
+            description: "delete word",
+        },
+        KeyBinding {
+            keys: "db",
+            description: "delete back word",
+        },
+        KeyBinding {
+            keys: "d$",
+            description: "delete to end",
+        },
+        KeyBinding {
+            keys: "x",
+            description: "delete char",
+        },
+    ];
+
+    #[test]
+    fn it_lists_the_completions_of_a_pending_prefix() {
+        let mut which_key = WhichKey::new(BINDINGS);
+        let now = Instant::now();
+
+        which_key.press('d', now);
+
+        assert_eq!(Some("d"), which_key.pending());
+        assert_eq!(
+            vec!["dw", "db", "d$"],
+            which_key
+                .completions()
+                .iter()
+                .map(|binding| binding.keys)
+                .collect::<Vec<&str>>()
+        );
+
+        which_key.press('w', now);
+
+        assert_eq!(None, which_key.pending());
+        assert!(which_key.completions().is_empty());
+    }
+
+    #[test]
+    fn it_shows_the_popup_after_a_delay_until_cancelled() {
+        let mut which_key = WhichKey::new(BINDINGS);
+        let now = Instant::now();
+
+        which_key.press('x', now);
+        assert_eq!(None, which_key.pending());
+
+        which_key.press('d', now);
+        assert!(!which_key.should_show(now));
+        assert!(which_key.should_show(now + WHICH_KEY_DELAY));
+
+        which_key.cancel();
+        assert!(!which_key.should_show(now + WHICH_KEY_DELAY));
+    }
+}
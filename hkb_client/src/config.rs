@@ -0,0 +1,335 @@
+use std::str::FromStr;
+
+use hkb_core::database::services::reminders::{
+    OrderDirection, ReminderOrderColumn, ReminderPrecision, DEFAULT_DELETE_CONFIRMATION_THRESHOLD,
+    DEFAULT_MAX_NOTE_LENGTH,
+};
+use hkb_date::date::{DisplayTimezone, FirstDayOfWeek, Weekday};
+use parking_lot::{MappedMutexGuard, Mutex, MutexGuard};
+
+use crate::components::{default_snooze_presets, SnoozePreset};
+use crate::fire_alert::FireAlert;
+
+static GLOBAL_CONFIG: Mutex<Option<Config>> = parking_lot::const_mutex(None);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReminderListColumn {
+    Note,
+    Due,
+    Date,
+    Time,
+    Attachment,
+}
+
+impl FromStr for ReminderListColumn {
+    type Err = String;
+
+    fn from_str(column: &str) -> Result<Self, Self::Err> {
+        match column.trim().to_lowercase().as_str() {
+            "note" => Ok(Self::Note),
+            "due" => Ok(Self::Due),
+            "date" => Ok(Self::Date),
+            "time" => Ok(Self::Time),
+            "attachment" => Ok(Self::Attachment),
+            _ => Err(format!("Unknown reminder list column: {column}")),
+        }
+    }
+}
+
+pub struct Config {
+    reminder_list_columns: Vec<ReminderListColumn>,
+    display_timezone: Option<DisplayTimezone>,
+    cache_reminders: bool,
+    reminder_precision: ReminderPrecision,
+    delete_confirmation_threshold: usize,
+    max_note_length: usize,
+    first_day_of_week: FirstDayOfWeek,
+    snippets: Vec<(String, String)>,
+    snooze_presets: Vec<SnoozePreset>,
+    confirm_quit_with_unsaved_input: bool,
+    edit_on_open: bool,
+    fire_alert: FireAlert,
+    default_order: (ReminderOrderColumn, OrderDirection),
+    key_repeat_cap: Option<usize>,
+    completed_retention_days: Option<u32>,
+    weekend_days: Vec<Weekday>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            reminder_list_columns: vec![
+                ReminderListColumn::Note,
+                ReminderListColumn::Due,
+                ReminderListColumn::Date,
+                ReminderListColumn::Attachment,
+            ],
+            display_timezone: None,
+            cache_reminders: false,
+            reminder_precision: ReminderPrecision::Minute,
+            delete_confirmation_threshold: DEFAULT_DELETE_CONFIRMATION_THRESHOLD,
+            max_note_length: DEFAULT_MAX_NOTE_LENGTH,
+            first_day_of_week: FirstDayOfWeek::default(),
+            snippets: vec![],
+            snooze_presets: default_snooze_presets(),
+            confirm_quit_with_unsaved_input: true,
+            edit_on_open: true,
+            fire_alert: FireAlert::default(),
+            default_order: (ReminderOrderColumn::Id, OrderDirection::Asc),
+            key_repeat_cap: None,
+            completed_retention_days: None,
+            weekend_days: vec![Weekday::Sat, Weekday::Sun],
+        }
+    }
+}
+
+impl Config {
+    fn get_global() -> MappedMutexGuard<'static, Self> {
+        MutexGuard::map(GLOBAL_CONFIG.lock(), |reader| {
+            reader.get_or_insert_with(Self::load)
+        })
+    }
+
+    fn load() -> Self {
+        let mut config = Self::default();
+
+        // Columns are specified as a comma separated list. Ex: time,note,due
+        if let Ok(columns) = std::env::var("HKB_REMINDER_LIST_COLUMNS") {
+            let columns = columns
+                .split(',')
+                .filter_map(|column| ReminderListColumn::from_str(column).ok())
+                .collect::<Vec<ReminderListColumn>>();
+
+            if !columns.is_empty() {
+                config.reminder_list_columns = columns;
+            }
+        }
+
+        // Timezone is one of: utc, local or an offset. Ex: +02:00
+        if let Ok(timezone) = std::env::var("HKB_DISPLAY_TIMEZONE") {
+            config.display_timezone = DisplayTimezone::from_str(&timezone).ok();
+        }
+
+        // Ex: HKB_CACHE_REMINDERS=1
+        if let Ok(cache_reminders) = std::env::var("HKB_CACHE_REMINDERS") {
+            config.cache_reminders = matches!(cache_reminders.trim(), "1" | "true");
+        }
+
+        // Precision is one of: minute or second. Ex: HKB_REMINDER_PRECISION=second
+        // Minute by default, as the create form always dropped the seconds. Unlike before,
+        // it applies to every date the client saves, ex: edited or snoozed reminders too.
+        if let Ok(precision) = std::env::var("HKB_REMINDER_PRECISION") {
+            if let Ok(precision) = ReminderPrecision::from_str(&precision) {
+                config.reminder_precision = precision;
+            }
+        }
+
+        // Ex: HKB_DELETE_CONFIRMATION_THRESHOLD=25
+        if let Ok(threshold) = std::env::var("HKB_DELETE_CONFIRMATION_THRESHOLD") {
+            if let Ok(threshold) = threshold.trim().parse() {
+                config.delete_confirmation_threshold = threshold;
+            }
+        }
+
+        // Ex: HKB_MAX_NOTE_LENGTH=500
+        if let Ok(length) = std::env::var("HKB_MAX_NOTE_LENGTH") {
+            if let Ok(length) = length.trim().parse() {
+                config.max_note_length = length;
+            }
+        }
+
+        // Day is one of: sunday or monday. Ex: HKB_FIRST_DAY_OF_WEEK=sunday
+        if let Ok(day) = std::env::var("HKB_FIRST_DAY_OF_WEEK") {
+            if let Ok(day) = FirstDayOfWeek::from_str(&day) {
+                config.first_day_of_week = day;
+            }
+        }
+
+        // Order is a column and a direction, separated by a colon. Ex: HKB_DEFAULT_ORDER=remind_at:asc
+        if let Ok(order) = std::env::var("HKB_DEFAULT_ORDER") {
+            if let Some(order) = parse_order(&order) {
+                config.default_order = order;
+            }
+        }
+
+        // Completed reminders are purged at startup after this many days, unset keeps them.
+        // Ex: HKB_COMPLETED_RETENTION_DAYS=30
+        if let Ok(days) = std::env::var("HKB_COMPLETED_RETENTION_DAYS") {
+            config.completed_retention_days = days.trim().parse().ok();
+        }
+
+        // Held keys are kept to this many events per frame, 0 keeps them all. Ex: HKB_KEY_REPEAT_CAP=2
+        if let Ok(cap) = std::env::var("HKB_KEY_REPEAT_CAP") {
+            config.key_repeat_cap = cap.trim().parse().ok().filter(|cap| *cap > 0);
+        }
+
+        // Days are specified as a comma separated list. Ex: HKB_WEEKEND_DAYS=fri,sat
+        if let Ok(days) = std::env::var("HKB_WEEKEND_DAYS") {
+            config.weekend_days = days
+                .split(',')
+                .filter_map(|day| day.trim().parse().ok())
+                .collect();
+        }
+
+        // Snippets are separated by |, each is a trigger and its text. Ex: ;addr=221B Baker Street|;sig=Best, K
+        if let Ok(snippets) = std::env::var("HKB_SNIPPETS") {
+            config.snippets = parse_snippets(&snippets);
+        }
+
+        // Presets are a comma separated list of minutes, hours, days, tonight, tomorrow or next week.
+        // Ex: 10m,2h,1d,tomorrow
+        if let Ok(presets) = std::env::var("HKB_SNOOZE_PRESETS") {
+            let presets = presets
+                .split(',')
+                .filter_map(|preset| SnoozePreset::from_str(preset).ok())
+                .collect::<Vec<SnoozePreset>>();
+
+            if !presets.is_empty() {
+                config.snooze_presets = presets;
+            }
+        }
+
+        // Ex: HKB_CONFIRM_QUIT_UNSAVED=0
+        if let Ok(confirm) = std::env::var("HKB_CONFIRM_QUIT_UNSAVED") {
+            config.confirm_quit_with_unsaved_input = !matches!(confirm.trim(), "0" | "false");
+        }
+
+        // Ex: HKB_EDIT_ON_OPEN=0
+        if let Ok(edit) = std::env::var("HKB_EDIT_ON_OPEN") {
+            config.edit_on_open = !matches!(edit.trim(), "0" | "false");
+        }
+
+        // Alert is one of: bell, flash or none. Ex: HKB_FIRE_ALERT=bell
+        if let Ok(alert) = std::env::var("HKB_FIRE_ALERT") {
+            if let Ok(alert) = FireAlert::from_str(&alert) {
+                config.fire_alert = alert;
+            }
+        }
+
+        config
+    }
+}
+
+fn parse_order(order: &str) -> Option<(ReminderOrderColumn, OrderDirection)> {
+    let (column, direction) = order.split_once(':')?;
+
+    Some((column.parse().ok()?, direction.parse().ok()?))
+}
+
+fn parse_snippets(snippets: &str) -> Vec<(String, String)> {
+    snippets
+        .split('|')
+        .filter_map(|snippet| snippet.split_once('='))
+        .map(|(trigger, text)| (trigger.trim().to_owned(), text.to_owned()))
+        .filter(|(trigger, _)| !trigger.is_empty() && !trigger.contains(char::is_whitespace))
+        .collect()
+}
+
+pub fn get_reminder_list_columns() -> Vec<ReminderListColumn> {
+    Config::get_global().reminder_list_columns.clone()
+}
+
+#[allow(dead_code)]
+pub fn set_reminder_list_columns(columns: Vec<ReminderListColumn>) {
+    Config::get_global().reminder_list_columns = columns;
+}
+
+/// When no display timezone is configured, dates are shown as they are stored
+pub fn get_display_timezone() -> Option<DisplayTimezone> {
+    Config::get_global().display_timezone
+}
+
+/// Reminders written by the daemon are not seen until the client writes one itself
+pub fn should_cache_reminders() -> bool {
+    Config::get_global().cache_reminders
+}
+
+/// Reminders are stored and shown without seconds, unless second precision is configured
+pub fn get_reminder_precision() -> ReminderPrecision {
+    Config::get_global().reminder_precision
+}
+
+/// Deleting more reminders than this at once asks for a confirmation first
+pub fn get_delete_confirmation_threshold() -> usize {
+    Config::get_global().delete_confirmation_threshold
+}
+
+/// Notes longer than this many characters are rejected when saving a reminder
+pub fn get_max_note_length() -> usize {
+    Config::get_global().max_note_length
+}
+
+/// Triggers typed in the note input, with the text they expand to
+pub fn get_snippets() -> Vec<(String, String)> {
+    Config::get_global().snippets.clone()
+}
+
+/// Quick choices offered when snoozing a reminder
+pub fn get_snooze_presets() -> Vec<SnoozePreset> {
+    Config::get_global().snooze_presets.clone()
+}
+
+/// The first snooze preset, used when snoozing without picking one
+pub fn get_default_snooze_preset() -> SnoozePreset {
+    Config::get_global()
+        .snooze_presets
+        .first()
+        .copied()
+        .unwrap_or(SnoozePreset::Minutes(5))
+}
+
+/// Quitting with unsaved input asks for a confirmation first, unless disabled
+pub fn should_confirm_quit_with_unsaved_input() -> bool {
+    Config::get_global().confirm_quit_with_unsaved_input
+}
+
+/// Forms start in insert mode on their first field, unless disabled
+pub fn should_edit_on_open() -> bool {
+    Config::get_global().edit_on_open
+}
+
+#[allow(dead_code)]
+pub fn set_edit_on_open(edit: bool) {
+    Config::get_global().edit_on_open = edit;
+}
+
+/// How a reminder firing while the client is open is cued, the screen flashes unless configured otherwise
+pub fn get_fire_alert() -> FireAlert {
+    Config::get_global().fire_alert
+}
+
+/// How reminders are ordered when a fetch does not ask for an order, by id unless configured otherwise
+pub fn get_default_order() -> (ReminderOrderColumn, OrderDirection) {
+    Config::get_global().default_order
+}
+
+/// How many events of a held key are handled per frame, every one of them unless configured
+pub fn get_key_repeat_cap() -> Option<usize> {
+    Config::get_global().key_repeat_cap
+}
+
+/// Days completed reminders are kept for, forever unless configured
+pub fn get_completed_retention_days() -> Option<u32> {
+    Config::get_global().completed_retention_days
+}
+
+/// Days reminders are not deferred to, saturday and sunday unless configured otherwise
+pub fn get_weekend_days() -> Vec<Weekday> {
+    Config::get_global().weekend_days.clone()
+}
+
+/// Weeks start on monday, unless configured otherwise
+#[allow(dead_code)]
+pub fn get_first_day_of_week() -> FirstDayOfWeek {
+    Config::get_global().first_day_of_week
+}
+
+#[allow(dead_code)]
+pub fn set_reminder_precision(precision: ReminderPrecision) {
+    Config::get_global().reminder_precision = precision;
+}
+
+#[allow(dead_code)]
+pub fn set_display_timezone(timezone: Option<DisplayTimezone>) {
+    Config::get_global().display_timezone = timezone;
+}
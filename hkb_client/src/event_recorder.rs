@@ -0,0 +1,173 @@
+use crossterm::event::Event;
+use hkb_core::logger::{error, info};
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Error as IOError, Result as IOResult, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+// Replayed events are pushed at a fixed rate, so that each one is handled in a separate frame
+const REPLAY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Writes events as json lines, so they can be replayed later
+pub struct EventRecorder<W: Write> {
+    writer: W,
+}
+
+impl EventRecorder<BufWriter<File>> {
+    pub fn create(path: impl AsRef<Path>) -> IOResult<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> EventRecorder<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    pub fn record(&mut self, event: &Event) -> IOResult<()> {
+        serde_json::to_writer(&mut self.writer, event)?;
+        self.writer.write_all(b"\n")?;
+        self.writer.flush()
+    }
+}
+
+pub struct EventReplayer {
+    events: VecDeque<Event>,
+    interval: Duration,
+    last_replayed_at: Option<Instant>,
+}
+
+impl EventReplayer {
+    pub fn open(path: impl AsRef<Path>) -> IOResult<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?), REPLAY_INTERVAL)
+    }
+
+    pub fn from_reader(reader: impl BufRead, interval: Duration) -> IOResult<Self> {
+        let mut events = VecDeque::new();
+
+        for line in reader.lines() {
+            let line = line?;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            events.push_back(serde_json::from_str(&line).map_err(IOError::from)?);
+        }
+
+        Ok(Self {
+            events,
+            interval,
+            last_replayed_at: None,
+        })
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Returns the next event if enough time has passed since the last replayed one
+    pub fn next_event(&mut self) -> Option<Event> {
+        if let Some(last_replayed_at) = self.last_replayed_at {
+            if last_replayed_at.elapsed() < self.interval {
+                return None;
+            }
+        }
+
+        let event = self.events.pop_front()?;
+        self.last_replayed_at = Some(Instant::now());
+
+        Some(event)
+    }
+}
+
+/// Creates a recorder if `HKB_RECORD_EVENTS` is set to a file path
+pub fn recorder_from_env() -> Option<EventRecorder<BufWriter<File>>> {
+    let path = std::env::var("HKB_RECORD_EVENTS").ok()?;
+
+    match EventRecorder::create(&path) {
+        Ok(recorder) => {
+            info!(target: "CLIENT_EVENT_RECORDER", "Recording events to {path}");
+
+            Some(recorder)
+        }
+        Err(e) => {
+            error!(target: "CLIENT_EVENT_RECORDER", "Failed to create events recording {path}: {e}");
+
+            None
+        }
+    }
+}
+
+/// Creates a replayer if `HKB_REPLAY_EVENTS` is set to a file path of a recording
+pub fn replayer_from_env() -> Option<EventReplayer> {
+    let path = std::env::var("HKB_REPLAY_EVENTS").ok()?;
+
+    match EventReplayer::open(&path) {
+        Ok(replayer) => {
+            info!(target: "CLIENT_EVENT_RECORDER", "Replaying events from {path}");
+
+            Some(replayer)
+        }
+        Err(e) => {
+            error!(target: "CLIENT_EVENT_RECORDER", "Failed to read events recording {path}: {e}");
+
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventHandler;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn key_event(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn it_replays_recorded_events_in_the_same_order() {
+        let recorded_events = vec![
+            key_event(KeyCode::Char('a')),
+            key_event(KeyCode::Tab),
+            Event::Resize(80, 24),
+            key_event(KeyCode::Enter),
+        ];
+        let mut recorder = EventRecorder::new(Vec::new());
+
+        for event in recorded_events.iter() {
+            recorder.record(event).unwrap();
+        }
+
+        let mut replayer =
+            EventReplayer::from_reader(recorder.writer.as_slice(), Duration::ZERO).unwrap();
+        let mut handler = EventHandler::new();
+
+        while let Some(event) = replayer.next_event() {
+            handler.push(event);
+        }
+
+        assert!(replayer.is_finished());
+        assert_eq!(recorded_events, handler.peek());
+    }
+
+    #[test]
+    fn it_replays_events_at_a_fixed_rate() {
+        let mut recorder = EventRecorder::new(Vec::new());
+        recorder.record(&key_event(KeyCode::Char('a'))).unwrap();
+        recorder.record(&key_event(KeyCode::Char('b'))).unwrap();
+
+        let mut replayer =
+            EventReplayer::from_reader(recorder.writer.as_slice(), Duration::from_secs(60))
+                .unwrap();
+
+        assert_eq!(Some(key_event(KeyCode::Char('a'))), replayer.next_event());
+        assert_eq!(None, replayer.next_event());
+        assert!(!replayer.is_finished());
+    }
+}
@@ -1,6 +1,6 @@
 use crossterm::event::{Event, KeyCode};
 use parking_lot::{MappedMutexGuard, Mutex, MutexGuard};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 macro_rules! consume_key_events {
     ($pattern:pat $(if $guard:expr)?) => {{
@@ -52,11 +52,18 @@ pub(crate) use has_key_event;
 
 static GLOBAL_EVENT_HANDLER: Mutex<Option<EventHandler>> = parking_lot::const_mutex(None);
 
+/// Identical key events closer than this to each other count as a held key repeating
+pub const KEY_REPEAT_WINDOW: Duration = Duration::from_millis(50);
+
 pub struct EventHandler {
     events: Vec<Event>,
     times_pressed: usize,
     key_release_delay: Instant,
     previous_key: Option<char>,
+    // held keys are kept to this many events per frame, `None` keeps them all
+    key_repeat_cap: Option<usize>,
+    // last key event of the frame, how many times it repeated and when it was last pushed
+    key_repeat: Option<(Event, usize, Instant)>,
 }
 
 impl EventHandler {
@@ -74,6 +81,9 @@ impl EventHandler {
             times_pressed: 0,
             previous_key: None,
             key_release_delay: Instant::now(),
+
+            key_repeat_cap: None,
+            key_repeat: None,
         }
     }
 }
@@ -84,7 +94,45 @@ impl EventHandler {
         self.events.iter().collect()
     }
 
+    /// Returns the current events without consuming them
+    #[allow(dead_code)]
+    pub fn peek(&self) -> Vec<Event> {
+        self.events.clone()
+    }
+
+    /// Whether the event repeats a held key more often than the cap allows in one frame
+    fn is_over_key_repeat_cap(&mut self, event: &Event, now: Instant) -> bool {
+        let Some(cap) = self.key_repeat_cap else {
+            return false;
+        };
+
+        if !matches!(event, Event::Key(_)) {
+            return false;
+        }
+
+        let repeated = match self.key_repeat.as_ref() {
+            Some((last_event, repeated, pushed_at))
+                if last_event == event && now.duration_since(*pushed_at) < KEY_REPEAT_WINDOW =>
+            {
+                repeated + 1
+            }
+            _ => 1,
+        };
+        self.key_repeat = Some((event.clone(), repeated, now));
+
+        repeated > cap
+    }
+
+    pub fn set_key_repeat_cap(&mut self, cap: Option<usize>) {
+        self.key_repeat_cap = cap;
+        self.key_repeat = None;
+    }
+
     pub fn push(&mut self, event: Event) {
+        if self.is_over_key_repeat_cap(&event, Instant::now()) {
+            return;
+        }
+
         if let Event::Key(e) = event {
             if let KeyCode::Char(c) = e.code {
                 // if 300 ms have passed, we assume we had a key release
@@ -107,20 +155,13 @@ impl EventHandler {
         self.key_release_delay = Instant::now();
     }
 
+    /// Removes and returns the matching events, both keep their order
     pub fn consume_if<T: Fn(&Event) -> bool>(&mut self, callback: T) -> Vec<Event> {
-        // arbitrary selected capacity
-        let mut events_to_consume: Vec<usize> = Vec::with_capacity(self.events.len() / 2);
-
-        for i in 0..self.events.len() {
-            if callback(&self.events[i]) {
-                events_to_consume.push(i);
-            }
-        }
+        let (consumed, kept): (Vec<Event>, Vec<Event>) =
+            self.events.drain(..).partition(|event| callback(event));
+        self.events = kept;
 
-        events_to_consume
-            .into_iter()
-            .filter_map(|i| self.consume(i))
-            .collect()
+        consumed
     }
 
     pub fn consume(&mut self, index: usize) -> Option<Event> {
@@ -145,6 +186,7 @@ impl EventHandler {
 
     pub fn clear(&mut self) {
         self.events.clear();
+        self.key_repeat = None;
     }
 }
 
@@ -152,6 +194,11 @@ pub fn push(event: Event) {
     EventHandler::get_global_handler().push(event)
 }
 
+#[allow(dead_code)]
+pub fn peek() -> Vec<Event> {
+    EventHandler::get_global_handler().peek()
+}
+
 pub fn consume_if<T: Fn(&Event) -> bool>(callback: T) -> Vec<Event> {
     EventHandler::get_global_handler().consume_if(callback)
 }
@@ -172,3 +219,144 @@ pub fn is_pressed_at_least(c: char, times: usize) -> bool {
 pub fn clear() {
     EventHandler::get_global_handler().clear()
 }
+
+/// Caps how many times a held key is seen per frame, so motions do not run away
+/// on terminals that flood repeats. `None` turns it off.
+pub fn set_key_repeat_cap(cap: Option<usize>) {
+    EventHandler::get_global_handler().set_key_repeat_cap(cap)
+}
+
+/// Helpers to drive components with synthetic events instead of the terminal
+#[cfg(test)]
+pub mod testing {
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+    pub fn key_event(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    /// Replaces the buffered events with the given ones, as if they arrived in one frame
+    pub fn inject(events: impl IntoIterator<Item = Event>) {
+        super::clear();
+
+        for event in events {
+            super::push(event);
+        }
+    }
+
+    /// Events still waiting to be consumed
+    pub fn buffer() -> Vec<Event> {
+        super::peek()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::testing::{buffer, inject, key_event};
+    use super::*;
+    use crate::events;
+    use serial_test::serial;
+
+    #[test]
+    fn it_can_peek_events_without_consuming_them() {
+        let mut handler = EventHandler::new();
+        handler.push(key_event(KeyCode::Char('a')));
+        handler.push(key_event(KeyCode::Enter));
+
+        assert_eq!(2, handler.peek().len());
+        assert_eq!(2, handler.peek().len());
+
+        let consumed = handler.consume_if(|event| *event == key_event(KeyCode::Enter));
+
+        assert_eq!(vec![key_event(KeyCode::Enter)], consumed);
+        assert_eq!(vec![key_event(KeyCode::Char('a'))], handler.peek());
+    }
+
+    #[test]
+    fn it_collapses_a_burst_of_identical_keys_to_the_cap() {
+        let mut handler = EventHandler::new();
+
+        for _ in 0..10 {
+            handler.push(key_event(KeyCode::Char('l')));
+        }
+
+        assert_eq!(10, handler.peek().len());
+
+        handler.clear();
+        handler.set_key_repeat_cap(Some(2));
+
+        for _ in 0..10 {
+            handler.push(key_event(KeyCode::Char('l')));
+        }
+        handler.push(key_event(KeyCode::Char('h')));
+        handler.push(key_event(KeyCode::Char('l')));
+
+        assert_eq!(
+            vec![
+                key_event(KeyCode::Char('l')),
+                key_event(KeyCode::Char('l')),
+                key_event(KeyCode::Char('h')),
+                key_event(KeyCode::Char('l')),
+            ],
+            handler.peek()
+        );
+
+        // every frame gets its own repeats
+        handler.clear();
+        handler.push(key_event(KeyCode::Char('l')));
+
+        assert_eq!(1, handler.peek().len());
+    }
+
+    #[test]
+    #[serial]
+    fn it_keeps_pushed_events_until_consumed() {
+        inject([key_event(KeyCode::Char('j'))]);
+        push(key_event(KeyCode::Enter));
+
+        assert_eq!(
+            vec![key_event(KeyCode::Char('j')), key_event(KeyCode::Enter)],
+            buffer()
+        );
+        assert_eq!(2, buffer().len());
+    }
+
+    #[test]
+    #[serial]
+    fn it_consumes_every_matching_key_event_in_order() {
+        inject([
+            key_event(KeyCode::Char('a')),
+            key_event(KeyCode::Enter),
+            key_event(KeyCode::Char('b')),
+            key_event(KeyCode::Esc),
+            key_event(KeyCode::Char('c')),
+        ]);
+        let mut typed = String::new();
+
+        consume_key_event!(
+            KeyCode::Char(c) => {
+                typed.push(c);
+            }
+        );
+
+        assert_eq!("abc", typed);
+        assert_eq!(
+            vec![key_event(KeyCode::Enter), key_event(KeyCode::Esc)],
+            buffer()
+        );
+        assert!(has_key_event!(KeyCode::Esc));
+        assert!(!has_key_event!(KeyCode::Esc));
+        assert_eq!(vec![key_event(KeyCode::Enter)], buffer());
+    }
+
+    #[test]
+    #[serial]
+    fn it_clears_the_buffer_between_frames() {
+        inject([key_event(KeyCode::Char('j')), key_event(KeyCode::Tab)]);
+
+        clear();
+
+        assert!(buffer().is_empty());
+        assert!(!has_key_event!(KeyCode::Tab));
+    }
+}
@@ -0,0 +1,124 @@
+use ratatui::prelude::{Frame, Rect};
+use ratatui::style::{Modifier, Style};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::{app_state, terminal};
+
+/// How long the screen stays inverted when a reminder fires
+pub const FLASH_DURATION: Duration = Duration::from_millis(300);
+
+/// The in app cue for a reminder firing while the client is open
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FireAlert {
+    Bell,
+    #[default]
+    Flash,
+    None,
+}
+
+impl FromStr for FireAlert {
+    type Err = String;
+
+    fn from_str(alert: &str) -> Result<Self, Self::Err> {
+        match alert.trim().to_lowercase().as_str() {
+            "bell" => Ok(FireAlert::Bell),
+            "flash" => Ok(FireAlert::Flash),
+            "none" => Ok(FireAlert::None),
+            _ => Err(format!("Unknown fire alert: {alert}")),
+        }
+    }
+}
+
+/// Rings the bell or flashes the screen when the daemon fires a reminder
+pub struct FireCue {
+    alert: FireAlert,
+    flash_until: Option<Instant>,
+    last_seen_fired: usize,
+    // whether the last draw was flashing, so the end of the flash is drawn as well
+    drawn_flashing: bool,
+}
+
+impl FireCue {
+    pub fn new(alert: FireAlert) -> Self {
+        Self {
+            alert,
+            flash_until: None,
+            last_seen_fired: app_state::get_reminders_fired(),
+            drawn_flashing: false,
+        }
+    }
+
+    /// Cues the reminders fired since the last tick, once
+    pub fn on_tick(&mut self, now: Instant) {
+        let fired = app_state::get_reminders_fired();
+
+        if fired != self.last_seen_fired {
+            self.last_seen_fired = fired;
+            self.on_reminder_fired(now);
+        }
+    }
+
+    pub fn on_reminder_fired(&mut self, now: Instant) {
+        match self.alert {
+            FireAlert::Bell => terminal::ring_bell(),
+            FireAlert::Flash => self.flash_until = Some(now + FLASH_DURATION),
+            FireAlert::None => {}
+        }
+    }
+
+    pub fn is_flashing(&self, now: Instant) -> bool {
+        self.flash_until
+            .is_some_and(|flash_until| now < flash_until)
+    }
+
+    /// Whether the flash started or ended since the last draw
+    pub fn is_dirty(&self, now: Instant) -> bool {
+        self.is_flashing(now) != self.drawn_flashing
+    }
+
+    /// Inverts whatever was drawn in the area while flashing
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, now: Instant) {
+        self.drawn_flashing = self.is_flashing(now);
+
+        if self.drawn_flashing {
+            frame
+                .buffer_mut()
+                .set_style(area, Style::default().add_modifier(Modifier::REVERSED));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_flashes_for_a_while_when_a_reminder_fires() {
+        let now = Instant::now();
+        let mut cue = FireCue::new(FireAlert::Flash);
+
+        assert!(!cue.is_flashing(now));
+
+        cue.on_reminder_fired(now);
+
+        assert_eq!(Some(now + FLASH_DURATION), cue.flash_until);
+        assert!(cue.is_flashing(now));
+        assert!(cue.is_dirty(now));
+        assert!(!cue.is_flashing(now + FLASH_DURATION));
+    }
+
+    #[test]
+    fn it_does_nothing_when_a_reminder_fires_without_an_alert() {
+        let now = Instant::now();
+        let mut cue = FireCue::new(FireAlert::None);
+
+        cue.on_reminder_fired(now);
+
+        assert_eq!(None, cue.flash_until);
+        assert!(!cue.is_flashing(now));
+        assert!(!cue.is_dirty(now));
+        assert_eq!(Ok(FireAlert::Bell), FireAlert::from_str("Bell"));
+        assert!(FireAlert::from_str("beep").is_err());
+    }
+}
@@ -0,0 +1,229 @@
+use crossterm::event::{Event, KeyCode};
+use hkb_core::database::services::reminders;
+use hkb_core::dtos::reminders::ReminderData;
+use hkb_core::logger::{debug, error};
+use hkb_date::date::SimpleDate;
+use ratatui::prelude::{Frame, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::widgets::{Clear, Paragraph};
+
+use crate::{app_state, config, events};
+
+/// How long the actions for a fired reminder are offered before the bar goes away
+const FIRED_TOAST_DURATION_IN_SECONDS: u64 = 15;
+
+/// Offers to snooze, complete or dismiss the reminder that just fired, with s, c or x.
+/// Snoozing uses the first snooze preset.
+pub struct FiredToast {
+    reminder: Option<ReminderData>,
+    shown_at: Option<SimpleDate>,
+    last_seen_fired: usize,
+    // whether the last draw showed the bar, so its removal is drawn as well
+    drawn_active: bool,
+}
+
+impl FiredToast {
+    pub fn new() -> Self {
+        Self {
+            reminder: None,
+            shown_at: None,
+            last_seen_fired: app_state::get_reminders_fired(),
+            drawn_active: false,
+        }
+    }
+
+    /// Shows the reminders fired since the last tick, the latest replacing the earlier ones
+    pub fn on_tick(&mut self, now: SimpleDate) {
+        let fired = app_state::get_reminders_fired();
+
+        if fired == self.last_seen_fired {
+            return;
+        }
+
+        self.last_seen_fired = fired;
+
+        if let Some(reminder) = app_state::get_last_fired_reminder() {
+            self.show(reminder, now);
+        }
+    }
+
+    pub fn show(&mut self, reminder: ReminderData, now: SimpleDate) {
+        self.reminder = Some(reminder);
+        self.shown_at = Some(now);
+    }
+
+    pub fn dismiss(&mut self) {
+        self.reminder = None;
+        self.shown_at = None;
+    }
+
+    pub fn is_active(&self, now: SimpleDate) -> bool {
+        self.reminder.is_some()
+            && self.shown_at.is_some_and(|shown_at| {
+                (now - shown_at).as_secs() < FIRED_TOAST_DURATION_IN_SECONDS
+            })
+    }
+
+    /// Whether the bar appeared or went away since the last draw
+    pub fn is_dirty(&self, now: SimpleDate) -> bool {
+        self.is_active(now) != self.drawn_active
+    }
+
+    /// Handles the keys of the bar while it is shown, before the views get them.
+    /// Typing into an input is left alone.
+    pub fn update(&mut self, now: SimpleDate) {
+        if !self.is_active(now) || app_state::is_editing() {
+            return;
+        }
+
+        let Some(reminder_id) = self.reminder.as_ref().map(|reminder| reminder.id) else {
+            return;
+        };
+        let mut snooze = false;
+        let mut complete = false;
+        let mut dismiss = false;
+
+        events::consume_key_event!(
+            KeyCode::Char(c) if c == 's' => {
+                snooze = true;
+            }
+            KeyCode::Char(c) if c == 'c' => {
+                complete = true;
+            }
+            KeyCode::Char(c) if c == 'x' => {
+                dismiss = true;
+            }
+        );
+
+        if snooze {
+            let preset = config::get_default_snooze_preset();
+            debug!(target: "CLIENT_FIRED_TOAST", "Snoozing fired reminder {reminder_id} by {preset}");
+
+            let result = preset
+                .remind_at(now)
+                .map_err(|e| e.to_string())
+                .and_then(|until| {
+                    reminders::snooze_reminder(reminder_id, until).map_err(|e| e.to_string())
+                });
+
+            match result {
+                Ok(_) => app_state::notify_reminders_changed(),
+                Err(e) => {
+                    error!(target: "CLIENT_FIRED_TOAST", "Failed to snooze fired reminder {reminder_id}: {e}")
+                }
+            }
+        } else if complete {
+            debug!(target: "CLIENT_FIRED_TOAST", "Completing fired reminder {reminder_id}");
+
+            match reminders::complete_reminder(reminder_id) {
+                Ok(_) => app_state::notify_reminders_changed(),
+                Err(e) => {
+                    error!(target: "CLIENT_FIRED_TOAST", "Failed to complete fired reminder {reminder_id}: {e}")
+                }
+            }
+        }
+
+        // the reminder is handled, or might be gone, either way there is nothing left to offer
+        if snooze || complete || dismiss {
+            self.dismiss();
+        }
+    }
+
+    /// Draws the bar over the last line of the area
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, now: SimpleDate) {
+        self.drawn_active = self.is_active(now);
+
+        let Some(reminder) = self.reminder.as_ref().filter(|_| self.drawn_active) else {
+            return;
+        };
+
+        if area.height == 0 {
+            return;
+        }
+
+        let bar_area = Rect::new(area.x, area.bottom() - 1, area.width, 1);
+
+        frame.render_widget(Clear, bar_area);
+        frame.render_widget(
+            Paragraph::new(format!(
+                "Fired: {} | s snooze {}  c complete  x dismiss",
+                reminder.display_note(),
+                config::get_default_snooze_preset()
+            ))
+            .style(Style::default().fg(Color::Black).bg(Color::Yellow)),
+            bar_area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::testing::{inject, key_event};
+    use hkb_core::database::init_database;
+    use hkb_core::database::services::reminders::{CreateReminderData, MissPolicy};
+    use hkb_core::dtos::reminders::fakes;
+    use serial_test::serial;
+
+    fn date(date: &str) -> SimpleDate {
+        SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    #[serial]
+    fn it_snoozes_the_fired_reminder_by_the_default_preset_and_dismisses() {
+        init_database(":memory:", vec![crate::CORE_MIGRATIONS]).unwrap();
+        app_state::set_editing(false);
+
+        let now = date("2024-04-05 08:00:00");
+        let reminder = reminders::create_reminder(CreateReminderData {
+            note: "Stretch".to_owned(),
+            body: None,
+            remind_at: now,
+            attachment: None,
+            recurrence: None,
+            miss_policy: MissPolicy::Keep,
+        })
+        .unwrap();
+        let mut toast = FiredToast::new();
+
+        toast.show(reminder.clone(), now);
+        inject([key_event(KeyCode::Char('s'))]);
+        toast.update(now);
+
+        assert!(!toast.is_active(now));
+        assert!(reminders::fetch_reminder(reminder.id)
+            .unwrap()
+            .remind_at
+            .eq_to_second(&config::get_default_snooze_preset().remind_at(now).unwrap()));
+    }
+
+    #[test]
+    #[serial]
+    fn it_only_offers_the_actions_for_a_while() {
+        app_state::set_editing(false);
+
+        let now = date("2024-04-05 08:00:00");
+        let mut toast = FiredToast::new();
+
+        assert!(!toast.is_active(now));
+
+        toast.show(fakes::create_reminder(), now);
+
+        assert!(toast.is_active(now));
+        assert!(toast.is_dirty(now));
+        assert!(!toast.is_active(date("2024-04-05 08:00:15")));
+
+        // other keys are passed on to the views
+        inject([key_event(KeyCode::Char('j'))]);
+        toast.update(now);
+
+        assert!(toast.is_active(now));
+        assert_eq!(1, events::testing::buffer().len());
+
+        inject([key_event(KeyCode::Char('x'))]);
+        toast.update(now);
+
+        assert!(!toast.is_active(now));
+    }
+}
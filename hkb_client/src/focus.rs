@@ -1,4 +1,212 @@
 pub trait Focusable {
     fn blur(&mut self);
     fn focus(&mut self);
+
+    /// Disabled focusables are skipped when cycling the focus
+    fn is_disabled(&self) -> bool {
+        false
+    }
+}
+
+/// Keeps track of which one of a fixed amount of focusables is focused
+pub struct FocusManager {
+    focused: usize,
+    len: usize,
+}
+
+impl FocusManager {
+    pub fn new(len: usize) -> Self {
+        Self { focused: 0, len }
+    }
+
+    pub fn focused(&self) -> usize {
+        self.focused
+    }
+
+    /// Moves the focus forward, wrapping to the first focusable
+    pub fn next(&mut self) {
+        self.focused = (self.focused + 1) % self.len.max(1);
+    }
+
+    /// Moves the focus backwards, wrapping to the last focusable
+    pub fn previous(&mut self) {
+        self.focused = self
+            .focused
+            .checked_sub(1)
+            .unwrap_or(self.len.saturating_sub(1));
+    }
+
+    /// Focuses the currently focused focusable and blurs the rest
+    pub fn apply(&self, focusables: &mut [&mut dyn Focusable]) {
+        for (i, focusable) in focusables.iter_mut().enumerate() {
+            if i == self.focused {
+                focusable.focus();
+            } else {
+                focusable.blur();
+            }
+        }
+    }
+}
+
+/// Cycles the focus through focusables in the order they were registered,
+/// skipping the disabled ones. Focusables are referred to by their index in the slice
+/// passed to `focus_next` and `focus_prev`.
+#[allow(dead_code)]
+#[derive(Default)]
+pub struct FocusOrder {
+    order: Vec<usize>,
+    position: Option<usize>,
+}
+
+#[allow(dead_code)]
+impl FocusOrder {
+    pub fn register(&mut self, index: usize) -> &mut Self {
+        self.order.push(index);
+
+        self
+    }
+
+    /// Index of the focused focusable, nothing before the focus is first moved
+    pub fn focused(&self) -> Option<usize> {
+        self.position.map(|position| self.order[position])
+    }
+
+    /// Focuses the next enabled focusable, wrapping to the first one
+    pub fn focus_next(&mut self, focusables: &mut [&mut dyn Focusable]) {
+        let len = self.order.len();
+        let start = self.position.map_or(0, |position| position + 1);
+
+        self.move_focus(focusables, (0..len).map(|step| (start + step) % len.max(1)));
+    }
+
+    /// Focuses the previous enabled focusable, wrapping to the last one
+    pub fn focus_prev(&mut self, focusables: &mut [&mut dyn Focusable]) {
+        let len = self.order.len();
+        let start = self.position.unwrap_or(0) + len;
+
+        self.move_focus(
+            focusables,
+            (1..=len).map(|step| (start - step) % len.max(1)),
+        );
+    }
+
+    fn move_focus(
+        &mut self,
+        focusables: &mut [&mut dyn Focusable],
+        positions: impl Iterator<Item = usize>,
+    ) {
+        let order = &self.order;
+        let Some(position) = positions.into_iter().find(|position| {
+            focusables
+                .get(order[*position])
+                .is_some_and(|focusable| !focusable.is_disabled())
+        }) else {
+            return;
+        };
+
+        if let Some(focusable) = self
+            .focused()
+            .and_then(|focused| focusables.get_mut(focused))
+        {
+            focusable.blur();
+        }
+
+        self.position = Some(position);
+        focusables[self.order[position]].focus();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Component {
+        focused: bool,
+        disabled: bool,
+    }
+
+    impl Focusable for Component {
+        fn blur(&mut self) {
+            self.focused = false;
+        }
+
+        fn focus(&mut self) {
+            self.focused = true;
+        }
+
+        fn is_disabled(&self) -> bool {
+            self.disabled
+        }
+    }
+
+    #[test]
+    fn it_wraps_focus_in_both_directions() {
+        let mut focus_manager = FocusManager::new(3);
+
+        focus_manager.previous();
+
+        assert_eq!(2, focus_manager.focused());
+
+        focus_manager.next();
+
+        assert_eq!(0, focus_manager.focused());
+    }
+
+    #[test]
+    fn it_cycles_focus_in_the_registered_order() {
+        let mut components: [Component; 3] = Default::default();
+        let mut focus_order = FocusOrder::default();
+        focus_order.register(2).register(0).register(1);
+
+        let mut cycle = |forward: bool, components: &mut [Component; 3]| {
+            let [first, second, third] = components;
+            let mut focusables: [&mut dyn Focusable; 3] = [first, second, third];
+
+            if forward {
+                focus_order.focus_next(&mut focusables);
+            } else {
+                focus_order.focus_prev(&mut focusables);
+            }
+
+            let focused = focus_order.focused().unwrap();
+
+            assert_eq!(
+                vec![focused],
+                (0..3)
+                    .filter(|i| components[*i].focused)
+                    .collect::<Vec<usize>>()
+            );
+
+            focused
+        };
+
+        assert_eq!(
+            vec![2, 0, 1, 2],
+            (0..4)
+                .map(|_| cycle(true, &mut components))
+                .collect::<Vec<usize>>()
+        );
+        assert_eq!(
+            vec![1, 0, 2, 1],
+            (0..4)
+                .map(|_| cycle(false, &mut components))
+                .collect::<Vec<usize>>()
+        );
+
+        components[0].disabled = true;
+
+        assert_eq!(
+            vec![2, 1, 2],
+            (0..3)
+                .map(|_| cycle(true, &mut components))
+                .collect::<Vec<usize>>()
+        );
+        assert_eq!(
+            vec![1, 2],
+            (0..2)
+                .map(|_| cycle(false, &mut components))
+                .collect::<Vec<usize>>()
+        );
+    }
 }
@@ -0,0 +1,358 @@
+use hkb_core::database::services::reminders::{
+    self, CreateReminderData, MissPolicy, ReminderId, UpdateReminderData,
+};
+use hkb_date::date::SimpleDate;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::io::{BufRead, Error as IOError};
+use std::str::FromStr;
+use thiserror::Error as ThisError;
+
+/// Subcommand that creates reminders from stdin instead of opening the TUI
+pub const IMPORT_COMMAND: &str = "import";
+/// Picks the `ConflictStrategy` of the import. Ex: `import --on-conflict skip`
+pub const CONFLICT_FLAG: &str = "--on-conflict";
+
+/// What happens to an imported reminder when one with the same note already exists
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictStrategy {
+    /// The imported reminder is left out
+    Skip,
+    /// The existing reminder is moved to the imported date
+    Overwrite,
+    /// The imported reminder is created next to the existing one
+    #[default]
+    Duplicate,
+}
+
+impl FromStr for ConflictStrategy {
+    type Err = String;
+
+    fn from_str(strategy: &str) -> Result<Self, Self::Err> {
+        match strategy.trim().to_lowercase().as_str() {
+            "skip" => Ok(ConflictStrategy::Skip),
+            "overwrite" => Ok(ConflictStrategy::Overwrite),
+            "duplicate" => Ok(ConflictStrategy::Duplicate),
+            _ => Err(format!("Unknown conflict strategy: {strategy}")),
+        }
+    }
+}
+
+/// Reads the conflict strategy flag from the command line arguments, `Duplicate` when it is not passed
+pub fn conflict_strategy_from_args(
+    args: impl IntoIterator<Item = String>,
+) -> Result<ConflictStrategy, String> {
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        if arg == CONFLICT_FLAG {
+            return args
+                .next()
+                .ok_or_else(|| format!("Missing value for {CONFLICT_FLAG}"))?
+                .parse();
+        }
+
+        if let Some(strategy) = arg.strip_prefix(&format!("{CONFLICT_FLAG}=")) {
+            return strategy.parse();
+        }
+    }
+
+    Ok(ConflictStrategy::default())
+}
+
+#[derive(ThisError, Debug, PartialEq)]
+pub enum ImportLineError {
+    #[error("Line {0}: expected \"note|date\"")]
+    MissingSeparator(usize),
+
+    #[error("Line {0}: the note is empty")]
+    EmptyNote(usize),
+
+    #[error("Line {0}: invalid date \"{1}\"")]
+    InvalidDate(usize, String),
+}
+
+#[derive(ThisError, Debug)]
+pub enum ImportError {
+    #[error("Failed to read stdin: {0}")]
+    FailedToRead(#[from] IOError),
+
+    #[error("Found {0} malformed lines, nothing was created")]
+    MalformedLines(usize),
+
+    #[error("Failed to create reminders: {0}")]
+    FailedToCreate(String),
+
+    #[error("Failed to overwrite reminders: {0}")]
+    FailedToOverwrite(String),
+}
+
+/// What an import did with the reminders it read
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    pub created: Vec<ReminderId>,
+    pub overwritten: Vec<ReminderId>,
+    pub skipped: usize,
+}
+
+impl Display for ImportReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Created {}, overwrote {}, skipped {}",
+            self.created.len(),
+            self.overwritten.len(),
+            self.skipped
+        )
+    }
+}
+
+/// Parses a `note|2024-04-05 08:00` line. Line numbers start at 1.
+pub fn parse_line(line_number: usize, line: &str) -> Result<CreateReminderData, ImportLineError> {
+    let (note, date) = line
+        .rsplit_once('|')
+        .ok_or(ImportLineError::MissingSeparator(line_number))?;
+    let note = note.trim();
+
+    if note.is_empty() {
+        return Err(ImportLineError::EmptyNote(line_number));
+    }
+
+    let remind_at = SimpleDate::parse_flexible(date)
+        .map_err(|_| ImportLineError::InvalidDate(line_number, date.trim().to_owned()))?;
+
+    Ok(CreateReminderData {
+        note: note.to_owned(),
+        body: None,
+        remind_at,
+        attachment: None,
+        recurrence: None,
+        miss_policy: MissPolicy::Keep,
+    })
+}
+
+/// Creates a reminder for every line, printing the ids of the created or overwritten reminders.
+/// Blank lines are skipped. When a line is malformed, no reminder is created.
+/// Lines with the note of an existing reminder are handled by the `strategy`.
+pub fn import_from(
+    reader: impl BufRead,
+    strategy: ConflictStrategy,
+) -> Result<ImportReport, ImportError> {
+    let mut reminders = vec![];
+    let mut malformed_lines = 0;
+
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match parse_line(i + 1, &line) {
+            Ok(reminder) => reminders.push(reminder),
+            Err(e) => {
+                eprintln!("{e}");
+                malformed_lines += 1;
+            }
+        }
+    }
+
+    if malformed_lines > 0 {
+        return Err(ImportError::MalformedLines(malformed_lines));
+    }
+
+    let (reminders, mut report) = resolve_conflicts(reminders, strategy)?;
+
+    report.created = reminders::create_reminders(reminders)
+        .map_err(|e| ImportError::FailedToCreate(e.to_string()))?
+        .into_iter()
+        .map(|reminder| reminder.id)
+        .collect();
+
+    for id in report.overwritten.iter().chain(report.created.iter()) {
+        println!("{id}");
+    }
+
+    eprintln!("{report}");
+
+    Ok(report)
+}
+
+/// Applies the strategy to reminders with the note of an existing one.
+/// Returns the reminders left to create and a report of the overwritten and skipped ones.
+fn resolve_conflicts(
+    reminders: Vec<CreateReminderData>,
+    strategy: ConflictStrategy,
+) -> Result<(Vec<CreateReminderData>, ImportReport), ImportError> {
+    let mut report = ImportReport::default();
+
+    if strategy == ConflictStrategy::Duplicate {
+        return Ok((reminders, report));
+    }
+
+    // reminders are fetched by id, so the oldest reminder with a note wins
+    let mut existing_ids = HashMap::new();
+
+    for reminder in
+        reminders::fetch_reminders(None).map_err(|e| ImportError::FailedToCreate(e.to_string()))?
+    {
+        existing_ids.entry(reminder.note).or_insert(reminder.id);
+    }
+
+    let mut to_create = vec![];
+
+    for reminder in reminders {
+        let Some(id) = existing_ids.get(&reminder.note).copied() else {
+            to_create.push(reminder);
+
+            continue;
+        };
+
+        if strategy == ConflictStrategy::Skip {
+            eprintln!("Skipped \"{}\", it already exists", reminder.note);
+            report.skipped += 1;
+
+            continue;
+        }
+
+        reminders::update_reminder(UpdateReminderData {
+            id,
+            note: None,
+            body: None,
+            remind_at: Some(reminder.remind_at),
+            attachment: None,
+            recurrence: None,
+            miss_policy: None,
+        })
+        .map_err(|e| ImportError::FailedToOverwrite(e.to_string()))?;
+        report.overwritten.push(id);
+    }
+
+    Ok((to_create, report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hkb_core::database::init_database;
+    use serial_test::serial;
+
+    const IMPORTED: &str = "Buy milk|2024-04-06 09:00\nCall mom|2024-04-06 18:00\n";
+
+    fn date(date: &str) -> SimpleDate {
+        SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    /// Imports `IMPORTED` into a database that already has "Buy milk",
+    /// returning the report and the reminders as `(note, remind_at)`
+    fn import_into_seeded_database(
+        strategy: ConflictStrategy,
+    ) -> (ImportReport, Vec<(String, SimpleDate)>) {
+        init_database(":memory:", vec![crate::CORE_MIGRATIONS]).unwrap();
+        reminders::create_reminder(parse_line(1, "Buy milk|2024-04-05 08:00").unwrap()).unwrap();
+
+        let report = import_from(IMPORTED.as_bytes(), strategy).unwrap();
+        let reminders = reminders::fetch_reminders(None)
+            .unwrap()
+            .into_iter()
+            .map(|reminder| (reminder.note, reminder.remind_at))
+            .collect();
+
+        (report, reminders)
+    }
+
+    #[test]
+    #[serial]
+    fn it_skips_reminders_that_already_exist() {
+        let (report, reminders) = import_into_seeded_database(ConflictStrategy::Skip);
+
+        assert_eq!(
+            vec![
+                ("Buy milk".to_owned(), date("2024-04-05 08:00:00")),
+                ("Call mom".to_owned(), date("2024-04-06 18:00:00")),
+            ],
+            reminders
+        );
+        assert_eq!("Created 1, overwrote 0, skipped 1", report.to_string());
+    }
+
+    #[test]
+    #[serial]
+    fn it_overwrites_reminders_that_already_exist() {
+        let (report, reminders) = import_into_seeded_database(ConflictStrategy::Overwrite);
+
+        assert_eq!(
+            vec![
+                ("Buy milk".to_owned(), date("2024-04-06 09:00:00")),
+                ("Call mom".to_owned(), date("2024-04-06 18:00:00")),
+            ],
+            reminders
+        );
+        assert_eq!("Created 1, overwrote 1, skipped 0", report.to_string());
+    }
+
+    #[test]
+    #[serial]
+    fn it_duplicates_reminders_that_already_exist() {
+        let (report, reminders) = import_into_seeded_database(ConflictStrategy::Duplicate);
+
+        assert_eq!(
+            vec![
+                ("Buy milk".to_owned(), date("2024-04-05 08:00:00")),
+                ("Buy milk".to_owned(), date("2024-04-06 09:00:00")),
+                ("Call mom".to_owned(), date("2024-04-06 18:00:00")),
+            ],
+            reminders
+        );
+        assert_eq!("Created 2, overwrote 0, skipped 0", report.to_string());
+    }
+
+    #[test]
+    fn it_reads_the_conflict_strategy_from_args() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            Ok(ConflictStrategy::Skip),
+            conflict_strategy_from_args(args(&["hkb", "import", "--on-conflict", "skip"]))
+        );
+        assert_eq!(
+            Ok(ConflictStrategy::Overwrite),
+            conflict_strategy_from_args(args(&["hkb", "import", "--on-conflict=overwrite"]))
+        );
+        assert_eq!(
+            Ok(ConflictStrategy::Duplicate),
+            conflict_strategy_from_args(args(&["hkb", "import"]))
+        );
+        assert!(conflict_strategy_from_args(args(&["hkb", "import", "--on-conflict"])).is_err());
+        assert!(
+            conflict_strategy_from_args(args(&["hkb", "import", "--on-conflict=merge"])).is_err()
+        );
+    }
+
+    #[test]
+    fn it_parses_a_valid_line() {
+        let reminder = parse_line(1, "Buy milk | 2024-04-05 08:00").unwrap();
+
+        assert_eq!("Buy milk", reminder.note);
+        assert_eq!(
+            SimpleDate::parse_from_str("2024-04-05 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap(),
+            reminder.remind_at
+        );
+    }
+
+    #[test]
+    fn it_reports_malformed_lines_with_their_number() {
+        assert_eq!(
+            Some(ImportLineError::MissingSeparator(2)),
+            parse_line(2, "Buy milk 2024-04-05 08:00").err()
+        );
+        assert_eq!(
+            Some(ImportLineError::EmptyNote(3)),
+            parse_line(3, " |2024-04-05 08:00").err()
+        );
+        assert_eq!(
+            Some(ImportLineError::InvalidDate(4, "tomorrow".to_owned())),
+            parse_line(4, "Buy milk|tomorrow").err()
+        );
+    }
+}
@@ -1,29 +1,54 @@
-use app_state::AppView;
 use components::{Component, Navigation};
 use crossterm::event::{self, Event, KeyCode};
 use diesel_migrations::{embed_migrations, EmbeddedMigrations};
-use hkb_core::database::init_database;
-use hkb_core::logger::{debug, error, init as logger_init};
+use hkb_core::database::services::reminders::{
+    self, set_cache_enabled, set_default_order, set_delete_confirmation_threshold,
+    set_max_note_length, set_precision, set_weekend_days,
+};
+use hkb_core::database::{init_database_with_tuning, DatabaseTuning};
+use hkb_core::logger::{debug, error, info, init as logger_init};
+use hkb_core::paths;
 use hkb_daemon_core::client::{Client, ClientError};
 use hkb_daemon_core::frame::Event as FrameEvent;
+use hkb_date::clock::{Clock, SystemClock};
+use hkb_date::date::SimpleDate;
 use ratatui::prelude::{Constraint, Direction, Layout};
 use ratatui::widgets::{Block, Borders};
-use singleton::set_server_msg_sender;
-use std::{io::Error as IOError, thread, time::Duration};
+use singleton::{set_server_connected, set_server_msg_sender};
+use std::{
+    io::Error as IOError,
+    rc::Rc,
+    thread,
+    time::{Duration, Instant},
+};
 use thiserror::Error as ThisError;
 
 mod app_state;
 mod apps;
 mod components;
+mod config;
+mod event_recorder;
 mod events;
+mod fire_alert;
+mod fired_toast;
 mod focus;
+mod import;
+mod overdue;
+mod quit;
 mod singleton;
 mod terminal;
 mod utils;
+mod welcome;
 
 pub const APP_MIGRATIONS: EmbeddedMigrations = embed_migrations!("./migrations");
 pub const CORE_MIGRATIONS: EmbeddedMigrations = embed_migrations!("../hkb_core/migrations");
 
+/// Prints today's agenda instead of starting the UI. Ex: `hkb_client agenda | mail -s "Today" me`
+const AGENDA_COMMAND: &str = "agenda";
+
+// redraw at least this often, even if nothing is dirty
+const IDLE_REDRAW_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(ThisError, Debug)]
 pub enum RendererError {
     #[error("Failed to render output!")]
@@ -44,6 +69,7 @@ async fn connect_to_server(mut rx: tokio::sync::mpsc::Receiver<FrameEvent>) {
     }
 
     let mut client = result.unwrap();
+    set_server_connected(true);
     let mut alternate_interval = tokio::time::interval(std::time::Duration::from_millis(500));
 
     loop {
@@ -66,6 +92,10 @@ async fn connect_to_server(mut rx: tokio::sync::mpsc::Receiver<FrameEvent>) {
                 match result {
                     Ok(event) => {
                         debug!(target: "CLIENT", "Received an event: {event:?}");
+
+                        if let FrameEvent::ReminderFired(reminder) = event {
+                            app_state::notify_reminder_fired(reminder);
+                        }
                     }
                     Err(ClientError::ConnectionClosed(e)) => {
                         debug!(target: "CLIENT", "Server disconnected: {e:?}");
@@ -80,6 +110,7 @@ async fn connect_to_server(mut rx: tokio::sync::mpsc::Receiver<FrameEvent>) {
     // if we are running the client and the connection closed out of the blue
     // while we were connected
     // then we try to connected again until
+    set_server_connected(false);
     drop(client);
     spawn_server_connection_thread(rx);
 }
@@ -91,12 +122,35 @@ fn spawn_server_connection_thread(rx: tokio::sync::mpsc::Receiver<FrameEvent>) {
 fn bootstrap() {
     logger_init(None);
 
-    let database_file_path = dirs::data_local_dir().unwrap().join("hkb/db");
-    init_database(
+    if let Some(data_dir) = paths::data_dir_from_args(std::env::args()) {
+        paths::set_data_dir(data_dir);
+    }
+
+    // an overridden data directory might not exist yet
+    std::fs::create_dir_all(paths::data_dir()).expect("Failed to create the data directory!");
+
+    let database_file_path = paths::database_path();
+    init_database_with_tuning(
         database_file_path.to_str().unwrap(),
         vec![CORE_MIGRATIONS, APP_MIGRATIONS],
+        DatabaseTuning::from_env(),
     )
     .expect("Failed to initialize database!");
+    set_cache_enabled(config::should_cache_reminders());
+    set_precision(config::get_reminder_precision());
+    let (order_column, order_direction) = config::get_default_order();
+    set_default_order(order_column, order_direction);
+    events::set_key_repeat_cap(config::get_key_repeat_cap());
+    set_weekend_days(&config::get_weekend_days());
+    set_delete_confirmation_threshold(config::get_delete_confirmation_threshold());
+    set_max_note_length(config::get_max_note_length());
+
+    if let Some(retention_days) = config::get_completed_retention_days() {
+        match reminders::purge_completed_past_retention(SimpleDate::local(), retention_days) {
+            Ok(purged) => info!(target: "CLIENT", "Purged {purged} completed reminders."),
+            Err(e) => error!(target: "CLIENT", "Failed to purge completed reminders: {e}"),
+        }
+    }
 
     let (tx, rx) = tokio::sync::mpsc::channel::<FrameEvent>(16);
 
@@ -104,63 +158,209 @@ fn bootstrap() {
     spawn_server_connection_thread(rx);
 }
 
+fn get_mode_title(editing: bool) -> &'static str {
+    if editing {
+        "Insert mode"
+    } else {
+        "Normal Mode"
+    }
+}
+
 #[tokio::main]
 async fn main() -> RenderResult {
     bootstrap();
 
+    if std::env::args().nth(1).as_deref() == Some(import::IMPORT_COMMAND) {
+        let result = import::conflict_strategy_from_args(std::env::args()).and_then(|strategy| {
+            import::import_from(std::io::stdin().lock(), strategy).map_err(|e| e.to_string())
+        });
+
+        if let Err(e) = result {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+
+        return Ok(());
+    }
+
+    if std::env::args().nth(1).as_deref() == Some(AGENDA_COMMAND) {
+        match reminders::today_agenda_text(SimpleDate::local()) {
+            Ok(agenda) => println!("{agenda}"),
+            Err(e) => {
+                eprintln!("{e}");
+                std::process::exit(1);
+            }
+        }
+
+        return Ok(());
+    }
+
     let mut terminal = terminal::init()?;
     let mut should_quit = false;
-    let mut main_app = apps::MainApp::new();
-    let mut reminders_app = apps::RemindersApp::new();
-    let mut navigation =
-        Navigation::new("HKB".to_string(), vec![AppView::Main, AppView::Reminders]);
+    let mut quit_guard = quit::QuitGuard::default();
+    let mut fire_cue = fire_alert::FireCue::new(config::get_fire_alert());
+    let mut fired_toast = fired_toast::FiredToast::new();
+    let mut overdue_status = overdue::OverdueStatus::new();
+    let mut event_recorder = event_recorder::recorder_from_env();
+    let mut event_replayer = event_recorder::replayer_from_env();
+    let mut editing_observer = app_state::EditingObserver::new();
+    let mut mode_title = get_mode_title(app_state::is_editing());
+    let mut last_drawn_at = Instant::now();
+    let clock: Rc<dyn Clock> = Rc::new(SystemClock);
+    let mut app_registry = apps::AppRegistry::default();
+    app_registry.register(apps::MainApp::new());
+    app_registry.register(apps::TodayApp::new(clock.clone()));
+    app_registry.register(apps::RemindersApp::new(clock.clone()));
+    let mut navigation = Navigation::new("HKB".to_string(), app_registry.navigation_tabs());
+
+    if let Some(id) = app_registry.first_id() {
+        app_state::set_view(id);
+    }
+
+    let started_at = Instant::now();
+    let away_summary = welcome::take_away_summary(clock.now());
 
     terminal.clear()?;
 
     while !should_quit {
+        let mut frame_events = vec![];
+
         while event::poll(Duration::ZERO).unwrap() {
             if let Ok(event) = event::read() {
-                if let Event::Key(event) = event { match event.code {
-                    KeyCode::Char(c) => {
-                        should_quit =
-                            c == 'c' && event.modifiers.contains(event::KeyModifiers::CONTROL)
+                if let Some(recorder) = event_recorder.as_mut() {
+                    if let Err(e) = recorder.record(&event) {
+                        error!(target: "CLIENT", "Failed to record event: {e}");
                     }
-                    KeyCode::Esc => app_state::set_editing(false),
-                    _ => {}
-                } }
+                }
+
+                frame_events.push(event);
+            }
+        }
+
+        if let Some(event) = event_replayer
+            .as_mut()
+            .and_then(|replayer| replayer.next_event())
+        {
+            frame_events.push(event);
+        } else if event_replayer
+            .as_ref()
+            .is_some_and(|replayer| replayer.is_finished())
+        {
+            info!(target: "CLIENT", "Finished replaying events.");
+            event_replayer = None;
+        }
+
+        let has_events = !frame_events.is_empty();
 
-                events::push(event);
+        for event in frame_events {
+            if let Event::Key(key) = event {
+                let has_unsaved_input = config::should_confirm_quit_with_unsaved_input()
+                    && app_state::has_unsaved_input();
+
+                match quit_guard.decide(&key, has_unsaved_input, Instant::now()) {
+                    quit::QuitDecision::Quit => {
+                        should_quit = true;
+
+                        continue;
+                    }
+                    // the quit key is not passed on, so inputs do not type it
+                    quit::QuitDecision::AskToConfirm => continue,
+                    quit::QuitDecision::Ignore => {}
+                }
+
+                if key.code == KeyCode::Esc {
+                    app_state::set_editing(false);
+                }
             }
+
+            events::push(event);
+        }
+
+        let now = clock.now();
+        navigation.on_tick(now);
+        overdue_status.on_tick(now);
+        fire_cue.on_tick(Instant::now());
+        fired_toast.on_tick(now);
+        // the bar gets its keys before the views
+        fired_toast.update(now);
+
+        if let Some(view) = app_state::get_view() {
+            app_registry.on_tick(view, now);
+        }
+
+        let mode_changed = if let Some(editing) = editing_observer.changed() {
+            mode_title = get_mode_title(editing);
+
+            true
+        } else {
+            false
+        };
+
+        // time based labels (ex: due in) still need an occasional redraw, even when idle
+        let should_draw = has_events
+            || mode_changed
+            || navigation.is_dirty()
+            || app_registry.is_dirty()
+            || fire_cue.is_dirty(Instant::now())
+            || fired_toast.is_dirty(now)
+            || last_drawn_at.elapsed() >= IDLE_REDRAW_INTERVAL;
+
+        if !should_draw {
+            thread::sleep(Duration::from_millis(16));
+
+            continue;
         }
 
         terminal.draw(|frame| {
+            if terminal::is_too_small(frame.size()) {
+                terminal::render_too_small(frame, frame.size());
+
+                return;
+            }
+
             let base_layout = Layout::new(
                 Direction::Vertical,
                 [
-                    Constraint::Length(1),
+                    Constraint::Length(navigation.height()),
                     Constraint::Min(0),
                     Constraint::Length(1),
                 ],
             )
             .split(frame.size());
             navigation.render(frame, base_layout[0]);
+            let status_title = match overdue_status.label() {
+                Some(overdue) => format!("{mode_title} | {overdue}"),
+                None => mode_title.to_string(),
+            };
             frame.render_widget(
                 Block::new()
                     .borders(Borders::TOP)
-                    .title(if app_state::is_editing() {
-                        "Insert mode"
-                    } else {
-                        "Normal Mode"
+                    .title(match away_summary.as_ref() {
+                        _ if quit_guard.is_asking(Instant::now()) => {
+                            format!("{status_title} | Discard unsaved reminder? Press Ctrl-c again to quit")
+                        }
+                        Some(summary) if started_at.elapsed() < welcome::AWAY_SUMMARY_DURATION => {
+                            format!("{status_title} | {summary}")
+                        }
+                        _ => status_title,
                     }),
                 base_layout[2],
             );
 
-            match app_state::get_view() {
-                AppView::Main => main_app.render(frame, base_layout[1]),
-                AppView::Reminders => reminders_app.render(frame, base_layout[1]),
-            };
+            if let Some(view) = app_state::get_view() {
+                app_registry.update(view);
+                app_registry.render(view, frame, base_layout[1]);
+            }
+
+            fired_toast.render(frame, base_layout[1], now);
+
+            fire_cue.render(frame, frame.size(), Instant::now());
         })?;
 
+        navigation.mark_clean();
+        app_registry.mark_clean();
+        last_drawn_at = Instant::now();
+
         events::clear();
 
         // 60 FPS = 16 millis. Since poll is blocking we can simulate it as a sleep
@@ -0,0 +1,62 @@
+use hkb_core::database::services::reminders;
+use hkb_date::date::SimpleDate;
+
+use crate::app_state;
+
+// reminders become overdue over time as well, not only when they change
+const OVERDUE_COUNT_REFRESH_INTERVAL_IN_SECONDS: u64 = 30;
+
+fn format_overdue_count(count: i64) -> Option<String> {
+    match count {
+        0 => None,
+        count => Some(format!("{count} overdue")),
+    }
+}
+
+/// Keeps the amount of overdue reminders shown in the status bar up to date
+pub struct OverdueStatus {
+    count: i64,
+    fetched_at: Option<SimpleDate>,
+    last_seen_reminders_change: usize,
+}
+
+impl OverdueStatus {
+    pub fn new() -> Self {
+        Self {
+            count: 0,
+            fetched_at: None,
+            last_seen_reminders_change: app_state::get_reminders_changes(),
+        }
+    }
+
+    pub fn on_tick(&mut self, now: SimpleDate) {
+        let reminders_changes = app_state::get_reminders_changes();
+        let is_stale = self.fetched_at.is_none_or(|fetched_at| {
+            (now - fetched_at).as_secs() >= OVERDUE_COUNT_REFRESH_INTERVAL_IN_SECONDS
+        });
+
+        if !is_stale && reminders_changes == self.last_seen_reminders_change {
+            return;
+        }
+
+        self.count = reminders::overdue_count(now).unwrap_or_default();
+        self.fetched_at = Some(now);
+        self.last_seen_reminders_change = reminders_changes;
+    }
+
+    /// Ex: "3 overdue", nothing when no reminder is overdue
+    pub fn label(&self) -> Option<String> {
+        format_overdue_count(self.count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_only_shows_the_count_when_reminders_are_overdue() {
+        assert_eq!(None, format_overdue_count(0));
+        assert_eq!(Some("3 overdue".to_string()), format_overdue_count(3));
+    }
+}
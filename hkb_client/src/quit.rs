@@ -0,0 +1,103 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::time::{Duration, Instant};
+
+/// How long a second Ctrl-c quits while there is unsaved input
+pub const QUIT_CONFIRMATION_WINDOW: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuitDecision {
+    /// Not a quit key, the event is handled as usual
+    Ignore,
+    /// Quitting would lose the unsaved input, so a second press is needed
+    AskToConfirm,
+    Quit,
+}
+
+fn is_quit_key(event: &KeyEvent) -> bool {
+    event.code == KeyCode::Char('c') && event.modifiers.contains(KeyModifiers::CONTROL)
+}
+
+/// Ctrl-c quits right away, unless there is unsaved input.
+/// Then it has to be pressed twice within `QUIT_CONFIRMATION_WINDOW`.
+#[derive(Default)]
+pub struct QuitGuard {
+    asked_at: Option<Instant>,
+}
+
+impl QuitGuard {
+    pub fn decide(
+        &mut self,
+        event: &KeyEvent,
+        has_unsaved_input: bool,
+        now: Instant,
+    ) -> QuitDecision {
+        if !is_quit_key(event) {
+            return QuitDecision::Ignore;
+        }
+
+        if !has_unsaved_input || self.is_asking(now) {
+            self.asked_at = None;
+
+            return QuitDecision::Quit;
+        }
+
+        self.asked_at = Some(now);
+
+        QuitDecision::AskToConfirm
+    }
+
+    /// Whether a second Ctrl-c would quit
+    pub fn is_asking(&self, now: Instant) -> bool {
+        self.asked_at
+            .is_some_and(|asked_at| now.duration_since(asked_at) < QUIT_CONFIRMATION_WINDOW)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn it_quits_right_away_without_unsaved_input() {
+        let mut guard = QuitGuard::default();
+        let now = Instant::now();
+
+        assert_eq!(
+            QuitDecision::Quit,
+            guard.decide(&key(KeyCode::Char('c'), KeyModifiers::CONTROL), false, now)
+        );
+        assert_eq!(
+            QuitDecision::Ignore,
+            guard.decide(&key(KeyCode::Char('c'), KeyModifiers::NONE), false, now)
+        );
+    }
+
+    #[test]
+    fn it_asks_to_confirm_with_unsaved_input() {
+        let mut guard = QuitGuard::default();
+        let ctrl_c = key(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        let now = Instant::now();
+
+        assert_eq!(QuitDecision::AskToConfirm, guard.decide(&ctrl_c, true, now));
+        assert!(guard.is_asking(now));
+        assert_eq!(
+            QuitDecision::Ignore,
+            guard.decide(&key(KeyCode::Char('a'), KeyModifiers::NONE), true, now)
+        );
+        assert_eq!(
+            QuitDecision::Quit,
+            guard.decide(&ctrl_c, true, now + Duration::from_millis(500))
+        );
+
+        // a late second press asks again
+        assert_eq!(QuitDecision::AskToConfirm, guard.decide(&ctrl_c, true, now));
+        assert_eq!(
+            QuitDecision::AskToConfirm,
+            guard.decide(&ctrl_c, true, now + QUIT_CONFIRMATION_WINDOW)
+        );
+    }
+}
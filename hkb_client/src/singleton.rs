@@ -1,15 +1,25 @@
+use hkb_core::logger::{debug, warn};
 use hkb_daemon_core::frame::Event as FrameEvent;
 
 use parking_lot::{MappedMutexGuard, Mutex, MutexGuard};
+use tokio::sync::mpsc::error::TrySendError;
 
 static GLOBAL_SINGLETON: Mutex<Option<Singleton>> = parking_lot::const_mutex(None);
 
 type ServerMsgSender = tokio::sync::mpsc::Sender<FrameEvent>;
 
+#[derive(Debug, PartialEq, Eq)]
+pub enum ServerMsgStatus {
+    Queued,
+    Dropped,
+}
+
 #[derive(Debug)]
 #[derive(Default)]
 pub struct Singleton {
     server_msg_sender: Option<ServerMsgSender>,
+    is_server_connected: bool,
+    dropped_server_msgs: usize,
 }
 
 
@@ -25,13 +35,115 @@ pub fn set_server_msg_sender(sender: ServerMsgSender) {
     Singleton::get_global().server_msg_sender = Some(sender);
 }
 
-pub fn send_server_msg(event: FrameEvent) {
-    let msg_sender = &Singleton::get_global().server_msg_sender;
+/// Marks whether the daemon is connected, events are only queued while it is
+pub fn set_server_connected(is_connected: bool) {
+    Singleton::get_global().is_server_connected = is_connected;
+}
+
+/// Queues an event for the daemon.
+/// The client has to be usable without a daemon, so while it is not connected
+/// the event is dropped instead. When the queue is full the event waits for room,
+/// the daemon is there and will get to it.
+pub fn send_server_msg(event: FrameEvent) -> ServerMsgStatus {
+    let mut singleton = Singleton::get_global();
+
+    let sender = match singleton.server_msg_sender.as_ref() {
+        Some(sender) if singleton.is_server_connected => sender,
+        _ => {
+            debug!(target: "CLIENT_SINGLETON", "No server connection, dropping event: {event:?}");
+            singleton.dropped_server_msgs += 1;
+
+            return ServerMsgStatus::Dropped;
+        }
+    };
+
+    match sender.try_send(event) {
+        Ok(_) => ServerMsgStatus::Queued,
+        Err(TrySendError::Full(event)) => {
+            debug!(target: "CLIENT_SINGLETON", "Server queue is full, waiting to queue event: {event:?}");
+
+            let sender = sender.clone();
+            tokio::spawn(async move { sender.send(event).await.unwrap_or_default() });
+
+            ServerMsgStatus::Queued
+        }
+        Err(TrySendError::Closed(event)) => {
+            warn!(target: "CLIENT_SINGLETON", "Server queue is closed, dropping event: {event:?}");
+            singleton.dropped_server_msgs += 1;
+
+            ServerMsgStatus::Dropped
+        }
+    }
+}
+
+#[cfg(test)]
+pub fn dropped_server_msgs_count() -> usize {
+    Singleton::get_global().dropped_server_msgs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn it_drops_messages_when_there_is_no_server() {
+        Singleton::get_global().server_msg_sender = None;
+        let dropped_server_msgs = dropped_server_msgs_count();
+
+        assert_eq!(
+            ServerMsgStatus::Dropped,
+            send_server_msg(FrameEvent::ReminderDeleted(1))
+        );
+        assert_eq!(dropped_server_msgs + 1, dropped_server_msgs_count());
+    }
+
+    #[test]
+    #[serial]
+    fn it_drops_messages_while_the_server_is_not_connected() {
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<FrameEvent>(1);
+        set_server_msg_sender(tx);
+        set_server_connected(false);
+        let dropped_server_msgs = dropped_server_msgs_count();
+
+        assert_eq!(
+            ServerMsgStatus::Dropped,
+            send_server_msg(FrameEvent::ReminderDeleted(1))
+        );
+        assert_eq!(dropped_server_msgs + 1, dropped_server_msgs_count());
+        assert!(rx.try_recv().is_err());
+
+        Singleton::get_global().server_msg_sender = None;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn it_waits_for_room_when_the_queue_is_full() {
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<FrameEvent>(1);
+        set_server_msg_sender(tx);
+        set_server_connected(true);
+        let dropped_server_msgs = dropped_server_msgs_count();
 
-    if let Some(sender) = msg_sender {
-        let handle = tokio::runtime::Handle::current();
-        let tx = sender.clone();
+        assert_eq!(
+            ServerMsgStatus::Queued,
+            send_server_msg(FrameEvent::ReminderDeleted(1))
+        );
+        assert_eq!(
+            ServerMsgStatus::Queued,
+            send_server_msg(FrameEvent::ReminderDeleted(2))
+        );
+        assert_eq!(dropped_server_msgs, dropped_server_msgs_count());
+        assert!(matches!(
+            rx.recv().await,
+            Some(FrameEvent::ReminderDeleted(1))
+        ));
+        assert!(matches!(
+            rx.recv().await,
+            Some(FrameEvent::ReminderDeleted(2))
+        ));
 
-        handle.spawn(async move { tx.send(event).await.unwrap_or_default() });
+        set_server_connected(false);
+        Singleton::get_global().server_msg_sender = None;
     }
 }
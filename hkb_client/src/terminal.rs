@@ -4,9 +4,12 @@ use crossterm::{
     ExecutableCommand,
 };
 use hkb_core::logger::{error, info};
-use ratatui::prelude::{CrosstermBackend, Terminal as TuiTerminal};
-use std::io::{self, stdout, Stdout};
-use std::panic;
+use ratatui::layout::Alignment;
+use ratatui::prelude::{CrosstermBackend, Frame, Rect, Terminal as TuiTerminal};
+use ratatui::widgets::{Paragraph, Wrap};
+use std::io::{self, stdout, Stdout, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
 use thiserror::Error as ThisError;
 
 #[derive(ThisError, Debug)]
@@ -19,9 +22,54 @@ pub enum TerminalError {
 
 pub type Terminal = TuiTerminal<CrosstermBackend<Stdout>>;
 
+/// Below this size the views have no room to render in, so only a message is shown
+pub const MIN_TERMINAL_WIDTH: u16 = 40;
+pub const MIN_TERMINAL_HEIGHT: u16 = 10;
+
+// set while a panic is going to be caught, so the terminal is kept as is
+static IS_CATCHING_PANICS: AtomicBool = AtomicBool::new(false);
+
+/// Runs the closure, returning the panic message instead of unwinding further if it panics.
+/// The panic is still logged, but the terminal is not closed.
+pub fn catch_panic<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    let was_catching = IS_CATCHING_PANICS.swap(true, Ordering::SeqCst);
+    // state the closure touched may be left half updated, callers should not rely on it afterwards
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    IS_CATCHING_PANICS.store(was_catching, Ordering::SeqCst);
+
+    result.map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string())
+    })
+}
+
+pub fn is_too_small(area: Rect) -> bool {
+    area.width < MIN_TERMINAL_WIDTH || area.height < MIN_TERMINAL_HEIGHT
+}
+
+pub fn render_too_small(frame: &mut Frame, area: Rect) {
+    frame.render_widget(
+        Paragraph::new(format!(
+            "Terminal too small, resize it to at least {MIN_TERMINAL_WIDTH}x{MIN_TERMINAL_HEIGHT}"
+        ))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true }),
+        area,
+    );
+}
+
 pub fn init() -> Result<Terminal, TerminalError> {
     // Return to cooked mode when app panics
     panic::set_hook(Box::new(|e| {
+        if IS_CATCHING_PANICS.load(Ordering::SeqCst) {
+            error!(target: "CLIENT_TERMINAL", "Caught PANIC: {e}");
+
+            return;
+        }
+
         close().unwrap_or_default();
 
         // Print panic info
@@ -56,6 +104,18 @@ pub fn set_cursor_to_default() {
         .expect("Should have been able to set cursor!");
 }
 
+pub fn ring_bell() {
+    let mut stdout = stdout();
+
+    if stdout
+        .write_all(b"\x07")
+        .and_then(|_| stdout.flush())
+        .is_err()
+    {
+        error!(target: "CLIENT_TERMINAL", "Failed to ring the bell :/");
+    }
+}
+
 #[allow(dead_code)]
 pub fn size() -> (u16, u16) {
     crossterminal::size().unwrap()
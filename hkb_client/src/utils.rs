@@ -1,6 +1,42 @@
+use hkb_core::database::services::reminders::{ReminderData, ReminderPrecision};
+use hkb_date::date::SimpleDate;
 use ratatui::prelude::{Constraint, Direction, Layout, Rect};
+use std::io::{Result as IOResult, Write};
+use std::process::{Command, Stdio};
+
+use crate::config::{get_display_timezone, get_reminder_precision};
 
 pub mod bounded_value;
+pub mod debounced_writer;
+pub mod fuzzy;
+pub mod highlight;
+pub mod search_history;
+
+/// Formats a date in the configured display timezone
+pub fn format_date(date: &SimpleDate, format: &str) -> String {
+    match get_display_timezone() {
+        // dates are stored as the local time they were entered in, even though they read as UTC
+        Some(timezone) => date.as_local().format_in_tz(timezone, format),
+        None => date.format(format),
+    }
+}
+
+/// Format of the time of day, seconds are only shown with second precision
+pub fn time_format() -> &'static str {
+    match get_reminder_precision() {
+        ReminderPrecision::Minute => "%H:%M",
+        ReminderPrecision::Second => "%H:%M:%S",
+    }
+}
+
+/// Time of day of the reminder, all day reminders have no meaningful time
+pub fn format_reminder_time(reminder: &ReminderData) -> String {
+    if reminder.all_day {
+        "all day".to_string()
+    } else {
+        format_date(&reminder.remind_at, time_format())
+    }
+}
 
 pub fn centered_layout(r: Rect, percent_x: u16, percent_y: u16) -> Rect {
     let vertical_layout = Layout::default()
@@ -21,3 +57,47 @@ pub fn centered_layout(r: Rect, percent_x: u16, percent_y: u16) -> Rect {
         ])
         .split(vertical_layout[1])[1]
 }
+
+/// Opens a file path or URL with the program the OS associates with it
+pub fn open_with_default_program(target: &str) -> IOResult<()> {
+    let program = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+
+    Command::new(program)
+        .arg(target)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    Ok(())
+}
+
+/// Copies the text to the system clipboard, through the clipboard program of the OS
+pub fn copy_to_clipboard(text: &str) -> IOResult<()> {
+    let (program, args): (&str, &[&str]) = if cfg!(target_os = "macos") {
+        ("pbcopy", &[])
+    } else if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        ("wl-copy", &[])
+    } else {
+        ("xclip", &["-selection", "clipboard"])
+    };
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    // the stdin is dropped after writing, so the program knows the text ended
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+
+    child.wait()?;
+
+    Ok(())
+}
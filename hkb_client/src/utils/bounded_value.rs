@@ -57,6 +57,7 @@ impl BoundedValue {
         self.max_val = max_val;
     }
 
+    #[allow(dead_code)]
     pub fn set_to_min(&mut self) {
         self.val = self.min_val;
     }
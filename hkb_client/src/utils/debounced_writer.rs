@@ -0,0 +1,96 @@
+use std::time::{Duration, Instant};
+
+/// Coalesces rapid updates into a single write, once no update
+/// has been received for the quiet period. Only the latest value is written.
+pub struct DebouncedWriter<T, F: FnMut(T)> {
+    pending: Option<T>,
+    last_update_at: Option<Instant>,
+    quiet_period: Duration,
+    write: F,
+}
+
+impl<T, F: FnMut(T)> DebouncedWriter<T, F> {
+    pub fn new(quiet_period: Duration, write: F) -> Self {
+        Self {
+            pending: None,
+            last_update_at: None,
+            quiet_period,
+            write,
+        }
+    }
+
+    pub fn update(&mut self, value: T, now: Instant) {
+        self.pending = Some(value);
+        self.last_update_at = Some(now);
+    }
+
+    /// Writes the pending value if the quiet period has passed.
+    /// Returns whether a write happened.
+    pub fn tick(&mut self, now: Instant) -> bool {
+        let Some(last_update_at) = self.last_update_at else {
+            return false;
+        };
+
+        if now.saturating_duration_since(last_update_at) < self.quiet_period {
+            return false;
+        }
+
+        self.flush()
+    }
+
+    /// Writes the pending value right away, ex: before exiting
+    pub fn flush(&mut self) -> bool {
+        self.last_update_at = None;
+
+        if let Some(value) = self.pending.take() {
+            (self.write)(value);
+
+            return true;
+        }
+
+        false
+    }
+
+    /// Drops the pending value without writing it
+    pub fn cancel(&mut self) {
+        self.pending = None;
+        self.last_update_at = None;
+    }
+
+    #[allow(dead_code)]
+    pub fn has_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{cell::RefCell, rc::Rc};
+
+    #[test]
+    fn it_coalesces_rapid_updates_into_a_single_write() {
+        let writes = Rc::new(RefCell::new(vec![]));
+        let writer_writes = writes.clone();
+        let mut writer = DebouncedWriter::new(Duration::from_millis(500), |value: String| {
+            writer_writes.borrow_mut().push(value)
+        });
+        let start = Instant::now();
+        let mut now = start;
+        let mut note = String::new();
+
+        for c in "buy some milk".chars() {
+            now += Duration::from_millis(50);
+            note.push(c);
+
+            writer.update(note.clone(), now);
+
+            assert!(!writer.tick(now));
+        }
+
+        assert!(writes.borrow().is_empty());
+        assert!(writer.tick(now + Duration::from_millis(500)));
+        assert!(!writer.tick(now + Duration::from_millis(1000)));
+        assert_eq!(vec!["buy some milk".to_owned()], *writes.borrow());
+    }
+}
@@ -0,0 +1,89 @@
+// bonuses on top of the point every matched character gets
+const CONSECUTIVE_BONUS: usize = 2;
+const WORD_START_BONUS: usize = 3;
+
+/// Scores how well the query matches the text, ignoring case.
+/// The characters of the query have to appear in the text in order, but not next to each other.
+/// Matches next to each other or at the start of a word score higher.
+/// `None` if the text does not match.
+pub fn fuzzy_score(text: &str, query: &str) -> Option<usize> {
+    let mut score = 0;
+    let mut text_chars = text.chars().enumerate();
+    let mut previous: Option<char> = None;
+    let mut last_match: Option<usize> = None;
+
+    for query_char in query.chars() {
+        loop {
+            let (i, text_char) = text_chars.next()?;
+            let is_word_start = previous.is_none_or(char::is_whitespace);
+            previous = Some(text_char);
+
+            if !text_char.to_lowercase().eq(query_char.to_lowercase()) {
+                continue;
+            }
+
+            score += 1;
+
+            if last_match.is_some_and(|last_match| last_match + 1 == i) {
+                score += CONSECUTIVE_BONUS;
+            }
+
+            if is_word_start {
+                score += WORD_START_BONUS;
+            }
+
+            last_match = Some(i);
+
+            break;
+        }
+    }
+
+    Some(score)
+}
+
+/// Items matching the query, from the best match. Equal matches keep their order.
+pub fn fuzzy_filter<'a, T>(items: &'a [T], query: &str, text: impl Fn(&T) -> &str) -> Vec<&'a T> {
+    let mut matches = items
+        .iter()
+        .filter_map(|item| fuzzy_score(text(item), query).map(|score| (score, item)))
+        .collect::<Vec<(usize, &T)>>();
+    matches.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+    matches.into_iter().map(|(_, item)| item).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_matches_characters_in_order_ignoring_case() {
+        assert!(fuzzy_score("Create reminder", "crem").is_some());
+        assert!(fuzzy_score("Create reminder", "CR").is_some());
+        assert!(fuzzy_score("Create reminder", "").is_some());
+        assert!(fuzzy_score("Create reminder", "mc").is_none());
+        assert!(fuzzy_score("Create", "Created").is_none());
+    }
+
+    #[test]
+    fn it_ranks_word_starts_and_consecutive_matches_first() {
+        let items = [
+            "Delete all overdue",
+            "Acknowledge due reminders",
+            "Add a tag",
+        ];
+
+        assert_eq!(
+            vec![&"Delete all overdue", &"Acknowledge due reminders"],
+            fuzzy_filter(&items, "de", |item| item)
+        );
+        assert_eq!(
+            vec![
+                &"Add a tag",
+                &"Delete all overdue",
+                &"Acknowledge due reminders"
+            ],
+            fuzzy_filter(&items, "ad", |item| item)
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Span;
+use std::ops::Range;
+
+/// Length in bytes of the query if the text starts with it, ignoring case
+fn match_len_at(text: &str, query: &str) -> Option<usize> {
+    let mut text_chars = text.char_indices();
+    let mut len = 0;
+
+    for query_char in query.chars() {
+        let (i, text_char) = text_chars.next()?;
+
+        if !text_char.to_lowercase().eq(query_char.to_lowercase()) {
+            return None;
+        }
+
+        len = i + text_char.len_utf8();
+    }
+
+    Some(len)
+}
+
+/// Byte ranges of every occurrence of the query in the text, ignoring case.
+/// Occurrences do not overlap.
+pub fn find_matches(text: &str, query: &str) -> Vec<Range<usize>> {
+    let mut matches = vec![];
+
+    if query.is_empty() {
+        return matches;
+    }
+
+    let mut start = 0;
+
+    while let Some(c) = text[start..].chars().next() {
+        match match_len_at(&text[start..], query) {
+            Some(len) => {
+                matches.push(start..start + len);
+                start += len;
+            }
+            None => start += c.len_utf8(),
+        }
+    }
+
+    matches
+}
+
+pub fn highlight_style() -> Style {
+    Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD)
+}
+
+/// Splits the text into spans, with the occurrences of the query highlighted
+pub fn highlight_matches(text: &str, query: &str) -> Vec<Span<'static>> {
+    let mut spans = vec![];
+    let mut last_end = 0;
+
+    for range in find_matches(text, query) {
+        if range.start > last_end {
+            spans.push(Span::raw(text[last_end..range.start].to_owned()));
+        }
+
+        spans.push(Span::styled(
+            text[range.clone()].to_owned(),
+            highlight_style(),
+        ));
+        last_end = range.end;
+    }
+
+    if last_end < text.len() || spans.is_empty() {
+        spans.push(Span::raw(text[last_end..].to_owned()));
+    }
+
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_finds_every_match_ignoring_case() {
+        assert_eq!(
+            vec![4..8, 13..17],
+            find_matches("Buy milk and MILK powder", "milk")
+        );
+        assert_eq!(vec![0..2, 2..4], find_matches("aaaaa", "AA"));
+        assert!(find_matches("Buy milk", "").is_empty());
+        assert!(find_matches("Buy milk", "bread").is_empty());
+    }
+
+    #[test]
+    fn it_highlights_the_matches() {
+        assert_eq!(
+            vec![
+                Span::raw("Buy "),
+                Span::styled("milk", highlight_style()),
+                Span::raw(" and "),
+                Span::styled("MILK", highlight_style()),
+            ],
+            highlight_matches("Buy milk and MILK", "milk")
+        );
+        assert_eq!(
+            vec![Span::raw("Buy milk")],
+            highlight_matches("Buy milk", "bread")
+        );
+    }
+}
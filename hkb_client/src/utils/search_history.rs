@@ -0,0 +1,153 @@
+use parking_lot::{MappedMutexGuard, Mutex, MutexGuard};
+use std::collections::VecDeque;
+
+pub const MAX_SEARCH_HISTORY: usize = 50;
+
+static GLOBAL_SEARCH_HISTORY: Mutex<Option<SearchHistory>> = parking_lot::const_mutex(None);
+
+/// Recent search queries, recalled from the newest to the oldest like a shell history
+pub struct SearchHistory {
+    queries: VecDeque<String>,
+    capacity: usize,
+    // index of the recalled query, counted from the newest one
+    recalled: Option<usize>,
+}
+
+impl SearchHistory {
+    fn get_global() -> MappedMutexGuard<'static, Self> {
+        MutexGuard::map(GLOBAL_SEARCH_HISTORY.lock(), |reader| {
+            reader.get_or_insert_with(|| Self::new(MAX_SEARCH_HISTORY))
+        })
+    }
+
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            queries: VecDeque::with_capacity(capacity),
+            capacity,
+            recalled: None,
+        }
+    }
+
+    /// Adds a query, dropping the oldest one once full.
+    /// Empty queries and repeats of the last query are skipped.
+    pub fn push(&mut self, query: String) {
+        self.recalled = None;
+
+        if query.is_empty() || self.queries.back() == Some(&query) {
+            return;
+        }
+
+        if self.queries.len() >= self.capacity {
+            self.queries.pop_front();
+        }
+
+        self.queries.push_back(query);
+    }
+
+    /// Steps to an older query, stopping at the oldest one
+    pub fn previous(&mut self) -> Option<&str> {
+        if self.queries.is_empty() {
+            return None;
+        }
+
+        let recalled = match self.recalled {
+            Some(recalled) => std::cmp::min(recalled + 1, self.queries.len() - 1),
+            None => 0,
+        };
+        self.recalled = Some(recalled);
+
+        self.recalled_query()
+    }
+
+    /// Steps to a newer query. Returns `None` once past the newest one.
+    pub fn next(&mut self) -> Option<&str> {
+        self.recalled = match self.recalled {
+            Some(0) | None => None,
+            Some(recalled) => Some(recalled - 1),
+        };
+
+        self.recalled_query()
+    }
+
+    pub fn reset_recall(&mut self) {
+        self.recalled = None;
+    }
+
+    fn recalled_query(&self) -> Option<&str> {
+        let recalled = self.recalled?;
+
+        self.queries
+            .get(self.queries.len() - 1 - recalled)
+            .map(|query| query.as_str())
+    }
+}
+
+pub fn push_query(query: String) {
+    SearchHistory::get_global().push(query);
+}
+
+pub fn recall_previous() -> Option<String> {
+    SearchHistory::get_global()
+        .previous()
+        .map(|query| query.to_owned())
+}
+
+pub fn recall_next() -> Option<String> {
+    SearchHistory::get_global()
+        .next()
+        .map(|query| query.to_owned())
+}
+
+pub fn reset_recall() {
+    SearchHistory::get_global().reset_recall();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_caps_the_amount_of_queries() {
+        let mut history = SearchHistory::new(2);
+
+        history.push("milk".to_owned());
+        history.push("bread".to_owned());
+        history.push("eggs".to_owned());
+
+        assert_eq!(vec!["bread", "eggs"], Vec::from(history.queries));
+    }
+
+    #[test]
+    fn it_skips_repeats_of_the_last_query() {
+        let mut history = SearchHistory::new(MAX_SEARCH_HISTORY);
+
+        history.push("milk".to_owned());
+        history.push("milk".to_owned());
+        history.push(String::new());
+        history.push("bread".to_owned());
+        history.push("milk".to_owned());
+
+        assert_eq!(vec!["milk", "bread", "milk"], Vec::from(history.queries));
+    }
+
+    #[test]
+    fn it_recalls_queries_from_the_newest_to_the_oldest() {
+        let mut history = SearchHistory::new(MAX_SEARCH_HISTORY);
+
+        assert_eq!(None, history.previous());
+
+        history.push("milk".to_owned());
+        history.push("bread".to_owned());
+
+        assert_eq!(Some("bread"), history.previous());
+        assert_eq!(Some("milk"), history.previous());
+        assert_eq!(Some("milk"), history.previous());
+        assert_eq!(Some("bread"), history.next());
+        assert_eq!(None, history.next());
+
+        history.previous();
+        history.push("eggs".to_owned());
+
+        assert_eq!(Some("eggs"), history.previous());
+    }
+}
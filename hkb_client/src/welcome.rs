@@ -0,0 +1,52 @@
+use hkb_core::database::services::{reminders, settings};
+use hkb_core::logger::error;
+use hkb_date::date::SimpleDate;
+use std::time::Duration;
+
+const LAST_OPENED_AT_SETTING: &str = "last_opened_at";
+
+/// How long the summary stays in the status bar after launch
+pub const AWAY_SUMMARY_DURATION: Duration = Duration::from_secs(5);
+
+fn format_away_summary(fired: i64) -> Option<String> {
+    match fired {
+        0 => None,
+        1 => Some("1 reminder fired while you were away".to_string()),
+        fired => Some(format!("{fired} reminders fired while you were away")),
+    }
+}
+
+/// Summarizes the reminders that fired since the client was last opened,
+/// then remembers `now` as the last time it was opened
+pub fn take_away_summary(now: SimpleDate) -> Option<String> {
+    let last_opened_at = settings::get_setting(LAST_OPENED_AT_SETTING)
+        .ok()
+        .flatten()
+        .and_then(|date| SimpleDate::parse_from_rfc3339(date).ok());
+
+    if let Err(e) = settings::set_setting(LAST_OPENED_AT_SETTING, now.to_string()) {
+        error!(target: "CLIENT", "Failed to store the last opened time: {e}");
+    }
+
+    let fired = reminders::count_fired_between(last_opened_at?, now).unwrap_or_default();
+
+    format_away_summary(fired)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_only_summarizes_when_reminders_fired() {
+        assert_eq!(None, format_away_summary(0));
+        assert_eq!(
+            Some("1 reminder fired while you were away".to_string()),
+            format_away_summary(1)
+        );
+        assert_eq!(
+            Some("3 reminders fired while you were away".to_string()),
+            format_away_summary(3)
+        );
+    }
+}
@@ -0,0 +1,6 @@
+CREATE TABLE attachments (
+  id INTEGER PRIMARY KEY,
+  reminder_id INTEGER NOT NULL REFERENCES reminders(id) ON DELETE CASCADE,
+  path TEXT NOT NULL,
+  created_at TIMESTAMP NOT NULL
+)
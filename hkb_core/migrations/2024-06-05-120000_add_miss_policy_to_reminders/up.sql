@@ -0,0 +1 @@
+ALTER TABLE reminders ADD COLUMN miss_policy TEXT NOT NULL DEFAULT 'keep'
@@ -0,0 +1,6 @@
+CREATE TABLE drafts (
+  view TEXT PRIMARY KEY NOT NULL,
+  note TEXT NOT NULL,
+  date TEXT NOT NULL,
+  updated_at TIMESTAMP NOT NULL
+)
@@ -0,0 +1 @@
+ALTER TABLE reminders ADD COLUMN pinned BOOLEAN NOT NULL DEFAULT 0
@@ -0,0 +1 @@
+ALTER TABLE reminders ADD COLUMN all_day BOOLEAN NOT NULL DEFAULT 0
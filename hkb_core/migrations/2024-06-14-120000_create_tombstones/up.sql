@@ -0,0 +1,5 @@
+CREATE TABLE tombstones (
+  id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+  reminder_id INTEGER NOT NULL,
+  deleted_at TEXT NOT NULL
+)
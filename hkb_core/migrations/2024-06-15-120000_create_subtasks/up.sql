@@ -0,0 +1,7 @@
+CREATE TABLE subtasks (
+  id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+  reminder_id INTEGER NOT NULL REFERENCES reminders(id) ON DELETE CASCADE,
+  text TEXT NOT NULL,
+  done BOOLEAN NOT NULL DEFAULT 0,
+  position INTEGER NOT NULL
+)
@@ -0,0 +1,2 @@
+ALTER TABLE reminders DROP COLUMN recurrence_count;
+ALTER TABLE reminders DROP COLUMN recurrence_until
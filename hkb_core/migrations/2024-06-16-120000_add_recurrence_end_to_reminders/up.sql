@@ -0,0 +1,2 @@
+ALTER TABLE reminders ADD COLUMN recurrence_until TIMESTAMP;
+ALTER TABLE reminders ADD COLUMN recurrence_count INTEGER
@@ -0,0 +1 @@
+DROP INDEX reminders_note_index
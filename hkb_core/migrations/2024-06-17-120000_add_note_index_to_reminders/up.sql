@@ -0,0 +1 @@
+CREATE INDEX reminders_note_index ON reminders (note)
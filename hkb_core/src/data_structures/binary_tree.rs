@@ -69,6 +69,26 @@ impl<T: Constraints> Node<T> {
         self.right.as_ref().map(|node| node.clone())
     }
 
+    // The setters and takers below exist for restructuring the tree, ex: rotations.
+    // They do not validate anything, so the caller must keep every value in the
+    // left subtree smaller and every value in the right subtree larger than `val`.
+
+    pub fn set_left(&mut self, left: Option<NodeRef<T>>) {
+        self.left = left;
+    }
+
+    pub fn set_right(&mut self, right: Option<NodeRef<T>>) {
+        self.right = right;
+    }
+
+    pub fn take_left(&mut self) -> Option<NodeRef<T>> {
+        self.left.take()
+    }
+
+    pub fn take_right(&mut self) -> Option<NodeRef<T>> {
+        self.right.take()
+    }
+
     pub fn height(&self) -> usize {
         self.height_recursive(self)
     }
@@ -87,9 +107,135 @@ impl<T: Constraints> Node<T> {
     }
 }
 
+impl<T: Constraints + Clone> Node<T> {
+    /// Returns the smallest value by following the leftmost chain.
+    /// As nodes implement `Ord`, `node.min()` resolves to `Ord::min`,
+    /// so call it as `Node::min(&node)`.
+    pub fn min(&self) -> T {
+        match self.get_left() {
+            Some(left) => Node::min(&left.borrow()),
+            None => self.val.clone(),
+        }
+    }
+
+    /// Returns the largest value by following the rightmost chain.
+    /// Call it as `Node::max(&node)` for the same reason as `Node::min`.
+    pub fn max(&self) -> T {
+        match self.get_right() {
+            Some(right) => Node::max(&right.borrow()),
+            None => self.val.clone(),
+        }
+    }
+
+    /// Returns the smallest value in the tree that is larger than `val`.
+    /// `val` does not need to be present in the tree.
+    pub fn successor(&self, val: &T) -> Option<T> {
+        if &self.val > val {
+            let left_successor = self
+                .get_left()
+                .and_then(|left| left.borrow().successor(val));
+
+            left_successor.or_else(|| Some(self.val.clone()))
+        } else {
+            self.get_right()
+                .and_then(|right| right.borrow().successor(val))
+        }
+    }
+
+    /// Returns the largest value in the tree that is smaller than `val`.
+    /// `val` does not need to be present in the tree.
+    pub fn predecessor(&self, val: &T) -> Option<T> {
+        if &self.val < val {
+            let right_predecessor = self
+                .get_right()
+                .and_then(|right| right.borrow().predecessor(val));
+
+            right_predecessor.or_else(|| Some(self.val.clone()))
+        } else {
+            self.get_left()
+                .and_then(|left| left.borrow().predecessor(val))
+        }
+    }
+
+    /// Collects the values within the inclusive range [lo, hi] in order.
+    /// Subtrees that cannot contain values in the range are not visited.
+    pub fn range(&self, lo: &T, hi: &T) -> Vec<T> {
+        let mut values = vec![];
+
+        self.range_recursive(lo, hi, &mut values);
+
+        values
+    }
+
+    fn range_recursive(&self, lo: &T, hi: &T, values: &mut Vec<T>) {
+        if &self.val > lo {
+            if let Some(left) = self.get_left() {
+                left.borrow().range_recursive(lo, hi, values);
+            }
+        }
+
+        if lo <= &self.val && &self.val <= hi {
+            values.push(self.val.clone());
+        }
+
+        if &self.val < hi {
+            if let Some(right) = self.get_right() {
+                right.borrow().range_recursive(lo, hi, values);
+            }
+        }
+    }
+
+    /// Collects every value of the tree in order
+    pub fn in_order(&self) -> Vec<T> {
+        let mut values = vec![];
+
+        self.in_order_recursive(&mut values);
+
+        values
+    }
+
+    fn in_order_recursive(&self, values: &mut Vec<T>) {
+        if let Some(left) = self.get_left() {
+            left.borrow().in_order_recursive(values);
+        }
+
+        values.push(self.val.clone());
+
+        if let Some(right) = self.get_right() {
+            right.borrow().in_order_recursive(values);
+        }
+    }
+
+    /// Builds a balanced tree from sorted values, the middle value becomes the root.
+    /// Returns `None` for no values.
+    pub fn from_sorted(values: &[T]) -> Option<NodeRef<T>> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let middle = values.len() / 2;
+        let node = Node {
+            val: values[middle].clone(),
+            left: Node::from_sorted(&values[..middle]),
+            right: Node::from_sorted(&values[middle + 1..]),
+        };
+
+        Some(Rc::new(RefCell::new(node)))
+    }
+
+    /// Rebuilds the tree as a balanced one, ex: after many inserts in order.
+    /// The values are copied, so the current tree stays as it is.
+    pub fn rebalance(&self) -> NodeRef<T> {
+        Node::from_sorted(&self.in_order())
+            .expect("a tree always has at least the value of its root")
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Node;
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::{Node, NodeRef};
 
     #[test]
     fn it_can_create_a_node_with_value() {
@@ -135,4 +281,153 @@ mod tests {
 
         assert_eq!(2, node.height());
     }
+
+    #[test]
+    fn it_can_take_and_set_children() {
+        let mut node = Node::new(3, Some(1), Some(5));
+        let left = node.take_left();
+        let right = node.take_right();
+
+        assert!(node.get_left().is_none());
+        assert!(node.get_right().is_none());
+
+        node.set_left(right);
+        node.set_right(left);
+
+        assert_eq!(5, node.get_left().unwrap().borrow().val);
+        assert_eq!(1, node.get_right().unwrap().borrow().val);
+    }
+
+    #[test]
+    fn it_can_be_rotated_to_the_left() {
+        //   2                4
+        //  / \              / \
+        // 1   4     =>     2   5
+        //    / \          / \
+        //   3   5        1   3
+        let root: NodeRef<i32> = Rc::new(RefCell::new(Node::with_nodes(
+            2,
+            Node::with_value(1),
+            Node::new(4, Some(3), Some(5)),
+        )));
+
+        let new_root = root.borrow_mut().take_right().unwrap();
+        let moved_child = new_root.borrow_mut().take_left();
+        root.borrow_mut().set_right(moved_child);
+        new_root.borrow_mut().set_left(Some(root));
+
+        let new_root = new_root.borrow();
+        let left = new_root.get_left().unwrap();
+
+        assert_eq!(4, new_root.val);
+        assert_eq!(5, new_root.get_right().unwrap().borrow().val);
+        assert_eq!(2, left.borrow().val);
+        assert_eq!(1, left.borrow().get_left().unwrap().borrow().val);
+        assert_eq!(3, left.borrow().get_right().unwrap().borrow().val);
+        assert_eq!(3, new_root.height());
+        assert_eq!(vec![1, 2, 3, 4, 5], new_root.range(&1, &5));
+    }
+
+    #[test]
+    fn it_builds_a_balanced_tree_from_sorted_values() {
+        let root = Node::from_sorted(&[1, 2, 3, 4, 5, 6, 7]).unwrap();
+        let root = root.borrow();
+
+        assert_eq!(4, root.val);
+        assert_eq!(3, root.height());
+        assert_eq!(vec![1, 2, 3, 4, 5, 6, 7], root.in_order());
+        assert!(Node::<i32>::from_sorted(&[]).is_none());
+    }
+
+    #[test]
+    fn it_rebalances_a_degenerate_tree() {
+        // 1 -> 2 -> ... -> 7, every node only has a right child
+        let mut node = Node::with_value(7);
+
+        for val in (1..7).rev() {
+            let mut parent = Node::with_value(val);
+            parent.set_right(Some(Rc::new(RefCell::new(node))));
+            node = parent;
+        }
+
+        assert_eq!(7, node.height());
+
+        let balanced = node.rebalance();
+        let balanced = balanced.borrow();
+
+        assert_eq!(3, balanced.height());
+        assert_eq!(4, balanced.val);
+        assert_eq!(vec![1, 2, 3, 4, 5, 6, 7], balanced.in_order());
+        assert_eq!(7, node.height());
+    }
+
+    #[test]
+    fn it_returns_values_within_a_range() {
+        let left_node = Node::new(4, Some(2), Some(6));
+        let right_node = Node::new(12, Some(10), Some(14));
+        let node = Node::with_nodes(8, left_node, right_node);
+
+        assert_eq!(vec![6, 8, 10], node.range(&5, &11));
+        assert_eq!(vec![2, 4, 6, 8, 10, 12, 14], node.range(&2, &14));
+        assert_eq!(vec![14], node.range(&14, &20));
+        assert!(node.range(&15, &20).is_empty());
+    }
+
+    #[test]
+    fn it_skips_subtrees_outside_of_the_range() {
+        // The tree is intentionally not a valid BST, 7 and 9 can only be found
+        // if we visit subtrees that are outside of the range
+        let left_node = Node::new(4, Some(7), Some(6));
+        let right_node = Node::new(12, Some(9), Some(14));
+        let node = Node::with_nodes(8, left_node, right_node);
+
+        assert_eq!(vec![6, 8], node.range(&5, &8));
+        assert_eq!(vec![12, 14], node.range(&12, &14));
+    }
+
+    #[test]
+    fn it_returns_the_min_and_max_of_a_single_node() {
+        let node = Node::with_value(3);
+
+        assert_eq!(3, Node::min(&node));
+        assert_eq!(3, Node::max(&node));
+    }
+
+    #[test]
+    fn it_returns_the_min_and_max_of_a_multi_level_tree() {
+        let left_node = Node::with_nodes(4, Node::new(2, Some(1), None), Node::with_value(6));
+        let right_node = Node::with_nodes(12, Node::with_value(10), Node::new(14, None, Some(15)));
+        let node = Node::with_nodes(8, left_node, right_node);
+
+        assert_eq!(1, Node::min(&node));
+        assert_eq!(15, Node::max(&node));
+    }
+
+    #[test]
+    fn it_returns_the_successor_of_a_value() {
+        let left_node = Node::new(4, Some(2), Some(6));
+        let right_node = Node::new(12, Some(10), Some(14));
+        let node = Node::with_nodes(8, left_node, right_node);
+
+        assert_eq!(Some(8), node.successor(&6));
+        assert_eq!(Some(10), node.successor(&8));
+        assert_eq!(Some(4), node.successor(&2));
+        assert_eq!(None, node.successor(&14));
+        assert_eq!(Some(10), node.successor(&9));
+        assert_eq!(Some(2), node.successor(&0));
+    }
+
+    #[test]
+    fn it_returns_the_predecessor_of_a_value() {
+        let left_node = Node::new(4, Some(2), Some(6));
+        let right_node = Node::new(12, Some(10), Some(14));
+        let node = Node::with_nodes(8, left_node, right_node);
+
+        assert_eq!(Some(6), node.predecessor(&8));
+        assert_eq!(Some(8), node.predecessor(&10));
+        assert_eq!(Some(12), node.predecessor(&14));
+        assert_eq!(None, node.predecessor(&2));
+        assert_eq!(Some(8), node.predecessor(&9));
+        assert_eq!(Some(14), node.predecessor(&20));
+    }
 }
@@ -1,2 +1,3 @@
 pub mod binary_tree;
 pub mod min_heap;
+pub mod reminder_index;
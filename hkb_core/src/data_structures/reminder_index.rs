@@ -0,0 +1,140 @@
+use hkb_date::date::SimpleDate;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::dtos::reminders::{ReminderData, ReminderId};
+
+// year, month and day of the month
+type DayKey = (i32, u32, u32);
+
+fn day_key(date: &SimpleDate) -> DayKey {
+    (date.year(), date.month(), date.day())
+}
+
+/// Lookups over already fetched reminders, so repeated queries do not hit the database.
+/// Days are taken from the timezone of each `remind_at`.
+#[derive(Debug, Default)]
+pub struct ReminderIndex {
+    reminders: Vec<ReminderData>,
+    by_id: HashMap<ReminderId, usize>,
+    by_day: BTreeMap<DayKey, Vec<usize>>,
+}
+
+impl ReminderIndex {
+    pub fn new(reminders: Vec<ReminderData>) -> Self {
+        let mut by_id = HashMap::with_capacity(reminders.len());
+        let mut by_day: BTreeMap<DayKey, Vec<usize>> = BTreeMap::new();
+
+        for (i, reminder) in reminders.iter().enumerate() {
+            by_id.insert(reminder.id, i);
+            by_day
+                .entry(day_key(&reminder.remind_at))
+                .or_default()
+                .push(i);
+        }
+
+        Self {
+            reminders,
+            by_id,
+            by_day,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.reminders.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reminders.is_empty()
+    }
+
+    pub fn get(&self, id: ReminderId) -> Option<&ReminderData> {
+        self.by_id.get(&id).map(|i| &self.reminders[*i])
+    }
+
+    /// Reminders on the day of the date, in the order they were given
+    pub fn on_day(&self, date: &SimpleDate) -> Vec<&ReminderData> {
+        self.by_day
+            .get(&day_key(date))
+            .map(|indexes| indexes.iter().map(|i| &self.reminders[*i]).collect())
+            .unwrap_or_default()
+    }
+
+    /// Days with at least one reminder, from the earliest, with their reminder count
+    pub fn days(&self) -> Vec<(DayKey, usize)> {
+        self.by_day
+            .iter()
+            .map(|(day, indexes)| (*day, indexes.len()))
+            .collect()
+    }
+
+    pub fn into_reminders(self) -> Vec<ReminderData> {
+        self.reminders
+    }
+}
+
+impl From<Vec<ReminderData>> for ReminderIndex {
+    fn from(reminders: Vec<ReminderData>) -> Self {
+        Self::new(reminders)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dtos::reminders::fakes;
+
+    fn date(date: &str) -> SimpleDate {
+        SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    fn create_index() -> ReminderIndex {
+        let reminders = [
+            (1, "2024-04-05 08:00:00"),
+            (2, "2024-04-06 09:00:00"),
+            (3, "2024-04-05 23:59:59"),
+            (4, "2024-05-05 08:00:00"),
+        ]
+        .into_iter()
+        .map(|(id, remind_at)| {
+            let mut reminder = fakes::create_reminder();
+            reminder.id = ReminderId(id);
+            reminder.remind_at = date(remind_at);
+
+            reminder
+        })
+        .collect::<Vec<ReminderData>>();
+
+        ReminderIndex::new(reminders)
+    }
+
+    #[test]
+    fn it_looks_up_reminders_by_id() {
+        let index = create_index();
+
+        assert_eq!(4, index.len());
+        assert_eq!(
+            Some(date("2024-04-06 09:00:00")),
+            index.get(ReminderId(2)).map(|r| r.remind_at)
+        );
+        assert_eq!(None, index.get(ReminderId(5)));
+    }
+
+    #[test]
+    fn it_looks_up_reminders_by_day() {
+        let index = create_index();
+
+        assert_eq!(
+            vec![1, 3],
+            index
+                .on_day(&date("2024-04-05 12:00:00"))
+                .iter()
+                .map(|reminder| reminder.id.0)
+                .collect::<Vec<i64>>()
+        );
+        assert!(index.on_day(&date("2024-04-07 12:00:00")).is_empty());
+        assert_eq!(
+            vec![((2024, 4, 5), 2), ((2024, 4, 6), 1), ((2024, 5, 5), 1)],
+            index.days()
+        );
+    }
+}
@@ -1,10 +1,17 @@
 use cfg_if::cfg_if;
+use diesel::migration::MigrationSource;
 use diesel::{result::Error as DieselResultError, Connection, ConnectionError};
 use diesel_migrations::{EmbeddedMigrations, MigrationHarness};
+use hkb_date::date::DateError;
 use log::{debug, error};
 use parking_lot::Mutex;
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::str::FromStr;
 use thiserror::Error as ThisError;
 
+use crate::dtos::reminders::ReminderId;
+
 pub(crate) mod models;
 mod schema;
 pub mod services;
@@ -15,10 +22,24 @@ pub enum DatabaseError {
     DatabaseNotInitialized,
     #[error("Failed to run migrations")]
     FailedToRunMigrations,
+    #[error("Migration version {0} is embedded more than once")]
+    DuplicateMigrationVersion(String),
     #[error("Failed to establish a connection!")]
     FailedToEstablishConnection(#[from] ConnectionError),
     #[error(transparent)]
     FailedToFetchResult(#[from] DieselResultError),
+    #[error("Failed to {operation}: {source}")]
+    OperationFailed {
+        operation: &'static str,
+        #[source]
+        source: DieselResultError,
+    },
+    #[error(transparent)]
+    InvalidDate(#[from] DateError),
+    #[error("Note is longer than {0} characters")]
+    NoteTooLong(usize),
+    #[error("Reminder with id {0} already exists")]
+    ReminderIdTaken(ReminderId),
 }
 
 cfg_if! {
@@ -33,11 +54,157 @@ cfg_if! {
     }
 }
 
+pub(crate) type DatabaseBackend = <DatabaseConnection as Connection>::Backend;
+
 static GLOBAL_CONNECTION: Mutex<Option<DatabaseConnection>> = parking_lot::const_mutex(None);
 
 pub type DatabaseResult<T> = Result<T, DatabaseError>;
 
+pub trait DatabaseResultExt<T> {
+    /// Labels a failed query with the operation it was part of. Ex: "create reminder"
+    /// Errors that already say what went wrong (ex: `NoteTooLong`) are returned as they are.
+    fn context(self, operation: &'static str) -> DatabaseResult<T>;
+}
+
+impl<T> DatabaseResultExt<T> for DatabaseResult<T> {
+    fn context(self, operation: &'static str) -> DatabaseResult<T> {
+        self.map_err(|e| match e {
+            DatabaseError::FailedToFetchResult(source) => {
+                DatabaseError::OperationFailed { operation, source }
+            }
+            e => e,
+        })
+    }
+}
+
+/// How safely SQLite flushes writes to disk, see its `synchronous` pragma
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Synchronous {
+    Off,
+    Normal,
+    Full,
+    Extra,
+}
+
+impl FromStr for Synchronous {
+    type Err = String;
+
+    fn from_str(synchronous: &str) -> Result<Self, Self::Err> {
+        match synchronous.trim().to_lowercase().as_str() {
+            "off" => Ok(Synchronous::Off),
+            "normal" => Ok(Synchronous::Normal),
+            "full" => Ok(Synchronous::Full),
+            "extra" => Ok(Synchronous::Extra),
+            _ => Err(format!("Unknown synchronous mode: {synchronous}")),
+        }
+    }
+}
+
+impl Display for Synchronous {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Synchronous::Off => write!(f, "OFF"),
+            Synchronous::Normal => write!(f, "NORMAL"),
+            Synchronous::Full => write!(f, "FULL"),
+            Synchronous::Extra => write!(f, "EXTRA"),
+        }
+    }
+}
+
+/// SQLite pragmas applied when the connection is opened, `None` keeps the SQLite default
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DatabaseTuning {
+    /// How long a query waits for a lock held by another process (ex: the daemon), in milliseconds
+    pub busy_timeout: Option<u32>,
+    /// Pages kept in memory, negative values are in KiB instead. Ex: -8000 for 8 MB
+    pub cache_size: Option<i64>,
+    pub synchronous: Option<Synchronous>,
+}
+
+impl DatabaseTuning {
+    /// Reads the tuning from HKB_DB_BUSY_TIMEOUT, HKB_DB_CACHE_SIZE and HKB_DB_SYNCHRONOUS.
+    /// Invalid values are ignored.
+    pub fn from_env() -> Self {
+        let env = |name: &str| std::env::var(name).ok();
+
+        Self {
+            // Ex: HKB_DB_BUSY_TIMEOUT=5000
+            busy_timeout: env("HKB_DB_BUSY_TIMEOUT")
+                .and_then(|timeout| timeout.trim().parse().ok()),
+            // Ex: HKB_DB_CACHE_SIZE=-8000
+            cache_size: env("HKB_DB_CACHE_SIZE").and_then(|size| size.trim().parse().ok()),
+            // Mode is one of: off, normal, full or extra. Ex: HKB_DB_SYNCHRONOUS=normal
+            synchronous: env("HKB_DB_SYNCHRONOUS").and_then(|mode| mode.parse().ok()),
+        }
+    }
+}
+
+#[cfg(feature = "sqlite-database")]
+fn apply_tuning(
+    connection: &mut DatabaseConnection,
+    tuning: &DatabaseTuning,
+) -> diesel::QueryResult<()> {
+    use diesel::RunQueryDsl;
+
+    // pragmas do not take bound values, the values are numbers or a known mode so they are inlined
+    let pragmas = [
+        tuning
+            .busy_timeout
+            .map(|timeout| format!("PRAGMA busy_timeout = {timeout}")),
+        tuning
+            .cache_size
+            .map(|size| format!("PRAGMA cache_size = {size}")),
+        tuning
+            .synchronous
+            .map(|mode| format!("PRAGMA synchronous = {mode}")),
+    ];
+
+    for pragma in pragmas.into_iter().flatten() {
+        debug!(target: "CORE_DATABASE", "Applying {pragma}");
+
+        diesel::sql_query(pragma).execute(connection)?;
+    }
+
+    Ok(())
+}
+
+/// Migration sets are run one after another, so a version in more than one set
+/// would only be applied once and the order would depend on the sets passed.
+fn ensure_unique_migration_versions(migrations: &[EmbeddedMigrations]) -> DatabaseResult<()> {
+    let mut versions = HashSet::new();
+
+    for migration_set in migrations {
+        let migration_set =
+            MigrationSource::<DatabaseBackend>::migrations(migration_set).map_err(|e| {
+                error!(target: "CORE_DATABASE", "Failed to read migrations: {e}");
+
+                DatabaseError::FailedToRunMigrations
+            })?;
+
+        for migration in migration_set {
+            let version = migration.name().version().to_string();
+
+            if !versions.insert(version.clone()) {
+                return Err(DatabaseError::DuplicateMigrationVersion(version));
+            }
+        }
+    }
+
+    Ok(())
+}
+
 pub fn init_database(url: &str, migrations: Vec<EmbeddedMigrations>) -> Result<(), DatabaseError> {
+    init_database_with_tuning(url, migrations, DatabaseTuning::default())
+}
+
+/// Like `init_database`, applying the tuning right after the connection is opened
+pub fn init_database_with_tuning(
+    url: &str,
+    migrations: Vec<EmbeddedMigrations>,
+    tuning: DatabaseTuning,
+) -> Result<(), DatabaseError> {
+    ensure_unique_migration_versions(&migrations)?;
+
     let mut connection = {
         cfg_if! {
             if #[cfg(feature = "mysql-database")] {
@@ -48,6 +215,19 @@ pub fn init_database(url: &str, migrations: Vec<EmbeddedMigrations>) -> Result<(
         }
     }?;
 
+    // SQLite does not enforce foreign keys unless told to
+    // and we rely on them to cascade deletes
+    #[cfg(feature = "sqlite-database")]
+    {
+        use diesel::RunQueryDsl;
+
+        diesel::sql_query("PRAGMA foreign_keys = ON").execute(&mut connection)?;
+        apply_tuning(&mut connection, &tuning)?;
+    }
+
+    #[cfg(not(feature = "sqlite-database"))]
+    let _ = tuning;
+
     debug!(target: "CORE_DATABASE", "Running migrations");
     // TODO: maybe we can use iter.enumurate() for this?
     // for now we just use a variable as it is easy
@@ -85,3 +265,60 @@ pub fn within_database<T, F: FnOnce(&mut DatabaseConnection) -> DatabaseResult<T
         Err(DatabaseError::DatabaseNotInitialized)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use diesel_migrations::embed_migrations;
+
+    use super::*;
+
+    const MIGRATIONS: EmbeddedMigrations = embed_migrations!("./migrations");
+
+    #[cfg(feature = "sqlite-database")]
+    #[test]
+    fn it_applies_the_configured_pragmas() {
+        use diesel::sql_types::BigInt;
+        use diesel::{QueryableByName, RunQueryDsl};
+
+        #[derive(QueryableByName)]
+        struct Pragma {
+            #[diesel(sql_type = BigInt)]
+            value: i64,
+        }
+
+        // a connection of its own, so the global one of the other tests is left alone
+        let mut connection = DatabaseConnection::establish(":memory:").unwrap();
+
+        apply_tuning(
+            &mut connection,
+            &DatabaseTuning {
+                busy_timeout: Some(5000),
+                cache_size: Some(-8000),
+                synchronous: Some(Synchronous::Normal),
+            },
+        )
+        .unwrap();
+
+        let mut read_pragma = |pragma: &str, column: &str| {
+            diesel::sql_query(format!("SELECT {column} AS value FROM pragma_{pragma}()"))
+                .get_result::<Pragma>(&mut connection)
+                .unwrap()
+                .value
+        };
+
+        assert_eq!(5000, read_pragma("busy_timeout", "timeout"));
+        assert_eq!(-8000, read_pragma("cache_size", "cache_size"));
+        // NORMAL is stored as 1
+        assert_eq!(1, read_pragma("synchronous", "synchronous"));
+        assert_eq!(Ok(Synchronous::Full), Synchronous::from_str("FULL"));
+        assert!(Synchronous::from_str("sometimes").is_err());
+    }
+
+    #[test]
+    fn it_rejects_migration_versions_embedded_more_than_once() {
+        assert!(matches!(
+            init_database(":memory:", vec![MIGRATIONS, MIGRATIONS]),
+            Err(DatabaseError::DuplicateMigrationVersion(version)) if version == "20240423191606"
+        ));
+    }
+}
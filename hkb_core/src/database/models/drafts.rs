@@ -0,0 +1,19 @@
+use crate::database::schema::drafts;
+use diesel::prelude::{Insertable, Queryable, Selectable};
+
+#[derive(Debug, Queryable, Selectable, Insertable)]
+#[diesel(table_name = drafts)]
+#[cfg_attr(
+    feature = "mysql-database",
+    diesel(check_for_backend(diesel::mysql::Mysql))
+)]
+#[cfg_attr(
+    feature = "sqlite-database",
+    diesel(check_for_backend(diesel::sqlite::Sqlite))
+)]
+pub(crate) struct Draft {
+    pub view: String,
+    pub note: String,
+    pub date: String,
+    pub updated_at: String,
+}
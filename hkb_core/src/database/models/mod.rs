@@ -1 +1,4 @@
+pub mod drafts;
 pub mod reminders;
+pub mod settings;
+pub mod subtasks;
@@ -1,10 +1,10 @@
-use crate::database::schema::reminders;
+use crate::database::schema::{attachments, reminders};
 use diesel::{
     prelude::{Insertable, Queryable, Selectable},
     query_builder::AsChangeset,
 };
 
-#[derive(Debug, Queryable, Selectable)]
+#[derive(Debug, Queryable, Selectable, Insertable)]
 #[diesel(table_name = reminders)]
 #[cfg_attr(
     feature = "mysql-database",
@@ -17,21 +17,66 @@ use diesel::{
 pub(crate) struct Reminder {
     pub id: i64,
     pub note: String,
+    pub body: Option<String>,
     pub remind_at: String,
     pub created_at: String,
+    pub attachment: Option<String>,
+    pub recurrence: Option<String>,
+    pub completed_at: Option<String>,
+    pub acknowledged_at: Option<String>,
+    pub miss_policy: String,
+    pub pinned: bool,
+    pub category: Option<String>,
+    pub all_day: bool,
+    pub recurrence_until: Option<String>,
+    pub recurrence_count: Option<i64>,
+    pub color: Option<String>,
 }
 
 #[derive(Debug, AsChangeset)]
 #[diesel(table_name = reminders)]
 pub(crate) struct UpdateReminder {
     pub note: Option<String>,
+    pub body: Option<String>,
     pub remind_at: Option<String>,
+    pub attachment: Option<String>,
+    pub recurrence: Option<String>,
+    pub miss_policy: Option<String>,
 }
 
 #[derive(Insertable)]
 #[diesel(table_name = reminders)]
 pub(crate) struct CreateReminder {
     pub note: String,
+    pub body: Option<String>,
     pub remind_at: String,
     pub created_at: String,
+    pub attachment: Option<String>,
+    pub recurrence: Option<String>,
+    pub miss_policy: String,
+}
+
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = attachments)]
+#[cfg_attr(
+    feature = "mysql-database",
+    diesel(check_for_backend(diesel::mysql::Mysql))
+)]
+#[cfg_attr(
+    feature = "sqlite-database",
+    diesel(check_for_backend(diesel::sqlite::Sqlite))
+)]
+pub(crate) struct Attachment {
+    pub id: i64,
+    pub reminder_id: i64,
+    pub path: String,
+    pub created_at: String,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = attachments)]
+pub(crate) struct CreateAttachment {
+    pub reminder_id: i64,
+    pub path: String,
+    pub created_at: String,
 }
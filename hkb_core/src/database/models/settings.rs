@@ -0,0 +1,17 @@
+use crate::database::schema::settings;
+use diesel::prelude::{Insertable, Queryable, Selectable};
+
+#[derive(Debug, Queryable, Selectable, Insertable)]
+#[diesel(table_name = settings)]
+#[cfg_attr(
+    feature = "mysql-database",
+    diesel(check_for_backend(diesel::mysql::Mysql))
+)]
+#[cfg_attr(
+    feature = "sqlite-database",
+    diesel(check_for_backend(diesel::sqlite::Sqlite))
+)]
+pub(crate) struct Setting {
+    pub key: String,
+    pub value: String,
+}
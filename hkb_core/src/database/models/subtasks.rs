@@ -0,0 +1,28 @@
+use crate::database::schema::subtasks;
+use diesel::prelude::{Insertable, Queryable, Selectable};
+
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = subtasks)]
+#[cfg_attr(
+    feature = "mysql-database",
+    diesel(check_for_backend(diesel::mysql::Mysql))
+)]
+#[cfg_attr(
+    feature = "sqlite-database",
+    diesel(check_for_backend(diesel::sqlite::Sqlite))
+)]
+pub(crate) struct Subtask {
+    pub id: i64,
+    pub reminder_id: i64,
+    pub text: String,
+    pub done: bool,
+    pub position: i64,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = subtasks)]
+pub(crate) struct CreateSubtask {
+    pub reminder_id: i64,
+    pub text: String,
+    pub position: i64,
+}
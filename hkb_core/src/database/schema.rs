@@ -2,7 +2,92 @@ diesel::table! {
     reminders (id) {
         id -> Int8,
         note -> Varchar,
+        body -> Nullable<Varchar>,
         remind_at -> Date,
         created_at -> Date,
+        attachment -> Nullable<Varchar>,
+        recurrence -> Nullable<Varchar>,
+        completed_at -> Nullable<Date>,
+        acknowledged_at -> Nullable<Date>,
+        miss_policy -> Varchar,
+        pinned -> Bool,
+        category -> Nullable<Varchar>,
+        all_day -> Bool,
+        recurrence_until -> Nullable<Date>,
+        recurrence_count -> Nullable<Int8>,
+        color -> Nullable<Varchar>,
     }
 }
+
+diesel::table! {
+    attachments (id) {
+        id -> Int8,
+        reminder_id -> Int8,
+        path -> Varchar,
+        created_at -> Date,
+    }
+}
+
+diesel::table! {
+    drafts (view) {
+        view -> Varchar,
+        note -> Varchar,
+        date -> Varchar,
+        updated_at -> Date,
+    }
+}
+
+diesel::table! {
+    settings (key) {
+        key -> Varchar,
+        value -> Varchar,
+    }
+}
+
+diesel::table! {
+    tags (id) {
+        id -> Int8,
+        name -> Varchar,
+    }
+}
+
+diesel::table! {
+    reminder_tags (reminder_id, tag_id) {
+        reminder_id -> Int8,
+        tag_id -> Int8,
+    }
+}
+
+diesel::table! {
+    tombstones (id) {
+        id -> Int8,
+        reminder_id -> Int8,
+        deleted_at -> Date,
+    }
+}
+
+diesel::table! {
+    subtasks (id) {
+        id -> Int8,
+        reminder_id -> Int8,
+        text -> Varchar,
+        done -> Bool,
+        position -> Int8,
+    }
+}
+
+diesel::joinable!(attachments -> reminders (reminder_id));
+diesel::joinable!(reminder_tags -> reminders (reminder_id));
+diesel::joinable!(reminder_tags -> tags (tag_id));
+diesel::joinable!(subtasks -> reminders (reminder_id));
+
+diesel::allow_tables_to_appear_in_same_query!(
+    attachments,
+    drafts,
+    reminder_tags,
+    reminders,
+    settings,
+    subtasks,
+    tags,
+    tombstones
+);
@@ -0,0 +1,135 @@
+pub use crate::dtos::drafts::*;
+use diesel::{
+    upsert::excluded, ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl, SelectableHelper,
+};
+use hkb_date::date::SimpleDate;
+use log::debug;
+
+use crate::database::{
+    self,
+    models::drafts::Draft,
+    schema::drafts::{self, dsl as drafts_dsl},
+    DatabaseResult,
+};
+
+impl From<Draft> for DraftData {
+    fn from(val: Draft) -> Self {
+        DraftData {
+            view: val.view,
+            note: val.note,
+            date: val.date,
+            updated_at: SimpleDate::parse_from_rfc3339(val.updated_at).unwrap(),
+        }
+    }
+}
+
+/// Saves the draft of a view, overwriting the previous one.
+/// A view has at most one draft.
+pub fn save_draft(
+    view: impl Into<String>,
+    note: impl Into<String>,
+    date: impl Into<String>,
+) -> DatabaseResult<DraftData> {
+    database::within_database(|conn| {
+        let draft = Draft {
+            view: view.into(),
+            note: note.into(),
+            date: date.into(),
+            updated_at: SimpleDate::local().to_string(),
+        };
+
+        debug!(target: "CORE_DRAFTS_SERVICE", "Saving draft of view {}", draft.view);
+
+        let saved_draft = diesel::insert_into(drafts::table)
+            .values(&draft)
+            .on_conflict(drafts_dsl::view)
+            .do_update()
+            .set((
+                drafts_dsl::note.eq(excluded(drafts_dsl::note)),
+                drafts_dsl::date.eq(excluded(drafts_dsl::date)),
+                drafts_dsl::updated_at.eq(excluded(drafts_dsl::updated_at)),
+            ))
+            .returning(Draft::as_returning())
+            .get_result(conn)?;
+
+        Ok(saved_draft.into())
+    })
+}
+
+pub fn fetch_draft(view: &str) -> DatabaseResult<Option<DraftData>> {
+    database::within_database(|conn| {
+        debug!(target: "CORE_DRAFTS_SERVICE", "Fetching draft of view {view}");
+
+        let draft = drafts_dsl::drafts
+            .find(view)
+            .select(Draft::as_select())
+            .first(conn)
+            .optional()?;
+
+        Ok(draft.map(|draft| draft.into()))
+    })
+}
+
+pub fn clear_draft(view: &str) -> DatabaseResult<()> {
+    database::within_database(|conn| {
+        diesel::delete(drafts_dsl::drafts.find(view)).execute(conn)?;
+
+        debug!(target: "CORE_DRAFTS_SERVICE", "Cleared draft of view {view}");
+
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use self::database::init_database;
+    use ctor::ctor;
+    use diesel_migrations::{embed_migrations, EmbeddedMigrations};
+    use serial_test::serial;
+    pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("./migrations");
+
+    use super::*;
+
+    #[ctor]
+    fn init() {
+        init_database(":memory:", vec![MIGRATIONS]).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn it_can_save_a_draft() {
+        save_draft("reminders_create", "Buy", "tomorrow").unwrap();
+        let draft = save_draft("reminders_create", "Buy milk", "tomorrow at 10").unwrap();
+
+        assert_eq!("reminders_create", draft.view);
+        assert_eq!("Buy milk", draft.note);
+        assert_eq!("tomorrow at 10", draft.date);
+
+        clear_draft("reminders_create").unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn it_can_restore_a_draft() {
+        let draft = save_draft("reminders_create", "Buy milk", "in 5 min").unwrap();
+
+        assert_eq!(Some(draft), fetch_draft("reminders_create").unwrap());
+        assert_eq!(None, fetch_draft("reminders_edit").unwrap());
+
+        clear_draft("reminders_create").unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn it_can_clear_a_draft() {
+        save_draft("reminders_create", "Buy milk", "").unwrap();
+        let other_draft = save_draft("reminders_edit", "Call mom", "").unwrap();
+
+        clear_draft("reminders_create").unwrap();
+
+        assert_eq!(None, fetch_draft("reminders_create").unwrap());
+        assert_eq!(Some(other_draft), fetch_draft("reminders_edit").unwrap());
+
+        clear_draft("reminders_edit").unwrap();
+    }
+}
@@ -1 +1,6 @@
+pub mod drafts;
 pub mod reminders;
+pub mod settings;
+pub mod subtasks;
+pub mod tags;
+pub mod tombstones;
@@ -1,36 +1,247 @@
 pub use crate::dtos::reminders::*;
 use diesel::{
-    sql_types::Date as SqlDateType, ExpressionMethods, IntoSql, QueryDsl, RunQueryDsl,
-    SelectableHelper,
+    debug_query,
+    dsl::{count_star, sql, AsSelect, SqlTypeOf},
+    query_builder::QueryFragment,
+    result::{DatabaseErrorKind, Error as DieselResultError},
+    sql_types::{Bool, Date as SqlDateType, Text},
+    upsert::excluded,
+    BoxableExpression, Connection, ExpressionMethods, IntoSql, NullableExpressionMethods, QueryDsl,
+    RunQueryDsl, SelectableHelper, TextExpressionMethods,
+};
+use hkb_date::{
+    date::{DateError, SimpleDate, Weekday},
+    duration::Duration,
+};
+use log::{debug, log_enabled, trace, Level};
+use parking_lot::Mutex;
+use std::{
+    collections::VecDeque,
+    str::FromStr,
+    sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering},
 };
-use hkb_date::date::SimpleDate;
-use log::debug;
 
 use crate::database::{
     self,
-    models::reminders::{CreateReminder, Reminder, UpdateReminder},
-    schema::reminders::{self, dsl as reminders_dsl},
-    DatabaseResult,
+    models::reminders::{Attachment, CreateAttachment, CreateReminder, Reminder, UpdateReminder},
+    schema::{
+        attachments::{self, dsl as attachments_dsl},
+        reminders::{self, dsl as reminders_dsl},
+    },
+    services::tombstones::record_tombstones,
+    DatabaseBackend, DatabaseError, DatabaseResult, DatabaseResultExt,
 };
 
-impl From<Reminder> for ReminderData {
-    fn from(val: Reminder) -> Self {
-        ReminderData {
-            id: val.id,
+pub const DEFAULT_MAX_NOTE_LENGTH: usize = 10_000;
+
+static MAX_NOTE_LENGTH: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_NOTE_LENGTH);
+
+/// Sets the maximum amount of characters a reminder note can have.
+/// It is enforced for every insert and update, no matter where they come from.
+pub fn set_max_note_length(length: usize) {
+    MAX_NOTE_LENGTH.store(length, Ordering::Relaxed);
+}
+
+pub fn get_max_note_length() -> usize {
+    MAX_NOTE_LENGTH.load(Ordering::Relaxed)
+}
+
+pub const DEFAULT_DELETE_CONFIRMATION_THRESHOLD: usize = 10;
+
+static DELETE_CONFIRMATION_THRESHOLD: AtomicUsize =
+    AtomicUsize::new(DEFAULT_DELETE_CONFIRMATION_THRESHOLD);
+
+/// Sets how many reminders can be deleted at once before the user has to confirm it
+pub fn set_delete_confirmation_threshold(threshold: usize) {
+    DELETE_CONFIRMATION_THRESHOLD.store(threshold, Ordering::Relaxed);
+}
+
+pub fn get_delete_confirmation_threshold() -> usize {
+    DELETE_CONFIRMATION_THRESHOLD.load(Ordering::Relaxed)
+}
+
+/// Whether deleting `rows` reminders at once should be confirmed first.
+/// Guards against a mis-specified filter wiping every reminder.
+pub fn requires_delete_confirmation(rows: usize) -> bool {
+    rows > get_delete_confirmation_threshold()
+}
+
+static PRECISION: Mutex<ReminderPrecision> = parking_lot::const_mutex(ReminderPrecision::Second);
+
+/// Sets the precision `remind_at` is stored with.
+/// With minute precision the seconds are dropped when a reminder is created or updated.
+pub fn set_precision(precision: ReminderPrecision) {
+    *PRECISION.lock() = precision;
+}
+
+pub fn get_precision() -> ReminderPrecision {
+    *PRECISION.lock()
+}
+
+fn apply_precision(remind_at: SimpleDate) -> SimpleDate {
+    match get_precision() {
+        ReminderPrecision::Minute => remind_at.truncate_to_minute(),
+        ReminderPrecision::Second => remind_at,
+    }
+}
+
+static DEFAULT_ORDER: Mutex<(ReminderOrderColumn, OrderDirection)> =
+    parking_lot::const_mutex((ReminderOrderColumn::Id, OrderDirection::Asc));
+
+/// Sets how fetched reminders are ordered when no `OrderBy` option is given
+pub fn set_default_order(column: ReminderOrderColumn, direction: OrderDirection) {
+    *DEFAULT_ORDER.lock() = (column, direction);
+    invalidate_cache();
+}
+
+pub fn get_default_order() -> (ReminderOrderColumn, OrderDirection) {
+    *DEFAULT_ORDER.lock()
+}
+
+// days of the weekend, a bit for each day counted from monday
+static WEEKEND_DAYS: AtomicU8 = AtomicU8::new(weekend_mask(&[Weekday::Sat, Weekday::Sun]));
+
+const fn weekend_mask(days: &[Weekday]) -> u8 {
+    let mut mask = 0;
+    let mut i = 0;
+
+    while i < days.len() {
+        mask |= 1 << days[i].num_days_from_monday();
+        i += 1;
+    }
+
+    mask
+}
+
+/// Sets the days reminders are not deferred to, saturday and sunday by default
+pub fn set_weekend_days(days: &[Weekday]) {
+    WEEKEND_DAYS.store(weekend_mask(days), Ordering::Relaxed);
+}
+
+pub fn is_weekend_day(day: Weekday) -> bool {
+    WEEKEND_DAYS.load(Ordering::Relaxed) & (1 << day.num_days_from_monday()) != 0
+}
+
+static CACHE_ENABLED: AtomicBool = AtomicBool::new(false);
+// most queries are made of dates taken from the clock, so each of them gets a new key,
+// only the most recently used ones are kept
+const MAX_CACHED_FETCHES: usize = 32;
+// fetched reminders, keyed by the query options they were fetched with,
+// the least recently used first
+type FetchCache = VecDeque<(String, Vec<ReminderData>)>;
+static FETCH_CACHE: Mutex<Option<FetchCache>> = parking_lot::const_mutex(None);
+
+/// Keeps fetched reminders in memory, so repeated fetches with the same options skip the database.
+/// Every write made through this service clears it. Writes from other processes
+/// (ex: the daemon) are not seen, so only enable it where that is acceptable.
+pub fn set_cache_enabled(enabled: bool) {
+    CACHE_ENABLED.store(enabled, Ordering::Relaxed);
+    invalidate_cache();
+}
+
+pub fn is_cache_enabled() -> bool {
+    CACHE_ENABLED.load(Ordering::Relaxed)
+}
+
+pub fn invalidate_cache() {
+    *FETCH_CACHE.lock() = None;
+}
+
+fn cached_fetch(cache_key: &str) -> Option<Vec<ReminderData>> {
+    let mut cache = FETCH_CACHE.lock();
+    let cache = cache.as_mut()?;
+    let index = cache.iter().position(|(key, _)| key == cache_key)?;
+    let entry = cache.remove(index)?;
+    let reminders = entry.1.clone();
+
+    cache.push_back(entry);
+
+    Some(reminders)
+}
+
+fn cache_fetch(cache_key: String, reminders: Vec<ReminderData>) {
+    let mut cache = FETCH_CACHE.lock();
+    let cache = cache.get_or_insert_with(VecDeque::new);
+
+    cache.retain(|(key, _)| *key != cache_key);
+
+    if cache.len() >= MAX_CACHED_FETCHES {
+        cache.pop_front();
+    }
+
+    cache.push_back((cache_key, reminders));
+}
+
+#[cfg(test)]
+fn cached_fetches_count() -> usize {
+    FETCH_CACHE.lock().as_ref().map_or(0, FetchCache::len)
+}
+
+fn validate_note(note: &str) -> DatabaseResult<()> {
+    let max_note_length = get_max_note_length();
+
+    if note.chars().count() > max_note_length {
+        return Err(DatabaseError::NoteTooLong(max_note_length));
+    }
+
+    Ok(())
+}
+
+impl TryFrom<Reminder> for ReminderData {
+    type Error = DateError;
+
+    fn try_from(val: Reminder) -> Result<Self, Self::Error> {
+        Ok(ReminderData {
+            id: val.id.into(),
             note: val.note,
-            remind_at: SimpleDate::parse_from_rfc3339(val.remind_at).unwrap(),
-            created_at: SimpleDate::parse_from_rfc3339(val.created_at).unwrap(),
-        }
+            body: val.body,
+            remind_at: SimpleDate::parse_from_rfc3339(val.remind_at)?,
+            created_at: SimpleDate::parse_from_rfc3339(val.created_at)?,
+            attachment: val.attachment,
+            recurrence: val
+                .recurrence
+                .and_then(|recurrence| recurrence.parse().ok()),
+            completed_at: val
+                .completed_at
+                .map(SimpleDate::parse_from_rfc3339)
+                .transpose()?,
+            acknowledged_at: val
+                .acknowledged_at
+                .map(SimpleDate::parse_from_rfc3339)
+                .transpose()?,
+            miss_policy: val.miss_policy.parse().unwrap_or_default(),
+            pinned: val.pinned,
+            category: val.category,
+            all_day: val.all_day,
+            recurrence_until: val
+                .recurrence_until
+                .map(SimpleDate::parse_from_rfc3339)
+                .transpose()?,
+            recurrence_count: val.recurrence_count,
+            color: val.color.and_then(|color| color.parse().ok()),
+        })
     }
 }
 
 impl From<ReminderData> for Reminder {
     fn from(val: ReminderData) -> Self {
         Reminder {
-            id: val.id,
+            id: val.id.0,
             note: val.note,
+            body: val.body,
             remind_at: val.remind_at.to_string(),
             created_at: val.created_at.to_string(),
+            attachment: val.attachment,
+            recurrence: val.recurrence.map(|recurrence| recurrence.to_string()),
+            completed_at: val.completed_at.map(|date| date.to_string()),
+            acknowledged_at: val.acknowledged_at.map(|date| date.to_string()),
+            miss_policy: val.miss_policy.to_string(),
+            pinned: val.pinned,
+            category: val.category,
+            all_day: val.all_day,
+            recurrence_until: val.recurrence_until.map(|date| date.to_string()),
+            recurrence_count: val.recurrence_count,
+            color: val.color.map(|color| color.to_string()),
         }
     }
 }
@@ -39,8 +250,12 @@ impl From<CreateReminderData> for CreateReminder {
     fn from(val: CreateReminderData) -> Self {
         CreateReminder {
             note: val.note,
+            body: val.body,
             remind_at: val.remind_at.to_string(),
             created_at: SimpleDate::local().to_string(),
+            attachment: val.attachment,
+            recurrence: val.recurrence.map(|recurrence| recurrence.to_string()),
+            miss_policy: val.miss_policy.to_string(),
         }
     }
 }
@@ -49,11 +264,71 @@ impl From<UpdateReminderData> for UpdateReminder {
     fn from(val: UpdateReminderData) -> Self {
         UpdateReminder {
             note: val.note,
+            body: val.body,
             remind_at: val.remind_at.map(|date| date.to_string()),
+            attachment: val.attachment,
+            recurrence: val.recurrence.map(|recurrence| recurrence.to_string()),
+            miss_policy: val.miss_policy.map(|miss_policy| miss_policy.to_string()),
+        }
+    }
+}
+
+impl TryFrom<Attachment> for AttachmentData {
+    type Error = DateError;
+
+    fn try_from(val: Attachment) -> Result<Self, Self::Error> {
+        Ok(AttachmentData {
+            id: val.id,
+            reminder_id: val.reminder_id.into(),
+            path: val.path,
+            created_at: SimpleDate::parse_from_rfc3339(val.created_at)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReminderOrderColumn {
+    Id,
+    RemindAt,
+    CreatedAt,
+    Pinned,
+}
+
+impl FromStr for ReminderOrderColumn {
+    type Err = String;
+
+    fn from_str(column: &str) -> Result<Self, Self::Err> {
+        match column.trim().to_lowercase().as_str() {
+            "id" => Ok(ReminderOrderColumn::Id),
+            "remind_at" => Ok(ReminderOrderColumn::RemindAt),
+            "created_at" => Ok(ReminderOrderColumn::CreatedAt),
+            "pinned" => Ok(ReminderOrderColumn::Pinned),
+            _ => Err(format!("Unknown order column: {column}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderDirection {
+    Asc,
+    Desc,
+}
+
+impl FromStr for OrderDirection {
+    type Err = String;
+
+    fn from_str(direction: &str) -> Result<Self, Self::Err> {
+        match direction.trim().to_lowercase().as_str() {
+            "asc" => Ok(OrderDirection::Asc),
+            "desc" => Ok(OrderDirection::Desc),
+            _ => Err(format!("Unknown order direction: {direction}")),
         }
     }
 }
 
+/// Filters are combined, while `OrderBy` options apply in the sequence given,
+/// each following one breaking the ties of the previous. Ordered results always
+/// fall back to the id, so reminders with equal values come back in a stable order.
 #[derive(Debug)]
 pub enum ReminderQueryOptions<'a> {
     RemindAtGe {
@@ -66,340 +341,3089 @@ pub enum ReminderQueryOptions<'a> {
         end_date: SimpleDate,
         start_date: SimpleDate,
     },
+    /// Reminders within `window` before or after the `pivot`
+    RemindAtAround {
+        pivot: SimpleDate,
+        window: Duration,
+    },
     WithIds {
-        ids: &'a Vec<i64>,
+        ids: &'a Vec<ReminderId>,
     },
     WithoutIds {
-        ids: &'a Vec<i64>,
+        ids: &'a Vec<ReminderId>,
+    },
+    NoteLike {
+        text: &'a str,
+    },
+    WithColor {
+        color: ReminderColor,
+    },
+    /// Only the pinned reminders, or only the ones that are not
+    Pinned {
+        pinned: bool,
+    },
+    OrderBy {
+        column: ReminderOrderColumn,
+        direction: OrderDirection,
+    },
+    Limit {
+        limit: i64,
+    },
+    /// Skips that many reminders, paired with `Limit` to fetch a page
+    Offset {
+        offset: i64,
     },
+    /// Sorts the fetched reminders by how close they are to `now`, in the past or the future.
+    /// The distance depends on `now`, so the sort happens in memory, after `Limit` and `Offset`.
+    /// Reminders equally close keep the order of the query.
+    ClosestTo {
+        now: SimpleDate,
+    },
+}
+
+fn raw_ids(ids: &[ReminderId]) -> Vec<i64> {
+    ids.iter().map(|id| id.0).collect()
+}
+
+fn around_bounds(pivot: SimpleDate, window: &Duration) -> DatabaseResult<(SimpleDate, SimpleDate)> {
+    Ok((pivot.sub_duration(window)?, pivot.add_duration(window)?))
+}
+
+type BoxedReminderQuery<'a> =
+    reminders::BoxedQuery<'a, DatabaseBackend, SqlTypeOf<AsSelect<Reminder, DatabaseBackend>>>;
+
+/// Logs the rendered SQL of a query.
+/// It is only rendered when trace logs are enabled, as formatting it is not free.
+fn log_sql<T: QueryFragment<DatabaseBackend>>(query: &T) {
+    if log_enabled!(target: "CORE_REMINDERS_SERVICE", Level::Trace) {
+        trace!(target: "CORE_REMINDERS_SERVICE", "SQL: {}", debug_query::<DatabaseBackend, _>(query));
+    }
+}
+
+type BoxedReminderFilter =
+    Box<dyn BoxableExpression<reminders::table, DatabaseBackend, SqlType = Bool>>;
+
+/// The condition a filter option puts on the reminders, shared by fetching, counting and deleting.
+/// `None` for the options that only order or page the reminders.
+fn reminder_filter(option: &ReminderQueryOptions) -> DatabaseResult<Option<BoxedReminderFilter>> {
+    let filter: BoxedReminderFilter = match option {
+        ReminderQueryOptions::RemindAtBetween {
+            end_date,
+            start_date,
+        } => Box::new(reminders_dsl::remind_at.between(
+            start_date.to_string().into_sql::<SqlDateType>(),
+            end_date.to_string().into_sql::<SqlDateType>(),
+        )),
+        ReminderQueryOptions::RemindAtAround { pivot, window } => {
+            let (start_date, end_date) = around_bounds(*pivot, window)?;
+
+            Box::new(reminders_dsl::remind_at.between(
+                start_date.to_string().into_sql::<SqlDateType>(),
+                end_date.to_string().into_sql::<SqlDateType>(),
+            ))
+        }
+        ReminderQueryOptions::RemindAtGe { date } => {
+            Box::new(reminders_dsl::remind_at.ge(date.to_string().into_sql::<SqlDateType>()))
+        }
+        ReminderQueryOptions::RemindAtLe { date } => {
+            Box::new(reminders_dsl::remind_at.le(date.to_string().into_sql::<SqlDateType>()))
+        }
+        ReminderQueryOptions::WithIds { ids } => Box::new(reminders_dsl::id.eq_any(raw_ids(ids))),
+        ReminderQueryOptions::WithoutIds { ids } => {
+            Box::new(diesel::dsl::not(reminders_dsl::id.eq_any(raw_ids(ids))))
+        }
+        ReminderQueryOptions::NoteLike { text } => {
+            Box::new(reminders_dsl::note.like(format!("%{text}%")))
+        }
+        // a NULL color never equals, so unlabeled reminders are left out either way
+        ReminderQueryOptions::WithColor { color } => {
+            Box::new(reminders_dsl::color.assume_not_null().eq(color.to_string()))
+        }
+        ReminderQueryOptions::Pinned { pinned } => Box::new(reminders_dsl::pinned.eq(*pinned)),
+        ReminderQueryOptions::OrderBy { .. }
+        | ReminderQueryOptions::Limit { .. }
+        | ReminderQueryOptions::Offset { .. }
+        | ReminderQueryOptions::ClosestTo { .. } => return Ok(None),
+    };
+
+    Ok(Some(filter))
+}
+
+fn build_fetch_query(
+    options: Option<Vec<ReminderQueryOptions>>,
+) -> DatabaseResult<BoxedReminderQuery> {
+    let mut query = reminders_dsl::reminders
+        .select(Reminder::as_select())
+        .into_boxed();
+    let mut is_ordered = false;
+
+    // the first order replaces the default one, the rest are chained after it
+    macro_rules! order_query {
+        ($order:expr) => {{
+            if is_ordered {
+                query.then_order_by($order)
+            } else {
+                query.order_by($order)
+            }
+        }};
+    }
+
+    macro_rules! order_by_column {
+        ($column:expr, $direction:expr) => {
+            match ($column, $direction) {
+                (ReminderOrderColumn::Id, OrderDirection::Asc) => {
+                    order_query!(reminders_dsl::id.asc())
+                }
+                (ReminderOrderColumn::Id, OrderDirection::Desc) => {
+                    order_query!(reminders_dsl::id.desc())
+                }
+                (ReminderOrderColumn::RemindAt, OrderDirection::Asc) => {
+                    order_query!(reminders_dsl::remind_at.asc())
+                }
+                (ReminderOrderColumn::RemindAt, OrderDirection::Desc) => {
+                    order_query!(reminders_dsl::remind_at.desc())
+                }
+                (ReminderOrderColumn::CreatedAt, OrderDirection::Asc) => {
+                    order_query!(reminders_dsl::created_at.asc())
+                }
+                (ReminderOrderColumn::CreatedAt, OrderDirection::Desc) => {
+                    order_query!(reminders_dsl::created_at.desc())
+                }
+                (ReminderOrderColumn::Pinned, OrderDirection::Asc) => {
+                    order_query!(reminders_dsl::pinned.asc())
+                }
+                (ReminderOrderColumn::Pinned, OrderDirection::Desc) => {
+                    order_query!(reminders_dsl::pinned.desc())
+                }
+            }
+        };
+    }
+
+    if let Some(options) = options {
+        for option in options {
+            if let Some(filter) = reminder_filter(&option)? {
+                query = query.filter(filter);
+            }
+
+            match option {
+                ReminderQueryOptions::OrderBy { column, direction } => {
+                    query = order_by_column!(column, direction);
+                    is_ordered = true;
+                }
+                ReminderQueryOptions::Limit { limit } => {
+                    query = query.limit(limit);
+                }
+                ReminderQueryOptions::Offset { offset } => {
+                    query = query.offset(offset);
+                }
+                // sorted after the fetch, see `fetch_reminders`
+                ReminderQueryOptions::ClosestTo { .. } => {}
+                // applied by `reminder_filter`
+                _ => {}
+            }
+        }
+    }
+
+    if !is_ordered {
+        // without an `OrderBy` option the configured default applies
+        let (column, direction) = get_default_order();
+        query = order_by_column!(column, direction);
+
+        // ids are unique, so there are no ties to break
+        if column == ReminderOrderColumn::Id {
+            return Ok(query);
+        }
+    }
+
+    Ok(query.then_order_by(reminders_dsl::id.asc()))
 }
 
+/// Fetches the reminders matching every option.
+/// Without an `OrderBy` option they are ordered by the default order, ascending id unless
+/// `set_default_order` changed it. Ties of any order are broken by ascending id,
+/// so the same reminders are always listed in the same order.
 pub fn fetch_reminders(
     options: Option<Vec<ReminderQueryOptions>>,
+) -> DatabaseResult<Vec<ReminderData>> {
+    let cache_key = is_cache_enabled().then(|| format!("{options:?}"));
+
+    if let Some(cache_key) = cache_key.as_ref() {
+        if let Some(reminders) = cached_fetch(cache_key) {
+            debug!(target: "CORE_REMINDERS_SERVICE", "Reminders fetched from cache: {}", reminders.len());
+
+            return Ok(reminders);
+        }
+    }
+
+    let reminders = fetch_reminders_from_database(options)?;
+
+    if let Some(cache_key) = cache_key {
+        cache_fetch(cache_key, reminders.clone());
+    }
+
+    Ok(reminders)
+}
+
+fn fetch_reminders_from_database(
+    options: Option<Vec<ReminderQueryOptions>>,
 ) -> DatabaseResult<Vec<ReminderData>> {
     database::within_database(|conn| {
         debug!(target: "CORE_REMINDERS_SERVICE", "Fetching reminders with options: {options:?}");
 
-        let mut query = reminders_dsl::reminders
-            .select(Reminder::as_select())
-            .order_by(reminders_dsl::id.asc())
-            .into_boxed();
-
-        if let Some(options) = options {
-            for option in options {
-                match option {
-                    ReminderQueryOptions::RemindAtBetween {
-                        end_date,
-                        start_date,
-                    } => {
-                        query = query.filter(reminders_dsl::remind_at.between(
-                            start_date.to_string().into_sql::<SqlDateType>(),
-                            end_date.to_string().into_sql::<SqlDateType>(),
-                        ));
-                    }
-                    ReminderQueryOptions::RemindAtGe { date } => {
-                        query = query.filter(
-                            reminders_dsl::remind_at.ge(date.to_string().into_sql::<SqlDateType>()),
-                        );
-                    }
-                    ReminderQueryOptions::RemindAtLe { date } => {
-                        query = query.filter(
-                            reminders_dsl::remind_at.le(date.to_string().into_sql::<SqlDateType>()),
-                        );
-                    }
-                    ReminderQueryOptions::WithIds { ids } => {
-                        query = query.filter(reminders_dsl::id.eq_any(ids));
-                    }
-                    ReminderQueryOptions::WithoutIds { ids } => {
-                        query = query.filter(diesel::dsl::not(reminders_dsl::id.eq_any(ids)));
-                    }
-                }
-            }
-        }
+        let closest_to = options.iter().flatten().find_map(|option| match option {
+            ReminderQueryOptions::ClosestTo { now } => Some(*now),
+            _ => None,
+        });
+        let query = build_fetch_query(options)?;
+        log_sql(&query);
 
-        let reminders: Vec<ReminderData> = query
+        let mut reminders: Vec<ReminderData> = query
             .get_results(conn)?
             .into_iter()
-            .map(|reminder| reminder.into())
-            .collect();
+            .map(ReminderData::try_from)
+            .collect::<Result<_, _>>()?;
+
+        if let Some(now) = closest_to {
+            reminders.sort_by_key(|reminder| reminder.remind_at.diff(&now));
+        }
 
         debug!(target: "CORE_REMINDERS_SERVICE", "Reminders fetched: {}", reminders.len());
 
         Ok(reminders)
     })
+    .context("fetch reminders")
 }
 
-pub fn fetch_reminder(id: i64) -> DatabaseResult<ReminderData> {
+/// Counts the reminders `fetch_reminders` would fetch with the same filters.
+/// Ordering and paging do not change which reminders match,
+/// so `OrderBy`, `Limit`, `Offset` and `ClosestTo` are ignored.
+pub fn count_reminders(options: Option<Vec<ReminderQueryOptions>>) -> DatabaseResult<i64> {
     database::within_database(|conn| {
-        debug!(target: "CORE_REMINDERS_SERVICE", "Fetching reminder with id {id}");
+        let mut query = reminders_dsl::reminders.count().into_boxed();
 
-        let reminder = reminders_dsl::reminders
-            .find(id)
+        for option in options.iter().flatten() {
+            if let Some(filter) = reminder_filter(option)? {
+                query = query.filter(filter);
+            }
+        }
+
+        log_sql(&query);
+
+        let count = query.get_result(conn)?;
+
+        debug!(target: "CORE_REMINDERS_SERVICE", "Reminders counted with options {options:?}: {count}");
+
+        Ok(count)
+    })
+    .context("count reminders")
+}
+
+/// Fetches the next `limit` reminders that are not due yet, soonest first.
+/// They are never cached, the query is different every time the clock moves.
+pub fn fetch_upcoming(limit: usize) -> DatabaseResult<Vec<ReminderData>> {
+    fetch_reminders_from_database(Some(vec![
+        ReminderQueryOptions::RemindAtGe {
+            date: SimpleDate::local(),
+        },
+        ReminderQueryOptions::OrderBy {
+            column: ReminderOrderColumn::RemindAt,
+            direction: OrderDirection::Asc,
+        },
+        ReminderQueryOptions::Limit {
+            limit: limit as i64,
+        },
+    ]))
+}
+
+/// Fetches the reminders within the range, grouped by the day they are due on.
+/// Days without reminders are skipped and reminders are ordered by their remind date.
+pub fn fetch_grouped_by_day(
+    range: (SimpleDate, SimpleDate),
+) -> DatabaseResult<Vec<(SimpleDate, Vec<ReminderData>)>> {
+    group_by_day(range, false)
+}
+
+/// Fetches the reminders from now until the end of the `days`th day, today being the first,
+/// grouped by day for an agenda. Days without reminders are kept, with no reminders.
+pub fn fetch_next_days(days: i64) -> DatabaseResult<Vec<(SimpleDate, Vec<ReminderData>)>> {
+    fetch_next_days_from(SimpleDate::local(), days)
+}
+
+/// Same as `fetch_next_days`, counting from `now`
+pub fn fetch_next_days_from(
+    now: SimpleDate,
+    days: i64,
+) -> DatabaseResult<Vec<(SimpleDate, Vec<ReminderData>)>> {
+    // no days are fetched for negative values
+    let days = u32::try_from(days).unwrap_or_default();
+
+    if days == 0 {
+        return Ok(vec![]);
+    }
+
+    let end_date = now.add_duration(Duration::Day(days - 1))?.end_of_day()?;
+
+    group_by_day((now, end_date), true)
+}
+
+fn group_by_day(
+    range: (SimpleDate, SimpleDate),
+    keep_empty_days: bool,
+) -> DatabaseResult<Vec<(SimpleDate, Vec<ReminderData>)>> {
+    let (start_date, end_date) = range;
+    let mut reminders = fetch_reminders(Some(vec![
+        ReminderQueryOptions::RemindAtBetween {
+            end_date,
+            start_date,
+        },
+        ReminderQueryOptions::OrderBy {
+            column: ReminderOrderColumn::RemindAt,
+            direction: OrderDirection::Asc,
+        },
+    ]))?
+    .into_iter()
+    .peekable();
+    let mut groups = vec![];
+
+    for day in SimpleDate::iter_days(start_date, end_date) {
+        let mut day_reminders = vec![];
+
+        while let Some(reminder) = reminders.next_if(|reminder| {
+            let remind_at = reminder.remind_at;
+
+            (remind_at.year(), remind_at.month(), remind_at.day())
+                == (day.year(), day.month(), day.day())
+        }) {
+            day_reminders.push(reminder);
+        }
+
+        if keep_empty_days || !day_reminders.is_empty() {
+            groups.push((day, day_reminders));
+        }
+    }
+
+    Ok(groups)
+}
+
+/// Exports the reminders within the range as a day by day plain text agenda
+pub fn export_agenda(range: (SimpleDate, SimpleDate)) -> DatabaseResult<String> {
+    let agenda = fetch_grouped_by_day(range)?
+        .into_iter()
+        .map(|(day, reminders)| {
+            let mut lines = vec![day.format("%A, %d %B %Y")];
+
+            lines.extend(reminders.iter().map(|reminder| {
+                format!("  {} {}", reminder.remind_at.format("%H:%M"), reminder.note)
+            }));
+
+            lines.join("\n")
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n");
+
+    Ok(agenda)
+}
+
+/// Formats the reminders of the day `now` is in as a plain text list, ordered by time.
+/// Meant to be piped into a mail, ex: from a cron job.
+pub fn today_agenda_text(now: SimpleDate) -> DatabaseResult<String> {
+    let reminders = fetch_reminders(Some(vec![
+        ReminderQueryOptions::RemindAtBetween {
+            end_date: now.end_of_day()?,
+            start_date: now.start_of_day()?,
+        },
+        ReminderQueryOptions::OrderBy {
+            column: ReminderOrderColumn::RemindAt,
+            direction: OrderDirection::Asc,
+        },
+    ]))?;
+    let mut lines = vec![format!("Agenda for {}", now.format("%A, %d %B %Y"))];
+
+    if reminders.is_empty() {
+        lines.push("  Nothing scheduled for today, enjoy your day!".to_string());
+    }
+
+    lines.extend(reminders.iter().map(|reminder| {
+        let time = if reminder.all_day {
+            "all day".to_string()
+        } else {
+            reminder.remind_at.format("%H:%M")
+        };
+
+        format!("  {time} {}", reminder.note)
+    }));
+
+    Ok(lines.join("\n"))
+}
+
+/// Scores how relevant a reminder is for a search query. The lower the better.
+/// An exact match scores 0, a prefix match 1, anything else 2.
+pub fn relevance_score(reminder: &ReminderData, query: &str) -> u8 {
+    let note = reminder.note.to_lowercase();
+    let query = query.to_lowercase();
+
+    if note == query {
+        0
+    } else if note.starts_with(&query) {
+        1
+    } else {
+        2
+    }
+}
+
+/// Sorts reminders by their relevance for the query.
+/// Ties are broken by the soonest remind date.
+pub fn rank_by_relevance(reminders: &mut [ReminderData], query: &str) {
+    reminders.sort_by_cached_key(|reminder| {
+        let remind_at = reminder.remind_at;
+
+        (
+            relevance_score(reminder, query),
+            remind_at.year(),
+            remind_at.month(),
+            remind_at.day(),
+            remind_at.hour(),
+            remind_at.minute(),
+            remind_at.second(),
+        )
+    });
+}
+
+/// Search reminders by their note, optionally limited to a date range.
+/// An empty text matches all notes, otherwise results are ranked by relevance.
+pub fn search(
+    text: &str,
+    range: Option<(SimpleDate, SimpleDate)>,
+) -> DatabaseResult<Vec<ReminderData>> {
+    let mut options = Vec::with_capacity(2);
+
+    if !text.is_empty() {
+        options.push(ReminderQueryOptions::NoteLike { text });
+    }
+
+    if let Some((start_date, end_date)) = range {
+        options.push(ReminderQueryOptions::RemindAtBetween {
+            end_date,
+            start_date,
+        });
+    }
+
+    let mut reminders = fetch_reminders(Some(options))?;
+
+    if !text.is_empty() {
+        rank_by_relevance(&mut reminders, text);
+    }
+
+    Ok(reminders)
+}
+
+/// Reminders whose note is exactly `note`, case included. Unlike `search` nothing is ranked,
+/// so it suits checking for duplicates. The comparison uses the index on the note column.
+pub fn find_by_note(note: &str) -> DatabaseResult<Vec<ReminderData>> {
+    database::within_database(|conn| {
+        debug!(target: "CORE_REMINDERS_SERVICE", "Finding reminders with note {note}");
+
+        let reminders = reminders_dsl::reminders
+            .filter(reminders_dsl::note.eq(note))
+            .order(reminders_dsl::id.asc())
             .select(Reminder::as_select())
-            .first(conn)?;
+            .load(conn)?;
 
-        debug!(target: "CORE_REMINDERS_SERVICE", "Found reminder {reminder:?}");
+        reminders
+            .into_iter()
+            .map(|reminder| Ok(reminder.try_into()?))
+            .collect()
+    })
+    .context("find reminders by note")
+}
+
+/// Counts the reminders that are due or overdue at `now` and are neither completed nor acknowledged
+pub fn count_due(now: SimpleDate) -> DatabaseResult<i64> {
+    database::within_database(|conn| {
+        let count = reminders_dsl::reminders
+            .filter(reminders_dsl::remind_at.le(now.to_string().into_sql::<SqlDateType>()))
+            .filter(reminders_dsl::completed_at.is_null())
+            .filter(reminders_dsl::acknowledged_at.is_null())
+            .count()
+            .get_result(conn)?;
+
+        debug!(target: "CORE_REMINDERS_SERVICE", "Due reminders counted: {count}");
 
-        Ok(reminder.into())
+        Ok(count)
     })
+    .context("count due reminders")
 }
 
-pub fn create_reminder(reminder: CreateReminderData) -> DatabaseResult<ReminderData> {
+/// Counts the reminders that are past due at `now` and not completed.
+/// Unlike `count_due`, acknowledged reminders are still overdue.
+pub fn overdue_count(now: SimpleDate) -> DatabaseResult<i64> {
     database::within_database(|conn| {
-        debug!(target: "CORE_REMINDERS_SERVICE", "Creating reminder: {reminder:?}");
+        let count = reminders_dsl::reminders
+            .filter(reminders_dsl::remind_at.lt(now.to_string().into_sql::<SqlDateType>()))
+            .filter(reminders_dsl::completed_at.is_null())
+            .count()
+            .get_result(conn)?;
+
+        debug!(target: "CORE_REMINDERS_SERVICE", "Overdue reminders counted: {count}");
+
+        Ok(count)
+    })
+    .context("count overdue reminders")
+}
+
+/// Counts the reminders that became due after `since` and up to `now`.
+/// Ex: the reminders that fired while the client was closed.
+pub fn count_fired_between(since: SimpleDate, now: SimpleDate) -> DatabaseResult<i64> {
+    database::within_database(|conn| {
+        let count = reminders_dsl::reminders
+            .filter(reminders_dsl::remind_at.gt(since.to_string().into_sql::<SqlDateType>()))
+            .filter(reminders_dsl::remind_at.le(now.to_string().into_sql::<SqlDateType>()))
+            .count()
+            .get_result(conn)?;
+
+        debug!(target: "CORE_REMINDERS_SERVICE", "Reminders fired since {since}: {count}");
+
+        Ok(count)
+    })
+    .context("count fired reminders")
+}
+
+// date portion of the stored rfc3339 remind_at. Ex: 2024-04-05
+const REMIND_AT_DAY_SQL: &str = "substr(remind_at, 1, 10)";
+
+/// Counts the reminders of each day between `start` and `end`, both inclusive.
+/// Only days with reminders are returned, ordered by day. See `fill_empty_days`.
+pub fn counts_by_day(start: SimpleDate, end: SimpleDate) -> DatabaseResult<Vec<(SimpleDate, i64)>> {
+    database::within_database(|conn| {
+        let counts = reminders_dsl::reminders
+            .filter(reminders_dsl::remind_at.between(
+                start.to_string().into_sql::<SqlDateType>(),
+                end.to_string().into_sql::<SqlDateType>(),
+            ))
+            .group_by(sql::<Text>(REMIND_AT_DAY_SQL))
+            .select((sql::<Text>(REMIND_AT_DAY_SQL), count_star()))
+            .order(sql::<Text>(REMIND_AT_DAY_SQL).asc())
+            .load::<(String, i64)>(conn)?;
+
+        let counts = counts
+            .into_iter()
+            .map(|(day, count)| {
+                SimpleDate::parse_from_str(format!("{day} 00:00:00"), "%Y-%m-%d %H:%M:%S")
+                    .map(|day| (day, count))
+            })
+            .collect::<Result<Vec<(SimpleDate, i64)>, _>>()?;
+
+        Ok(counts)
+    })
+    .context("count reminders by day")
+}
+
+/// Lists every day from `start` to `end` (inclusive) with its count, days without reminders get 0
+pub fn fill_empty_days(
+    counts: &[(SimpleDate, i64)],
+    start: SimpleDate,
+    end: SimpleDate,
+) -> Vec<(SimpleDate, i64)> {
+    // days are iterated up to the end exclusive, so we stop at the start of the next day
+    let end = end
+        .start_of_day()
+        .and_then(|end| end.add_duration(Duration::Day(1)))
+        .unwrap_or(end);
+
+    SimpleDate::iter_days(start, end)
+        .map(|day| {
+            let count = counts
+                .iter()
+                .find(|(counted_day, _)| counted_day.is_same_day(&day))
+                .map_or(0, |(_, count)| *count);
+
+            (day, count)
+        })
+        .collect()
+}
+
+/// Acknowledges every reminder that is due at `now` and is not completed, ex: to clear notifications.
+/// Returns the amount of reminders acknowledged.
+pub fn acknowledge_all_due(now: SimpleDate) -> DatabaseResult<usize> {
+    database::within_database(|conn| {
+        let acknowledged = diesel::update(
+            reminders_dsl::reminders
+                .filter(reminders_dsl::remind_at.le(now.to_string().into_sql::<SqlDateType>()))
+                .filter(reminders_dsl::completed_at.is_null())
+                .filter(reminders_dsl::acknowledged_at.is_null()),
+        )
+        .set(reminders_dsl::acknowledged_at.eq(now.to_string()))
+        .execute(conn)?;
+
+        debug!(target: "CORE_REMINDERS_SERVICE", "Acknowledged {acknowledged} due reminders.");
+
+        invalidate_cache();
+
+        Ok(acknowledged)
+    })
+    .context("acknowledge due reminders")
+}
+
+pub fn fetch_reminder(id: ReminderId) -> DatabaseResult<ReminderData> {
+    database::within_database(|conn| {
+        debug!(target: "CORE_REMINDERS_SERVICE", "Fetching reminder with id {id}");
+
+        let reminder = reminders_dsl::reminders
+            .find(id.0)
+            .select(Reminder::as_select())
+            .first(conn)?;
+
+        debug!(target: "CORE_REMINDERS_SERVICE", "Found reminder {reminder:?}");
+
+        Ok(reminder.try_into()?)
+    })
+    .context("fetch reminder")
+}
+
+pub fn create_reminder(mut reminder: CreateReminderData) -> DatabaseResult<ReminderData> {
+    validate_note(&reminder.note)?;
+    reminder.remind_at = apply_precision(reminder.remind_at);
+
+    database::within_database(|conn| {
+        debug!(target: "CORE_REMINDERS_SERVICE", "Creating reminder: {reminder:?}");
+
+        let create_reminder: CreateReminder = reminder.into();
+        let created_reminder = diesel::insert_into(reminders::table)
+            .values(&create_reminder)
+            .returning(Reminder::as_returning())
+            .get_result(conn)?;
+
+        debug!(target: "CORE_REMINDERS_SERVICE", "Reminder created. ID is: : {}", created_reminder.id);
+
+        invalidate_cache();
+
+        Ok(created_reminder.try_into()?)
+    })
+    .context("create reminder")
+}
+
+/// Creates all of the reminders, or none of them if any fails
+pub fn create_reminders(reminders: Vec<CreateReminderData>) -> DatabaseResult<Vec<ReminderData>> {
+    for reminder in reminders.iter() {
+        validate_note(&reminder.note)?;
+    }
+
+    database::within_database(|conn| {
+        debug!(target: "CORE_REMINDERS_SERVICE", "Creating {} reminders", reminders.len());
+
+        let created_reminders = conn.transaction::<_, DatabaseError, _>(|conn| {
+            let mut created_reminders = Vec::with_capacity(reminders.len());
+
+            for mut reminder in reminders {
+                reminder.remind_at = apply_precision(reminder.remind_at);
+
+                let create_reminder: CreateReminder = reminder.into();
+                let created_reminder = diesel::insert_into(reminders::table)
+                    .values(&create_reminder)
+                    .returning(Reminder::as_returning())
+                    .get_result(conn)?;
+
+                created_reminders.push(created_reminder.try_into()?);
+            }
+
+            Ok(created_reminders)
+        })?;
+
+        invalidate_cache();
+
+        Ok(created_reminders)
+    })
+    .context("create reminders")
+}
+
+pub fn update_reminder(mut reminder: UpdateReminderData) -> DatabaseResult<ReminderData> {
+    if let Some(note) = reminder.note.as_ref() {
+        validate_note(note)?;
+    }
+
+    reminder.remind_at = reminder.remind_at.map(apply_precision);
+
+    database::within_database(|conn| {
+        debug!(target: "CORE_REMINDERS_SERVICE", "Updating reminder: {reminder:?}");
+
+        let id = reminder.id;
+        let update_reminder: UpdateReminder = reminder.into();
+        let old_reminder: ReminderData = reminders_dsl::reminders
+            .find(id.0)
+            .select(Reminder::as_select())
+            .first(conn)?
+            .try_into()?;
+        let updated_reminder: ReminderData = diesel::update(reminders_dsl::reminders.find(id.0))
+            .set(&update_reminder)
+            .returning(Reminder::as_returning())
+            .get_result(conn)?
+            .try_into()?;
+
+        debug!(target: "CORE_REMINDERS_SERVICE", "Reminder {id} updated! Changes: {:?}", old_reminder.diff(&updated_reminder));
+
+        invalidate_cache();
+
+        Ok(updated_reminder)
+    })
+    .context("update reminder")
+}
+
+/// Pins the reminder, pinned reminders are listed before the rest regardless of their time.
+pub fn pin_reminder(id: ReminderId, pinned: bool) -> DatabaseResult<ReminderData> {
+    database::within_database(|conn| {
+        debug!(target: "CORE_REMINDERS_SERVICE", "Setting pinned of reminder {id} to {pinned}");
+
+        let reminder = diesel::update(reminders_dsl::reminders.find(id.0))
+            .set(reminders_dsl::pinned.eq(pinned))
+            .returning(Reminder::as_returning())
+            .get_result(conn)?;
+
+        invalidate_cache();
+
+        Ok(reminder.try_into()?)
+    })
+    .context("pin reminder")
+}
+
+/// Labels the reminder with a color, `None` clears it.
+pub fn set_color(id: ReminderId, color: Option<ReminderColor>) -> DatabaseResult<ReminderData> {
+    database::within_database(|conn| {
+        debug!(target: "CORE_REMINDERS_SERVICE", "Setting color of reminder {id} to {color:?}");
+
+        let reminder = diesel::update(reminders_dsl::reminders.find(id.0))
+            .set(reminders_dsl::color.eq(color.map(|color| color.to_string())))
+            .returning(Reminder::as_returning())
+            .get_result(conn)?;
+
+        invalidate_cache();
+
+        Ok(reminder.try_into()?)
+    })
+    .context("set reminder color")
+}
+
+/// Moves the reminder to `until` and clears its acknowledgement, so it becomes due again then.
+pub fn snooze_reminder(id: ReminderId, until: SimpleDate) -> DatabaseResult<ReminderData> {
+    let until = apply_precision(until);
+
+    database::within_database(|conn| {
+        debug!(target: "CORE_REMINDERS_SERVICE", "Snoozing reminder {id} until {until}");
+
+        let reminder = diesel::update(reminders_dsl::reminders.find(id.0))
+            .set((
+                reminders_dsl::remind_at.eq(until.to_string()),
+                reminders_dsl::acknowledged_at.eq(None::<String>),
+            ))
+            .returning(Reminder::as_returning())
+            .get_result(conn)?;
+
+        invalidate_cache();
+
+        Ok(reminder.try_into()?)
+    })
+    .context("snooze reminder")
+}
+
+/// Moves the reminder to the first day after it that is not a weekend day, at `time_of_day`.
+/// Ex: a reminder on friday is deferred to monday and one on tuesday to wednesday.
+pub fn defer_to_next_workday(
+    id: ReminderId,
+    time_of_day: (u32, u32),
+) -> DatabaseResult<ReminderData> {
+    let (hour, minute) = time_of_day;
+    let mut remind_at = fetch_reminder(id)?.remind_at;
+    remind_at.set_hms(hour, minute, 0)?;
+
+    let mut next_day = remind_at;
+
+    for _ in 0..7 {
+        next_day = next_day.add_duration(Duration::Day(1))?;
+
+        if !is_weekend_day(next_day.weekday()) {
+            return snooze_reminder(id, next_day);
+        }
+    }
+
+    // every day is a weekend day, so there is no workday to find
+    snooze_reminder(id, remind_at.add_duration(Duration::Day(1))?)
+}
+
+/// Marks the reminder as completed now, see `time_to_complete` for how long it took.
+pub fn complete_reminder(id: ReminderId) -> DatabaseResult<ReminderData> {
+    database::within_database(|conn| {
+        debug!(target: "CORE_REMINDERS_SERVICE", "Completing reminder {id}");
+
+        let reminder = diesel::update(reminders_dsl::reminders.find(id.0))
+            .set(reminders_dsl::completed_at.eq(SimpleDate::local().to_string()))
+            .returning(Reminder::as_returning())
+            .get_result(conn)?;
+
+        invalidate_cache();
+
+        Ok(reminder.try_into()?)
+    })
+    .context("complete reminder")
+}
+
+/// Marks the reminder as lasting the whole day, it is then due at the start of its day.
+pub fn set_all_day(id: ReminderId, all_day: bool) -> DatabaseResult<ReminderData> {
+    database::within_database(|conn| {
+        debug!(target: "CORE_REMINDERS_SERVICE", "Setting all day of reminder {id} to {all_day}");
+
+        let mut reminder: ReminderData = reminders_dsl::reminders
+            .find(id.0)
+            .select(Reminder::as_select())
+            .first(conn)?
+            .try_into()?;
+        reminder.all_day = all_day;
+
+        let reminder = diesel::update(reminders_dsl::reminders.find(id.0))
+            .set((
+                reminders_dsl::all_day.eq(all_day),
+                reminders_dsl::remind_at.eq(reminder.due_at().to_string()),
+            ))
+            .returning(Reminder::as_returning())
+            .get_result(conn)?;
+
+        invalidate_cache();
+
+        Ok(reminder.try_into()?)
+    })
+    .context("set all day")
+}
+
+/// Ends the recurrence of the reminder after a date, a number of times, or whichever comes first.
+/// `None` for both lets it recur forever.
+pub fn set_recurrence_end(
+    id: ReminderId,
+    until: Option<SimpleDate>,
+    count: Option<i64>,
+) -> DatabaseResult<ReminderData> {
+    database::within_database(|conn| {
+        debug!(target: "CORE_REMINDERS_SERVICE", "Ending recurrence of reminder {id} until {until:?} or after {count:?} times");
+
+        let reminder = diesel::update(reminders_dsl::reminders.find(id.0))
+            .set((
+                reminders_dsl::recurrence_until.eq(until.map(|date| date.to_string())),
+                reminders_dsl::recurrence_count.eq(count),
+            ))
+            .returning(Reminder::as_returning())
+            .get_result(conn)?;
+
+        invalidate_cache();
+
+        Ok(reminder.try_into()?)
+    })
+    .context("set recurrence end")
+}
+
+/// Sets the category of every reminder in `ids` at once, `None` clears it.
+/// Returns the amount of reminders changed.
+pub fn assign_category(ids: &[ReminderId], category: Option<&str>) -> DatabaseResult<usize> {
+    database::within_database(|conn| {
+        debug!(target: "CORE_REMINDERS_SERVICE", "Setting category of reminders {ids:?} to {category:?}");
+
+        let assigned = diesel::update(
+            reminders_dsl::reminders.filter(reminders_dsl::id.eq_any(raw_ids(ids))),
+        )
+        .set(reminders_dsl::category.eq(category))
+        .execute(conn)?;
+
+        invalidate_cache();
+
+        Ok(assigned)
+    })
+    .context("assign category")
+}
+
+/// Pushes every reminder in `ids` back by `by` at once, clearing their acknowledgement
+/// like `snooze_reminder`. Either all of them are snoozed or none.
+/// Returns the amount of reminders snoozed.
+pub fn snooze_many(ids: &[ReminderId], by: Duration) -> DatabaseResult<usize> {
+    database::within_database(|conn| {
+        debug!(target: "CORE_REMINDERS_SERVICE", "Snoozing reminders {ids:?} by {by:?}");
+
+        let snoozed = conn.transaction::<_, DatabaseError, _>(|conn| {
+            let reminders: Vec<ReminderData> = reminders_dsl::reminders
+                .filter(reminders_dsl::id.eq_any(raw_ids(ids)))
+                .select(Reminder::as_select())
+                .load(conn)?
+                .into_iter()
+                .map(ReminderData::try_from)
+                .collect::<Result<_, _>>()?;
+
+            for reminder in reminders.iter() {
+                let remind_at = apply_precision(reminder.remind_at.add_duration(&by)?);
+
+                diesel::update(reminders_dsl::reminders.find(reminder.id.0))
+                    .set((
+                        reminders_dsl::remind_at.eq(remind_at.to_string()),
+                        reminders_dsl::acknowledged_at.eq(None::<String>),
+                    ))
+                    .execute(conn)?;
+            }
+
+            Ok(reminders.len())
+        })?;
+
+        debug!(target: "CORE_REMINDERS_SERVICE", "Snoozed {snoozed} reminders.");
+
+        invalidate_cache();
+
+        Ok(snoozed)
+    })
+    .context("snooze reminders")
+}
+
+/// Inserts the reminder or, if a reminder with the same id exists, overwrites it.
+pub fn upsert(mut reminder: ReminderData) -> DatabaseResult<ReminderData> {
+    validate_note(&reminder.note)?;
+    reminder.remind_at = apply_precision(reminder.remind_at);
+
+    database::within_database(|conn| {
+        debug!(target: "CORE_REMINDERS_SERVICE", "Upserting reminder: {reminder:?}");
+
+        let reminder: Reminder = reminder.into();
+        let upserted_reminder = diesel::insert_into(reminders::table)
+            .values(&reminder)
+            .on_conflict(reminders_dsl::id)
+            .do_update()
+            .set((
+                reminders_dsl::note.eq(excluded(reminders_dsl::note)),
+                reminders_dsl::body.eq(excluded(reminders_dsl::body)),
+                reminders_dsl::remind_at.eq(excluded(reminders_dsl::remind_at)),
+                reminders_dsl::created_at.eq(excluded(reminders_dsl::created_at)),
+                reminders_dsl::attachment.eq(excluded(reminders_dsl::attachment)),
+                reminders_dsl::recurrence.eq(excluded(reminders_dsl::recurrence)),
+                reminders_dsl::completed_at.eq(excluded(reminders_dsl::completed_at)),
+                reminders_dsl::acknowledged_at.eq(excluded(reminders_dsl::acknowledged_at)),
+                reminders_dsl::miss_policy.eq(excluded(reminders_dsl::miss_policy)),
+                reminders_dsl::pinned.eq(excluded(reminders_dsl::pinned)),
+                reminders_dsl::category.eq(excluded(reminders_dsl::category)),
+                reminders_dsl::all_day.eq(excluded(reminders_dsl::all_day)),
+                reminders_dsl::recurrence_until.eq(excluded(reminders_dsl::recurrence_until)),
+                reminders_dsl::recurrence_count.eq(excluded(reminders_dsl::recurrence_count)),
+                reminders_dsl::color.eq(excluded(reminders_dsl::color)),
+            ))
+            .returning(Reminder::as_returning())
+            .get_result(conn)?;
+
+        debug!(target: "CORE_REMINDERS_SERVICE", "Reminder {} upserted!", upserted_reminder.id);
+
+        invalidate_cache();
+
+        Ok(upserted_reminder.try_into()?)
+    })
+    .context("upsert reminder")
+}
+
+/// Inserts the reminder keeping its id, so a restored database matches the backup.
+/// Fails with `ReminderIdTaken` if a reminder with the same id already exists.
+pub fn restore_reminder(reminder: ReminderData) -> DatabaseResult<ReminderData> {
+    validate_note(&reminder.note)?;
+
+    database::within_database(|conn| {
+        debug!(target: "CORE_REMINDERS_SERVICE", "Restoring reminder: {reminder:?}");
+
+        let id = reminder.id;
+        let reminder: Reminder = reminder.into();
+        let restored_reminder = diesel::insert_into(reminders::table)
+            .values(&reminder)
+            .returning(Reminder::as_returning())
+            .get_result(conn)
+            .map_err(|e| match e {
+                DieselResultError::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+                    DatabaseError::ReminderIdTaken(id)
+                }
+                e => e.into(),
+            })?;
+
+        debug!(target: "CORE_REMINDERS_SERVICE", "Reminder {id} restored!");
+
+        invalidate_cache();
+
+        Ok(restored_reminder.try_into()?)
+    })
+    .context("restore reminder")
+}
+
+/// Moves every reminder that is already due to the `target` date.
+/// When `preserve_time` is set only the date is changed and each reminder keeps its time of day.
+/// Returns the amount of reminders postponed.
+pub fn postpone_overdue_to(target: SimpleDate, preserve_time: bool) -> DatabaseResult<usize> {
+    database::within_database(|conn| {
+        debug!(target: "CORE_REMINDERS_SERVICE", "Postponing overdue reminders to {target}");
+
+        let now = SimpleDate::local();
+        let postponed = conn.transaction::<_, DatabaseError, _>(|conn| {
+            let overdue_reminders: Vec<ReminderData> = reminders_dsl::reminders
+                .filter(reminders_dsl::remind_at.lt(now.to_string().into_sql::<SqlDateType>()))
+                .select(Reminder::as_select())
+                .load(conn)?
+                .into_iter()
+                .map(ReminderData::try_from)
+                .collect::<Result<_, _>>()?;
+
+            for reminder in overdue_reminders.iter() {
+                let mut remind_at = target;
+
+                if preserve_time {
+                    remind_at.set_hms(
+                        reminder.remind_at.hour(),
+                        reminder.remind_at.minute(),
+                        reminder.remind_at.second(),
+                    )?;
+                }
+
+                diesel::update(reminders_dsl::reminders.find(reminder.id.0))
+                    .set(reminders_dsl::remind_at.eq(remind_at.to_string()))
+                    .execute(conn)?;
+            }
+
+            Ok(overdue_reminders.len())
+        })?;
+
+        debug!(target: "CORE_REMINDERS_SERVICE", "Postponed {postponed} reminders.");
+
+        invalidate_cache();
+
+        Ok(postponed)
+    })
+    .context("postpone overdue reminders")
+}
+
+/// Advances a recurring reminder by whole recurrence steps until it is after `now`.
+/// Missed occurrences are skipped instead of being fired one after another.
+/// Once the recurrence end is reached, the reminder stays on its last occurrence and is completed.
+/// Reminders without a recurrence, or already completed, are returned unchanged.
+pub fn catch_up_recurrence(id: ReminderId, now: SimpleDate) -> DatabaseResult<ReminderData> {
+    let reminder = fetch_reminder(id)?;
+    let Some(recurrence) = reminder.recurrence else {
+        return Ok(reminder);
+    };
+
+    if reminder.is_completed() {
+        return Ok(reminder);
+    }
+
+    let mut remind_at = reminder.remind_at;
+    let mut recurrence_count = reminder.recurrence_count;
+    let mut has_ended = false;
+
+    // subtracting dates saturates to zero, so this loops until remind_at is after now
+    while (remind_at - now).is_zero() {
+        let next_remind_at = recurrence.next_occurrence(remind_at)?;
+        let is_last = recurrence_count.is_some_and(|count| count <= 1)
+            || reminder
+                .recurrence_until
+                .is_some_and(|until| !(next_remind_at - until).is_zero());
+
+        if is_last {
+            has_ended = true;
+
+            break;
+        }
+
+        remind_at = next_remind_at;
+        recurrence_count = recurrence_count.map(|count| count - 1);
+    }
+
+    if remind_at == reminder.remind_at && !has_ended {
+        return Ok(reminder);
+    }
+
+    debug!(target: "CORE_REMINDERS_SERVICE", "Catching up reminder {id} to {remind_at}, ended: {has_ended}");
+
+    database::within_database(|conn| {
+        let reminder = diesel::update(reminders_dsl::reminders.find(id.0))
+            .set((
+                reminders_dsl::remind_at.eq(remind_at.to_string()),
+                reminders_dsl::recurrence_count.eq(recurrence_count),
+                reminders_dsl::completed_at.eq(has_ended.then(|| now.to_string())),
+            ))
+            .returning(Reminder::as_returning())
+            .get_result(conn)?;
+
+        invalidate_cache();
+
+        Ok(reminder.try_into()?)
+    })
+    .context("catch up recurrence")
+}
+
+/// Moves every recurring reminder that is due at or before `now` to its next occurrence,
+/// see `catch_up_recurrence`. Returns the ids of the reminders that were caught up.
+pub fn catch_up_due_recurrences(now: SimpleDate) -> DatabaseResult<Vec<ReminderId>> {
+    let ids: Vec<ReminderId> = database::within_database(|conn| {
+        let ids = reminders_dsl::reminders
+            .filter(reminders_dsl::remind_at.le(now.to_string().into_sql::<SqlDateType>()))
+            .filter(reminders_dsl::completed_at.is_null())
+            .filter(reminders_dsl::recurrence.is_not_null())
+            .select(reminders_dsl::id)
+            .load::<i64>(conn)?;
+
+        Ok(ids.into_iter().map(ReminderId).collect())
+    })
+    .context("fetch due recurring reminders")?;
+
+    debug!(target: "CORE_REMINDERS_SERVICE", "Catching up {} due recurring reminders.", ids.len());
+
+    for id in ids.iter() {
+        catch_up_recurrence(*id, now)?;
+    }
+
+    Ok(ids)
+}
+
+/// Deletes the reminders without a recurrence that were due at or before `date`.
+/// Recurring reminders are kept, they move on to their next occurrence instead.
+/// Returns the amount of reminders deleted.
+pub fn delete_past_one_off_reminders(date: SimpleDate) -> DatabaseResult<usize> {
+    database::within_database(|conn| {
+        debug!(target: "CORE_REMINDERS_SERVICE", "Deleting one-off reminders due before {date}");
+
+        let ids = conn.transaction::<_, DatabaseError, _>(|conn| {
+            let ids = diesel::delete(
+                reminders_dsl::reminders
+                    .filter(reminders_dsl::remind_at.le(date.to_string().into_sql::<SqlDateType>()))
+                    .filter(reminders_dsl::recurrence.is_null()),
+            )
+            .returning(reminders_dsl::id)
+            .get_results::<i64>(conn)?;
+            record_tombstones(conn, &ids)?;
+
+            Ok(ids)
+        })?;
+
+        debug!(target: "CORE_REMINDERS_SERVICE", "Deleted {} one-off reminders.", ids.len());
+
+        invalidate_cache();
+
+        Ok(ids.len())
+    })
+    .context("delete past one-off reminders")
+}
+
+/// Applies the miss policy of every non recurring reminder that was due before `now`
+/// and is not completed. Recurring reminders are handled by `catch_up_recurrence`.
+/// Returns the amount of reminders that were snoozed or dropped.
+pub fn reconcile_missed(now: SimpleDate) -> DatabaseResult<usize> {
+    database::within_database(|conn| {
+        debug!(target: "CORE_REMINDERS_SERVICE", "Reconciling reminders missed before {now}");
+
+        let reconciled = conn.transaction::<_, DatabaseError, _>(|conn| {
+            let missed_reminders: Vec<ReminderData> = reminders_dsl::reminders
+                .filter(reminders_dsl::remind_at.lt(now.to_string().into_sql::<SqlDateType>()))
+                .filter(reminders_dsl::completed_at.is_null())
+                .filter(reminders_dsl::recurrence.is_null())
+                .filter(reminders_dsl::miss_policy.ne(MissPolicy::Keep.to_string()))
+                .select(Reminder::as_select())
+                .load(conn)?
+                .into_iter()
+                .map(ReminderData::try_from)
+                .collect::<Result<_, _>>()?;
+
+            for reminder in missed_reminders.iter() {
+                match reminder.miss_policy {
+                    MissPolicy::Keep => {}
+                    MissPolicy::SnoozeToNextDay => {
+                        let mut remind_at = now.add_duration(Duration::Day(1))?;
+                        remind_at.set_hms(
+                            reminder.remind_at.hour(),
+                            reminder.remind_at.minute(),
+                            reminder.remind_at.second(),
+                        )?;
+
+                        diesel::update(reminders_dsl::reminders.find(reminder.id.0))
+                            .set(reminders_dsl::remind_at.eq(remind_at.to_string()))
+                            .execute(conn)?;
+                    }
+                    MissPolicy::Drop => {
+                        diesel::delete(reminders_dsl::reminders.find(reminder.id.0))
+                            .execute(conn)?;
+                        record_tombstones(conn, &[reminder.id.0])?;
+                    }
+                }
+            }
+
+            Ok(missed_reminders.len())
+        })?;
+
+        debug!(target: "CORE_REMINDERS_SERVICE", "Reconciled {reconciled} missed reminders.");
+
+        invalidate_cache();
+
+        Ok(reconciled)
+    })
+    .context("reconcile missed reminders")
+}
+
+/// Counts the reminders `delete_reminders` would remove with the same option, without deleting them
+pub fn preview_delete(option: ReminderQueryOptions) -> DatabaseResult<usize> {
+    match option {
+        // ordering and paging do not select any reminders, so nothing would be deleted
+        ReminderQueryOptions::OrderBy { .. }
+        | ReminderQueryOptions::Limit { .. }
+        | ReminderQueryOptions::Offset { .. }
+        | ReminderQueryOptions::ClosestTo { .. } => Ok(0),
+        option => Ok(fetch_reminders(Some(vec![option]))?.len()),
+    }
+}
+
+pub fn delete_reminders(option: ReminderQueryOptions) -> DatabaseResult<()> {
+    database::within_database(|conn| {
+        debug!(target: "CORE_REMINDERS_SERVICE", "Deleting reminders: {option:?}");
+
+        conn.transaction::<_, DatabaseError, _>(|conn| {
+            // ordering and paging do not select any reminders, so there is nothing to delete
+            if let Some(filter) = reminder_filter(&option)? {
+                let statement = diesel::delete(reminders_dsl::reminders.filter(filter));
+
+                log_sql(&statement);
+
+                let ids = statement
+                    .returning(reminders_dsl::id)
+                    .get_results::<i64>(conn)?;
+                record_tombstones(conn, &ids)?;
+            }
+
+            Ok(())
+        })?;
+
+        debug!(target: "CORE_REMINDERS_SERVICE", "Deleted Reminders.");
+
+        invalidate_cache();
+
+        Ok(())
+    })
+    .context("delete reminders")
+}
+
+pub fn delete_reminder(id: ReminderId) -> DatabaseResult<()> {
+    database::within_database(|conn| {
+        debug!(target: "CORE_REMINDERS_SERVICE", "Deleting reminder: {id}");
+
+        conn.transaction::<_, DatabaseError, _>(|conn| {
+            let deleted = diesel::delete(reminders_dsl::reminders.find(id.0)).execute(conn)?;
+
+            if deleted > 0 {
+                record_tombstones(conn, &[id.0])?;
+            }
+
+            Ok(())
+        })?;
+
+        debug!(target: "CORE_REMINDERS_SERVICE", "Deleted Reminder: {id}");
+
+        invalidate_cache();
+
+        Ok(())
+    })
+    .context("delete reminder")
+}
+
+/// Removes the reminders completed before `date`, to keep the database from growing forever.
+/// Returns the amount of reminders purged.
+pub fn purge_completed_before(date: SimpleDate) -> DatabaseResult<usize> {
+    database::within_database(|conn| {
+        debug!(target: "CORE_REMINDERS_SERVICE", "Purging reminders completed before {date}");
+
+        let ids = conn.transaction::<_, DatabaseError, _>(|conn| {
+            let ids = diesel::delete(
+                reminders_dsl::reminders.filter(
+                    reminders_dsl::completed_at
+                        .lt(date.to_string().into_sql::<SqlDateType>().nullable()),
+                ),
+            )
+            .returning(reminders_dsl::id)
+            .get_results::<i64>(conn)?;
+            record_tombstones(conn, &ids)?;
+
+            Ok(ids)
+        })?;
+
+        debug!(target: "CORE_REMINDERS_SERVICE", "Purged {} completed reminders.", ids.len());
+
+        invalidate_cache();
+
+        Ok(ids.len())
+    })
+    .context("purge completed reminders")
+}
+
+/// Purges the reminders completed more than `retention_days` before `now`.
+/// Returns the amount of reminders purged.
+pub fn purge_completed_past_retention(
+    now: SimpleDate,
+    retention_days: u32,
+) -> DatabaseResult<usize> {
+    purge_completed_before(now.sub_duration(Duration::Day(retention_days))?)
+}
+
+/// Attaches a file path or URI to a reminder.
+/// Attachments are removed together with their reminder.
+pub fn add_attachment(
+    reminder_id: ReminderId,
+    path: impl Into<String>,
+) -> DatabaseResult<AttachmentData> {
+    database::within_database(|conn| {
+        let create_attachment = CreateAttachment {
+            reminder_id: reminder_id.0,
+            path: path.into(),
+            created_at: SimpleDate::local().to_string(),
+        };
+
+        debug!(target: "CORE_REMINDERS_SERVICE", "Adding attachment {} to reminder {reminder_id}", create_attachment.path);
+
+        let created_attachment = diesel::insert_into(attachments::table)
+            .values(&create_attachment)
+            .returning(Attachment::as_returning())
+            .get_result(conn)?;
+
+        debug!(target: "CORE_REMINDERS_SERVICE", "Attachment created. ID is: {}", created_attachment.id);
+
+        Ok(created_attachment.try_into()?)
+    })
+    .context("add attachment")
+}
+
+pub fn list_attachments(reminder_id: ReminderId) -> DatabaseResult<Vec<AttachmentData>> {
+    database::within_database(|conn| {
+        debug!(target: "CORE_REMINDERS_SERVICE", "Fetching attachments of reminder {reminder_id}");
+
+        let attachments = attachments_dsl::attachments
+            .filter(attachments_dsl::reminder_id.eq(reminder_id.0))
+            .order(attachments_dsl::id.asc())
+            .select(Attachment::as_select())
+            .load(conn)?
+            .into_iter()
+            .map(AttachmentData::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(attachments)
+    })
+    .context("list attachments")
+}
+
+#[cfg(test)]
+mod tests {
+    use self::database::{init_database, within_database};
+    use ctor::ctor;
+    use diesel::sql_query;
+    use diesel_migrations::{embed_migrations, EmbeddedMigrations};
+    use serial_test::serial;
+    pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("./migrations");
+
+    use super::*;
+
+    macro_rules! create_a_reminder {
+        () => {{
+            let date =
+                SimpleDate::parse_from_str("2024-04-05 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+            let reminder_data = CreateReminderData {
+                remind_at: date,
+                note: "Testing".to_owned(),
+                body: None,
+                attachment: None,
+                recurrence: None,
+                miss_policy: MissPolicy::Keep,
+            };
+
+            create_reminder(reminder_data).unwrap()
+        }};
+
+        ($date:expr) => {{
+            let reminder_data = CreateReminderData {
+                remind_at: $date,
+                note: "Testing".to_owned(),
+                body: None,
+                attachment: None,
+                recurrence: None,
+                miss_policy: MissPolicy::Keep,
+            };
+
+            create_reminder(reminder_data).unwrap()
+        }};
+    }
+
+    macro_rules! truncate_table {
+        () => {
+            within_database(|conn| {
+                sql_query("DELETE from reminders where 1=1")
+                    .execute(conn)
+                    .unwrap();
+
+                Ok(())
+            })
+            .unwrap();
+        };
+    }
+
+    #[test]
+    #[ctor]
+    fn init() {
+        init_database(":memory:", vec![MIGRATIONS]).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn it_can_fetch_a_reminder() {
+        let reminder = create_a_reminder!();
+        let fetched_reminder = fetch_reminder(reminder.id).unwrap();
+
+        assert_eq!(reminder.id, fetched_reminder.id);
+        assert_eq!(reminder.note, fetched_reminder.note);
+        assert!(reminder.remind_at.eq_to_second(&fetched_reminder.remind_at));
+
+        // an id kept as an integer, ex: in a notification, finds the same reminder
+        let raw_id = i64::from(reminder.id);
+
+        assert_eq!(
+            fetched_reminder,
+            fetch_reminder(ReminderId::from(raw_id)).unwrap()
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn it_can_fetch_reminders() {
+        truncate_table!();
+
+        let reminders = [
+            create_a_reminder!(),
+            create_a_reminder!(),
+            create_a_reminder!(),
+        ];
+        let fetched_reminders = fetch_reminders(None).unwrap();
+
+        assert_eq!(reminders.len(), fetched_reminders.len());
+
+        for i in 0..fetched_reminders.len() {
+            let reminder = fetched_reminders.get(i).unwrap();
+            let expected_reminder = reminders.get(i).unwrap();
+
+            assert_eq!(expected_reminder, reminder);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn it_fetches_reminders_by_ascending_id_without_options() {
+        truncate_table!();
+
+        // created latest first, so neither the time nor the note matches the id order
+        for (note, date) in [
+            ("c", "2024-04-07 08:00:00"),
+            ("a", "2024-04-06 08:00:00"),
+            ("b", "2024-04-05 08:00:00"),
+        ] {
+            create_reminder(CreateReminderData {
+                remind_at: SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap(),
+                note: note.to_owned(),
+                body: None,
+                attachment: None,
+                recurrence: None,
+                miss_policy: MissPolicy::Keep,
+            })
+            .unwrap();
+        }
+
+        let ids = fetch_reminders(None)
+            .unwrap()
+            .iter()
+            .map(|reminder| reminder.id.0)
+            .collect::<Vec<i64>>();
+
+        assert_eq!(
+            (ReminderOrderColumn::Id, OrderDirection::Asc),
+            get_default_order()
+        );
+        assert_eq!(3, ids.len());
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    #[serial]
+    fn it_can_fetch_reminders_in_between() {
+        truncate_table!();
+
+        let d1 = SimpleDate::parse_from_str("2024-03-11 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let d2 = SimpleDate::parse_from_str("2024-03-12 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let start_date =
+            SimpleDate::parse_from_str("2024-03-01 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let end_date =
+            SimpleDate::parse_from_str("2024-04-01 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+
+        let reminders = vec![
+            create_a_reminder!(d1),
+            create_a_reminder!(d2),
+            create_a_reminder!(),
+            create_a_reminder!(),
+        ];
+        let fetched_reminders =
+            fetch_reminders(Some(vec![ReminderQueryOptions::RemindAtBetween {
+                end_date,
+                start_date,
+            }]))
+            .unwrap();
+
+        assert_eq!(2, fetched_reminders.len());
+
+        assert_eq!(
+            reminders.first().unwrap(),
+            fetched_reminders.first().unwrap()
+        );
+        assert_eq!(reminders.get(1).unwrap(), fetched_reminders.get(1).unwrap());
+
+        let start_date =
+            SimpleDate::parse_from_str("2024-04-01 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let end_date =
+            SimpleDate::parse_from_str("2024-05-01 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let fetched_reminders =
+            fetch_reminders(Some(vec![ReminderQueryOptions::RemindAtBetween {
+                end_date,
+                start_date,
+            }]))
+            .unwrap();
+
+        assert_eq!(2, fetched_reminders.len());
+
+        assert_eq!(
+            reminders.get(2).unwrap(),
+            fetched_reminders.first().unwrap()
+        );
+        assert_eq!(reminders.get(3).unwrap(), fetched_reminders.get(1).unwrap());
+    }
+
+    #[test]
+    #[serial]
+    fn it_can_fetch_reminders_around_a_date() {
+        truncate_table!();
+
+        let pivot = SimpleDate::parse_from_str("2024-03-11 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let d1 = SimpleDate::parse_from_str("2024-03-11 09:30:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let d2 = SimpleDate::parse_from_str("2024-03-11 15:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let d3 = SimpleDate::parse_from_str("2024-03-11 08:59:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let d4 = SimpleDate::parse_from_str("2024-03-11 15:01:00", "%Y-%m-%d %H:%M:%S").unwrap();
+
+        let reminders = vec![create_a_reminder!(d1), create_a_reminder!(d2)];
+        create_a_reminder!(d3);
+        create_a_reminder!(d4);
+
+        let fetched_reminders = fetch_reminders(Some(vec![ReminderQueryOptions::RemindAtAround {
+            pivot,
+            window: Duration::Hour(3),
+        }]))
+        .unwrap();
+
+        assert_eq!(reminders, fetched_reminders);
+    }
+
+    #[test]
+    #[serial]
+    fn it_can_export_an_agenda_grouped_by_day() {
+        truncate_table!();
+
+        let create = |note: &str, date: &str| {
+            create_reminder(CreateReminderData {
+                remind_at: SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap(),
+                note: note.to_owned(),
+                body: None,
+                attachment: None,
+                recurrence: None,
+                miss_policy: MissPolicy::Keep,
+            })
+            .unwrap()
+        };
+        create("Call mom", "2024-04-12 18:00:00");
+        create("Buy milk", "2024-04-11 09:00:00");
+        create("Gym", "2024-04-12 07:30:00");
+        create("Dentist", "2024-04-11 14:15:00");
+        create("Out of range", "2024-04-13 10:00:00");
+
+        let start_date =
+            SimpleDate::parse_from_str("2024-04-11 00:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let end_date =
+            SimpleDate::parse_from_str("2024-04-12 23:59:59", "%Y-%m-%d %H:%M:%S").unwrap();
+
+        assert_eq!(
+            "Thursday, 11 April 2024\n  09:00 Buy milk\n  14:15 Dentist\n\n\
+             Friday, 12 April 2024\n  07:30 Gym\n  18:00 Call mom",
+            export_agenda((start_date, end_date)).unwrap()
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn it_orders_unordered_fetches_by_the_default_order() {
+        truncate_table!();
+
+        let d1 = SimpleDate::parse_from_str("2024-04-12 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let d2 = SimpleDate::parse_from_str("2024-04-10 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let late_reminder = create_a_reminder!(d1);
+        let early_reminder = create_a_reminder!(d2);
+        let fetch_ids = |options| {
+            fetch_reminders(options)
+                .unwrap()
+                .into_iter()
+                .map(|reminder| reminder.id)
+                .collect::<Vec<ReminderId>>()
+        };
+
+        assert_eq!(vec![late_reminder.id, early_reminder.id], fetch_ids(None));
+
+        set_default_order(ReminderOrderColumn::RemindAt, OrderDirection::Asc);
+
+        assert_eq!(vec![early_reminder.id, late_reminder.id], fetch_ids(None));
+        assert_eq!(
+            vec![late_reminder.id, early_reminder.id],
+            fetch_ids(Some(vec![ReminderQueryOptions::OrderBy {
+                column: ReminderOrderColumn::Id,
+                direction: OrderDirection::Asc,
+            }]))
+        );
+
+        set_default_order(ReminderOrderColumn::Id, OrderDirection::Asc);
+    }
+
+    #[test]
+    #[serial]
+    fn it_formats_today_agenda_as_plain_text() {
+        truncate_table!();
+
+        let now = SimpleDate::parse_from_str("2024-04-11 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+
+        assert_eq!(
+            "Agenda for Thursday, 11 April 2024\n  Nothing scheduled for today, enjoy your day!",
+            today_agenda_text(now).unwrap()
+        );
+
+        let create = |note: &str, date: &str| {
+            create_reminder(CreateReminderData {
+                remind_at: SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap(),
+                note: note.to_owned(),
+                body: None,
+                attachment: None,
+                recurrence: None,
+                miss_policy: MissPolicy::Keep,
+            })
+            .unwrap()
+        };
+        create("Dentist", "2024-04-11 14:15:00");
+        create("Yesterday", "2024-04-10 09:00:00");
+        create("Buy milk", "2024-04-11 06:30:00");
+        create("Tomorrow", "2024-04-12 09:00:00");
+        let birthday = create("Mom's birthday", "2024-04-11 10:00:00");
+        set_all_day(birthday.id, true).unwrap();
+
+        assert_eq!(
+            "Agenda for Thursday, 11 April 2024\n  all day Mom's birthday\n  06:30 Buy milk\n  14:15 Dentist",
+            today_agenda_text(now).unwrap()
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn it_fetches_the_next_days_including_empty_ones() {
+        truncate_table!();
+
+        let date = |date: &str| SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap();
+        let now = date("2024-04-05 10:00:00");
+        create_a_reminder!(date("2024-04-05 09:00:00"));
+        let today = create_a_reminder!(date("2024-04-05 18:00:00"));
+        let first = create_a_reminder!(date("2024-04-07 08:00:00"));
+        let second = create_a_reminder!(date("2024-04-07 20:00:00"));
+        let last_day = create_a_reminder!(date("2024-04-11 23:30:00"));
+        create_a_reminder!(date("2024-04-12 08:00:00"));
+
+        let days = fetch_next_days_from(now, 7).unwrap();
+
+        assert_eq!(7, days.len());
+        assert!(days[0].0.is_same_day(&now));
+        assert!(days[6].0.is_same_day(&date("2024-04-11 00:00:00")));
+        assert_eq!(
+            vec![
+                vec![today.id],
+                vec![],
+                vec![first.id, second.id],
+                vec![],
+                vec![],
+                vec![],
+                vec![last_day.id],
+            ],
+            days.iter()
+                .map(|(_, reminders)| reminders.iter().map(|reminder| reminder.id).collect())
+                .collect::<Vec<Vec<ReminderId>>>()
+        );
+        assert!(fetch_next_days_from(now, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn it_can_count_due_reminders() {
+        truncate_table!();
+
+        let now = SimpleDate::parse_from_str("2024-04-10 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let d1 = SimpleDate::parse_from_str("2024-04-09 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let d2 = SimpleDate::parse_from_str("2024-04-11 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        create_a_reminder!(d1);
+        create_a_reminder!(now);
+        create_a_reminder!(d2);
+
+        assert_eq!(2, count_due(now).unwrap());
+    }
+
+    #[test]
+    #[serial]
+    fn it_counts_overdue_reminders_that_are_not_completed() {
+        truncate_table!();
+
+        let now = SimpleDate::parse_from_str("2024-04-10 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let d1 = SimpleDate::parse_from_str("2024-04-09 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let d2 = SimpleDate::parse_from_str("2024-04-11 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let completed_reminder = create_a_reminder!(d1);
+        create_a_reminder!(d1);
+        create_a_reminder!(now);
+        create_a_reminder!(d2);
+
+        complete_reminder(completed_reminder.id).unwrap();
+        // acknowledging does not make a reminder any less overdue
+        acknowledge_all_due(now).unwrap();
+
+        assert_eq!(1, overdue_count(now).unwrap());
+        assert_eq!(
+            2,
+            overdue_count(now.add_duration(Duration::Day(1)).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn it_counts_reminders_fired_since_a_date() {
+        truncate_table!();
+
+        let last_opened_at =
+            SimpleDate::parse_from_str("2024-04-08 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let now = SimpleDate::parse_from_str("2024-04-10 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+
+        for date in [
+            "2024-04-07 12:00:00",
+            "2024-04-08 12:00:00",
+            "2024-04-09 08:00:00",
+            "2024-04-09 20:00:00",
+            "2024-04-10 12:00:00",
+            "2024-04-11 12:00:00",
+        ] {
+            create_a_reminder!(SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap());
+        }
+
+        assert_eq!(3, count_fired_between(last_opened_at, now).unwrap());
+        assert_eq!(0, count_fired_between(now, now).unwrap());
+    }
+
+    #[test]
+    #[serial]
+    fn it_counts_reminders_by_day() {
+        truncate_table!();
+
+        let parse = |date| SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap();
+        let start = parse("2024-04-08 00:00:00");
+        let end = parse("2024-04-11 23:59:59");
+
+        for date in [
+            "2024-04-07 12:00:00",
+            "2024-04-08 00:00:00",
+            "2024-04-08 23:59:59",
+            "2024-04-10 08:00:00",
+            "2024-04-10 09:00:00",
+            "2024-04-10 20:00:00",
+            "2024-04-12 12:00:00",
+        ] {
+            create_a_reminder!(parse(date));
+        }
+
+        let counts = counts_by_day(start, end).unwrap();
+
+        assert_eq!(
+            vec![
+                (parse("2024-04-08 00:00:00"), 2),
+                (parse("2024-04-10 00:00:00"), 3)
+            ],
+            counts
+        );
+        assert_eq!(
+            vec![
+                (parse("2024-04-08 00:00:00"), 2),
+                (parse("2024-04-09 00:00:00"), 0),
+                (parse("2024-04-10 00:00:00"), 3),
+                (parse("2024-04-11 00:00:00"), 0)
+            ],
+            fill_empty_days(&counts, start, end)
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn it_acknowledges_only_due_reminders() {
+        truncate_table!();
+
+        let now = SimpleDate::parse_from_str("2024-04-10 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let d1 = SimpleDate::parse_from_str("2024-04-09 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let d2 = SimpleDate::parse_from_str("2024-04-11 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let overdue = create_a_reminder!(d1);
+        let due = create_a_reminder!(now);
+        let not_due = create_a_reminder!(d2);
+
+        assert_eq!(2, acknowledge_all_due(now).unwrap());
+        assert_eq!(0, count_due(now).unwrap());
+        assert_eq!(
+            Some(now),
+            fetch_reminder(overdue.id).unwrap().acknowledged_at
+        );
+        assert_eq!(Some(now), fetch_reminder(due.id).unwrap().acknowledged_at);
+        assert_eq!(None, fetch_reminder(not_due.id).unwrap().acknowledged_at);
+
+        // already acknowledged reminders are not counted again
+        assert_eq!(0, acknowledge_all_due(now).unwrap());
+    }
+
+    #[test]
+    #[serial]
+    fn it_labels_reminders_with_a_color_and_filters_by_it() {
+        truncate_table!();
+
+        let red = create_a_reminder!();
+        let blue = create_a_reminder!();
+        let plain = create_a_reminder!();
+
+        let red = set_color(red.id, Some(ReminderColor::Red)).unwrap();
+        let blue = set_color(blue.id, Some(ReminderColor::Blue)).unwrap();
+
+        assert_eq!(Some(ReminderColor::Red), red.color);
+        assert_eq!(red, fetch_reminder(red.id).unwrap());
+        assert_eq!(None, fetch_reminder(plain.id).unwrap().color);
+        assert_eq!(
+            vec![blue.clone()],
+            fetch_reminders(Some(vec![ReminderQueryOptions::WithColor {
+                color: ReminderColor::Blue
+            }]))
+            .unwrap()
+        );
+
+        assert_eq!(None, set_color(blue.id, None).unwrap().color);
+        assert!(fetch_reminders(Some(vec![ReminderQueryOptions::WithColor {
+            color: ReminderColor::Blue
+        }]))
+        .unwrap()
+        .is_empty());
+        assert!(set_color(ReminderId(plain.id.0 + 100), Some(ReminderColor::Red)).is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn it_counts_the_reminders_matching_the_filters() {
+        truncate_table!();
+
+        let create_with_note = |note: &str| {
+            create_reminder(CreateReminderData {
+                remind_at: SimpleDate::parse_from_str("2024-04-05 08:00:00", "%Y-%m-%d %H:%M:%S")
+                    .unwrap(),
+                note: note.to_owned(),
+                body: None,
+                attachment: None,
+                recurrence: None,
+                miss_policy: MissPolicy::Keep,
+            })
+            .unwrap()
+        };
+
+        let groceries = create_with_note("Buy groceries");
+        create_with_note("Buy a gift");
+        create_with_note("Call mom");
+        set_color(groceries.id, Some(ReminderColor::Green)).unwrap();
+
+        let note_like = || vec![ReminderQueryOptions::NoteLike { text: "Buy" }];
+
+        assert_eq!(3, count_reminders(None).unwrap());
+        assert_eq!(
+            fetch_reminders(Some(note_like())).unwrap().len() as i64,
+            count_reminders(Some(note_like())).unwrap()
+        );
+        assert_eq!(2, count_reminders(Some(note_like())).unwrap());
+        assert_eq!(
+            1,
+            count_reminders(Some(vec![
+                ReminderQueryOptions::NoteLike { text: "Buy" },
+                ReminderQueryOptions::WithColor {
+                    color: ReminderColor::Green
+                },
+                // paging does not change the count
+                ReminderQueryOptions::Limit { limit: 0 },
+            ]))
+            .unwrap()
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn it_assigns_a_category_only_to_the_selected_reminders() {
+        truncate_table!();
+
+        let selected_ids = vec![
+            create_a_reminder!().id,
+            create_a_reminder!().id,
+            create_a_reminder!().id,
+        ];
+        let other = create_a_reminder!();
+
+        assert_eq!(3, assign_category(&selected_ids, Some("errands")).unwrap());
+
+        for id in selected_ids.iter() {
+            assert_eq!(
+                Some("errands".to_owned()),
+                fetch_reminder(*id).unwrap().category
+            );
+        }
+        assert_eq!(None, fetch_reminder(other.id).unwrap().category);
+
+        assert_eq!(3, assign_category(&selected_ids, None).unwrap());
+        assert_eq!(None, fetch_reminder(selected_ids[0]).unwrap().category);
+    }
+
+    #[test]
+    #[serial]
+    fn it_orders_reminders_with_the_same_remind_at_by_id() {
+        truncate_table!();
+
+        let d1 = SimpleDate::parse_from_str("2024-03-11 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let d2 = SimpleDate::parse_from_str("2024-03-10 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let first = create_a_reminder!(d1);
+        let second = create_a_reminder!(d1);
+        let earliest = create_a_reminder!(d2);
+
+        let fetched_reminders = fetch_reminders(Some(vec![ReminderQueryOptions::OrderBy {
+            column: ReminderOrderColumn::RemindAt,
+            direction: OrderDirection::Asc,
+        }]))
+        .unwrap();
+
+        assert_eq!(
+            vec![earliest.clone(), first.clone(), second.clone()],
+            fetched_reminders
+        );
+
+        let fetched_reminders = fetch_reminders(Some(vec![
+            ReminderQueryOptions::OrderBy {
+                column: ReminderOrderColumn::RemindAt,
+                direction: OrderDirection::Desc,
+            },
+            ReminderQueryOptions::OrderBy {
+                column: ReminderOrderColumn::Id,
+                direction: OrderDirection::Desc,
+            },
+        ]))
+        .unwrap();
+
+        assert_eq!(vec![second, first, earliest], fetched_reminders);
+    }
+
+    #[test]
+    #[serial]
+    fn it_orders_pinned_reminders_first() {
+        truncate_table!();
+
+        let d1 = SimpleDate::parse_from_str("2024-03-10 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let d2 = SimpleDate::parse_from_str("2024-03-11 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let d3 = SimpleDate::parse_from_str("2024-05-01 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let earliest = create_a_reminder!(d1);
+        let later = create_a_reminder!(d2);
+        let latest = create_a_reminder!(d3);
+        let latest = pin_reminder(latest.id, true).unwrap();
+
+        assert!(latest.pinned);
+
+        let fetched_reminders = fetch_reminders(Some(vec![
+            ReminderQueryOptions::OrderBy {
+                column: ReminderOrderColumn::Pinned,
+                direction: OrderDirection::Desc,
+            },
+            ReminderQueryOptions::OrderBy {
+                column: ReminderOrderColumn::RemindAt,
+                direction: OrderDirection::Asc,
+            },
+        ]))
+        .unwrap();
+
+        assert_eq!(vec![latest.clone(), earliest, later], fetched_reminders);
+        assert_eq!(
+            vec![latest.clone()],
+            fetch_reminders(Some(vec![ReminderQueryOptions::Pinned { pinned: true }])).unwrap()
+        );
+        assert_eq!(
+            2,
+            count_reminders(Some(vec![ReminderQueryOptions::Pinned { pinned: false }])).unwrap()
+        );
+        assert!(!pin_reminder(latest.id, false).unwrap().pinned);
+    }
+
+    #[test]
+    #[serial]
+    fn it_orders_reminders_by_how_close_they_are_to_now() {
+        truncate_table!();
+
+        let now = SimpleDate::parse_from_str("2024-03-10 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let parse = |date| SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap();
+        let last_week = create_a_reminder!(parse("2024-03-03 12:00:00"));
+        let in_an_hour = create_a_reminder!(parse("2024-03-10 13:00:00"));
+        let tomorrow = create_a_reminder!(parse("2024-03-11 08:00:00"));
+        let half_an_hour_ago = create_a_reminder!(parse("2024-03-10 11:30:00"));
+        let an_hour_ago = create_a_reminder!(parse("2024-03-10 11:00:00"));
+
+        let fetched_reminders = fetch_reminders(Some(vec![
+            ReminderQueryOptions::OrderBy {
+                column: ReminderOrderColumn::RemindAt,
+                direction: OrderDirection::Asc,
+            },
+            ReminderQueryOptions::ClosestTo { now },
+        ]))
+        .unwrap();
+
+        // equally close reminders keep the order of the query, so the past one comes first
+        assert_eq!(
+            vec![
+                half_an_hour_ago,
+                an_hour_ago,
+                in_an_hour,
+                tomorrow,
+                last_week
+            ],
+            fetched_reminders
+        );
+        assert_eq!(
+            0,
+            preview_delete(ReminderQueryOptions::ClosestTo { now }).unwrap()
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn it_makes_all_day_reminders_due_at_the_start_of_the_day() {
+        truncate_table!();
+
+        let date = SimpleDate::parse_from_str("2024-03-10 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let start_of_day = date.start_of_day().unwrap();
+        let reminder = create_a_reminder!(date);
+
+        let reminder = set_all_day(reminder.id, true).unwrap();
+
+        assert!(reminder.all_day);
+        assert!(reminder.remind_at.eq_to_second(&start_of_day));
+        assert_eq!(
+            vec![reminder.id],
+            fetch_reminders(Some(vec![ReminderQueryOptions::RemindAtBetween {
+                start_date: start_of_day,
+                end_date: start_of_day.add_duration(Duration::Minute(1)).unwrap(),
+            }]))
+            .unwrap()
+            .into_iter()
+            .map(|reminder| reminder.id)
+            .collect::<Vec<ReminderId>>()
+        );
+        assert!(!set_all_day(reminder.id, false).unwrap().all_day);
+    }
+
+    #[test]
+    #[serial]
+    fn it_snoozes_a_reminder_until_a_date() {
+        truncate_table!();
+
+        let now = SimpleDate::parse_from_str("2024-04-05 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let until = SimpleDate::parse_from_str("2024-04-05 08:15:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let reminder = create_a_reminder!(now);
+        acknowledge_all_due(now).unwrap();
+
+        let snoozed_reminder = snooze_reminder(reminder.id, until).unwrap();
+
+        assert!(snoozed_reminder.remind_at.eq_to_second(&until));
+        assert_eq!(None, snoozed_reminder.acknowledged_at);
+        assert_eq!(snoozed_reminder, fetch_reminder(reminder.id).unwrap());
+        assert!(snooze_reminder(ReminderId(reminder.id.0 + 100), until).is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn it_snoozes_many_reminders_by_a_duration() {
+        truncate_table!();
+
+        let date = |date: &str| SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap();
+        let first = create_a_reminder!(date("2024-04-05 08:00:00"));
+        let second = create_a_reminder!(date("2024-04-06 09:30:00"));
+        let third = create_a_reminder!(date("2024-04-07 23:30:00"));
+        let untouched = create_a_reminder!();
+        acknowledge_all_due(date("2024-04-05 12:00:00")).unwrap();
+
+        assert_eq!(
+            3,
+            snooze_many(&[first.id, second.id, third.id], Duration::Hour(1)).unwrap()
+        );
+
+        let is_due_at = |id: ReminderId, remind_at: &str| {
+            fetch_reminder(id)
+                .unwrap()
+                .remind_at
+                .eq_to_second(&date(remind_at))
+        };
+
+        assert!(is_due_at(first.id, "2024-04-05 09:00:00"));
+        assert!(is_due_at(second.id, "2024-04-06 10:30:00"));
+        assert!(is_due_at(third.id, "2024-04-08 00:30:00"));
+        assert!(is_due_at(untouched.id, "2024-04-05 08:00:00"));
+        assert_eq!(None, fetch_reminder(first.id).unwrap().acknowledged_at);
+        assert_ne!(None, fetch_reminder(untouched.id).unwrap().acknowledged_at);
+        assert_eq!(
+            0,
+            snooze_many(&[ReminderId(third.id.0 + 100)], Duration::Hour(1)).unwrap()
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn it_defers_a_reminder_to_the_next_workday() {
+        truncate_table!();
+
+        let friday =
+            SimpleDate::parse_from_str("2024-04-12 16:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let tuesday =
+            SimpleDate::parse_from_str("2024-04-16 16:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let friday_reminder = create_a_reminder!(friday);
+        let tuesday_reminder = create_a_reminder!(tuesday);
+
+        assert!(defer_to_next_workday(friday_reminder.id, (9, 0))
+            .unwrap()
+            .remind_at
+            .eq_to_second(
+                &SimpleDate::parse_from_str("2024-04-15 09:00:00", "%Y-%m-%d %H:%M:%S").unwrap()
+            ));
+        assert!(defer_to_next_workday(tuesday_reminder.id, (9, 30))
+            .unwrap()
+            .remind_at
+            .eq_to_second(
+                &SimpleDate::parse_from_str("2024-04-17 09:30:00", "%Y-%m-%d %H:%M:%S").unwrap()
+            ));
+
+        // with a friday and saturday weekend, thursday defers to sunday
+        set_weekend_days(&[Weekday::Fri, Weekday::Sat]);
+        let thursday =
+            SimpleDate::parse_from_str("2024-04-18 16:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let thursday_reminder = create_a_reminder!(thursday);
+        let deferred_reminder = defer_to_next_workday(thursday_reminder.id, (9, 0));
+        set_weekend_days(&[Weekday::Sat, Weekday::Sun]);
+
+        assert!(deferred_reminder.unwrap().remind_at.eq_to_second(
+            &SimpleDate::parse_from_str("2024-04-21 09:00:00", "%Y-%m-%d %H:%M:%S").unwrap()
+        ));
+        assert!(defer_to_next_workday(ReminderId(thursday_reminder.id.0 + 100), (9, 0)).is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn it_records_when_a_reminder_is_completed() {
+        truncate_table!();
+
+        let reminder = create_a_reminder!();
+        assert_eq!(None, time_to_complete(&reminder));
+
+        let completed_reminder = complete_reminder(reminder.id).unwrap();
+
+        assert!(completed_reminder.completed_at.is_some());
+        assert_eq!(
+            completed_reminder.completed_at,
+            fetch_reminder(reminder.id).unwrap().completed_at
+        );
+        // durations cannot be negative, so check it does not exceed the time since creation
+        let time_since_creation = SimpleDate::local() - reminder.created_at;
+        assert!(time_to_complete(&completed_reminder)
+            .is_some_and(|duration| duration <= time_since_creation));
+        assert!(complete_reminder(ReminderId(reminder.id.0 + 100)).is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn it_purges_reminders_completed_before_a_date() {
+        truncate_table!();
+
+        let complete_at = |id: ReminderId, date: &str| {
+            within_database(|conn| {
+                diesel::update(reminders_dsl::reminders.find(id.0))
+                    .set(
+                        reminders_dsl::completed_at.eq(SimpleDate::parse_from_str(
+                            date,
+                            "%Y-%m-%d %H:%M:%S",
+                        )
+                        .unwrap()
+                        .to_string()),
+                    )
+                    .execute(conn)?;
+
+                Ok(())
+            })
+            .unwrap();
+        };
+        let old_reminder = create_a_reminder!();
+        complete_at(old_reminder.id, "2024-03-01 08:00:00");
+        let recent_reminder = create_a_reminder!();
+        complete_at(recent_reminder.id, "2024-04-01 08:00:00");
+        let pending_reminder = create_a_reminder!();
+        let cutoff =
+            SimpleDate::parse_from_str("2024-03-15 00:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+
+        assert_eq!(1, purge_completed_before(cutoff).unwrap());
+        assert!(fetch_reminder(old_reminder.id).is_err());
+        assert_eq!(
+            vec![recent_reminder.id, pending_reminder.id],
+            fetch_reminders(None)
+                .unwrap()
+                .iter()
+                .map(|reminder| reminder.id)
+                .collect::<Vec<ReminderId>>()
+        );
+        assert_eq!(0, purge_completed_before(cutoff).unwrap());
+    }
+
+    #[test]
+    #[serial]
+    fn it_purges_reminders_completed_past_the_retention_period() {
+        truncate_table!();
+
+        let now = SimpleDate::parse_from_str("2024-04-10 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let complete_days_ago = |id: ReminderId, days: u32| {
+            within_database(|conn| {
+                diesel::update(reminders_dsl::reminders.find(id.0))
+                    .set(
+                        reminders_dsl::completed_at
+                            .eq(now.sub_duration(Duration::Day(days)).unwrap().to_string()),
+                    )
+                    .execute(conn)?;
+
+                Ok(())
+            })
+            .unwrap();
+        };
+        let expired_reminder = create_a_reminder!();
+        complete_days_ago(expired_reminder.id, 31);
+        let recent_reminder = create_a_reminder!();
+        complete_days_ago(recent_reminder.id, 29);
+
+        assert_eq!(1, purge_completed_past_retention(now, 30).unwrap());
+        assert!(fetch_reminder(expired_reminder.id).is_err());
+        assert!(fetch_reminder(recent_reminder.id).is_ok());
+    }
+
+    #[test]
+    fn it_renders_the_sql_of_the_fetch_query() {
+        let date = SimpleDate::parse_from_str("2024-03-11 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let query = build_fetch_query(Some(vec![
+            ReminderQueryOptions::RemindAtGe { date },
+            ReminderQueryOptions::NoteLike { text: "milk" },
+        ]))
+        .unwrap();
+        let sql = debug_query::<DatabaseBackend, _>(&query).to_string();
+
+        assert!(
+            sql.contains("WHERE ((`reminders`.`remind_at` >= ?) AND (`reminders`.`note` LIKE ?))")
+        );
+        assert!(sql.contains("2024-03-11T08:00:00Z"));
+        assert!(sql.contains("%milk%"));
+    }
+
+    #[test]
+    #[serial]
+    fn it_invalidates_cached_reminders_on_write() {
+        truncate_table!();
+        set_cache_enabled(true);
+
+        let first = create_a_reminder!();
+
+        assert_eq!(vec![first.clone()], fetch_reminders(None).unwrap());
+
+        // deleting behind the service's back does not invalidate the cache
+        truncate_table!();
+
+        assert_eq!(vec![first], fetch_reminders(None).unwrap());
+
+        let second = create_a_reminder!();
+        let fetched_reminders = fetch_reminders(None).unwrap();
+        set_cache_enabled(false);
+
+        assert_eq!(vec![second], fetched_reminders);
+    }
+
+    #[test]
+    #[serial]
+    fn it_does_not_grow_the_cache_with_upcoming_reminders() {
+        truncate_table!();
+        set_cache_enabled(true);
+
+        create_a_reminder!();
+
+        for _ in 0..5 {
+            fetch_upcoming(10).unwrap();
+        }
+
+        let cached_upcoming = cached_fetches_count();
+
+        // every query with a new date is another entry, up to the limit
+        for day in 0..(MAX_CACHED_FETCHES as u32 * 2) {
+            fetch_reminders(Some(vec![ReminderQueryOptions::RemindAtGe {
+                date: SimpleDate::local()
+                    .add_duration(Duration::Day(day))
+                    .unwrap(),
+            }]))
+            .unwrap();
+        }
+
+        let cached_fetches = cached_fetches_count();
+        set_cache_enabled(false);
+
+        assert_eq!(0, cached_upcoming);
+        assert_eq!(MAX_CACHED_FETCHES, cached_fetches);
+    }
+
+    #[test]
+    #[serial]
+    fn it_can_fetch_reminders_by_filtering_out_some_ids() {
+        truncate_table!();
+
+        let reminders = vec![
+            create_a_reminder!(),
+            create_a_reminder!(),
+            create_a_reminder!(),
+            create_a_reminder!(),
+            create_a_reminder!(),
+        ];
+        let ids_to_exclude = vec![reminders[2].id, reminders[4].id];
+        let fetched_reminders = fetch_reminders(Some(vec![ReminderQueryOptions::WithoutIds {
+            ids: &ids_to_exclude,
+        }]))
+        .unwrap();
+
+        assert_eq!(
+            reminders.len() - ids_to_exclude.len(),
+            fetched_reminders.len()
+        );
+
+        for id in ids_to_exclude.iter() {
+            for reminder in fetched_reminders.iter() {
+                assert_ne!(*id, reminder.id);
+            }
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn it_can_search_reminders_by_text() {
+        truncate_table!();
+
+        let date = SimpleDate::parse_from_str("2024-04-05 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let reminder = create_reminder(CreateReminderData {
+            remind_at: date,
+            note: "Buy some milk".to_owned(),
+            body: None,
+            attachment: None,
+            recurrence: None,
+            miss_policy: MissPolicy::Keep,
+        })
+        .unwrap();
+        create_a_reminder!();
+
+        let fetched_reminders = search("milk", None).unwrap();
+
+        assert_eq!(1, fetched_reminders.len());
+        assert_eq!(reminder, fetched_reminders[0]);
+        assert_eq!(2, search("", None).unwrap().len());
+    }
+
+    #[test]
+    #[serial]
+    fn it_finds_reminders_by_their_exact_note() {
+        truncate_table!();
+
+        let create = |note: &str| {
+            create_reminder(CreateReminderData {
+                remind_at: SimpleDate::parse_from_str("2024-04-05 08:00:00", "%Y-%m-%d %H:%M:%S")
+                    .unwrap(),
+                note: note.to_owned(),
+                body: None,
+                attachment: None,
+                recurrence: None,
+                miss_policy: MissPolicy::Keep,
+            })
+            .unwrap()
+        };
+        let first = create("Buy milk");
+        create("Buy milk and bread");
+        create("buy milk");
+        create("Buy milk ");
+        let second = create("Buy milk");
+
+        assert_eq!(vec![first, second], find_by_note("Buy milk").unwrap());
+        assert!(find_by_note("Buy").unwrap().is_empty());
+        assert!(find_by_note("Buy%").unwrap().is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn it_ranks_searched_reminders_by_relevance() {
+        truncate_table!();
+
+        let create = |note: &str, date: &str| {
+            create_reminder(CreateReminderData {
+                remind_at: SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap(),
+                note: note.to_owned(),
+                body: None,
+                attachment: None,
+                recurrence: None,
+                miss_policy: MissPolicy::Keep,
+            })
+            .unwrap()
+        };
+        let late_substring = create("Buy some milk", "2024-04-07 08:00:00");
+        let early_substring = create("Buy milk and bread", "2024-04-05 08:00:00");
+        let prefix = create("Milk the cow", "2024-04-09 08:00:00");
+        let exact = create("milk", "2024-04-10 08:00:00");
+        create("Buy bread", "2024-04-01 08:00:00");
+
+        let fetched_reminders = search("milk", None).unwrap();
+
+        assert_eq!(
+            vec![exact, prefix, early_substring, late_substring],
+            fetched_reminders
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn it_can_search_reminders_by_range() {
+        truncate_table!();
+
+        let d1 = SimpleDate::parse_from_str("2024-03-11 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let start_date =
+            SimpleDate::parse_from_str("2024-03-01 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let end_date =
+            SimpleDate::parse_from_str("2024-04-01 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let reminder = create_a_reminder!(d1);
+        create_a_reminder!();
+
+        let fetched_reminders = search("", Some((start_date, end_date))).unwrap();
+
+        assert_eq!(1, fetched_reminders.len());
+        assert_eq!(reminder, fetched_reminders[0]);
+    }
+
+    #[test]
+    #[serial]
+    fn it_can_search_reminders_by_text_and_range() {
+        truncate_table!();
+
+        let d1 = SimpleDate::parse_from_str("2024-03-11 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let d2 = SimpleDate::parse_from_str("2024-04-11 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let start_date =
+            SimpleDate::parse_from_str("2024-03-01 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let end_date =
+            SimpleDate::parse_from_str("2024-04-01 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let reminder = create_reminder(CreateReminderData {
+            remind_at: d1,
+            note: "Buy some milk".to_owned(),
+            body: None,
+            attachment: None,
+            recurrence: None,
+            miss_policy: MissPolicy::Keep,
+        })
+        .unwrap();
+        create_reminder(CreateReminderData {
+            remind_at: d2,
+            note: "Buy some milk".to_owned(),
+            body: None,
+            attachment: None,
+            recurrence: None,
+            miss_policy: MissPolicy::Keep,
+        })
+        .unwrap();
+        create_a_reminder!(d1);
+
+        let fetched_reminders = search("milk", Some((start_date, end_date))).unwrap();
+
+        assert_eq!(1, fetched_reminders.len());
+        assert_eq!(reminder, fetched_reminders[0]);
+    }
+
+    #[test]
+    #[serial]
+    fn it_can_postpone_overdue_reminders() {
+        truncate_table!();
+
+        let overdue_date =
+            SimpleDate::parse_from_str("2024-04-05 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let future_date =
+            SimpleDate::parse_from_str("2099-04-05 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let target =
+            SimpleDate::parse_from_str("2099-01-01 10:30:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let overdue_reminder = create_a_reminder!(overdue_date);
+        let other_overdue_reminder = create_a_reminder!(overdue_date);
+        let future_reminder = create_a_reminder!(future_date);
+
+        assert_eq!(2, postpone_overdue_to(target, false).unwrap());
+
+        assert_eq!(
+            target,
+            fetch_reminder(overdue_reminder.id).unwrap().remind_at
+        );
+        assert_eq!(
+            target,
+            fetch_reminder(other_overdue_reminder.id).unwrap().remind_at
+        );
+        assert_eq!(future_reminder, fetch_reminder(future_reminder.id).unwrap());
+    }
+
+    #[test]
+    #[serial]
+    fn it_can_postpone_overdue_reminders_preserving_their_time() {
+        truncate_table!();
+
+        let overdue_date =
+            SimpleDate::parse_from_str("2024-04-05 08:15:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let target =
+            SimpleDate::parse_from_str("2099-01-01 10:30:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let reminder = create_a_reminder!(overdue_date);
+
+        assert_eq!(1, postpone_overdue_to(target, true).unwrap());
+
+        let remind_at = fetch_reminder(reminder.id).unwrap().remind_at;
+
+        assert_eq!(
+            (target.year(), target.month(), target.day()),
+            (remind_at.year(), remind_at.month(), remind_at.day())
+        );
+        assert_eq!(
+            (8, 15, 0),
+            (remind_at.hour(), remind_at.minute(), remind_at.second())
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn it_can_catch_up_a_recurring_reminder() {
+        truncate_table!();
+
+        let remind_at =
+            SimpleDate::parse_from_str("2024-04-01 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let now = SimpleDate::parse_from_str("2024-04-24 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let reminder = create_reminder(CreateReminderData {
+            remind_at,
+            note: "Take out the trash".to_owned(),
+            body: None,
+            attachment: None,
+            recurrence: Some(Recurrence::Weekly),
+            miss_policy: MissPolicy::Keep,
+        })
+        .unwrap();
+
+        let caught_up_reminder = catch_up_recurrence(reminder.id, now).unwrap();
+
+        assert_eq!(
+            SimpleDate::parse_from_str("2024-04-29 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap(),
+            caught_up_reminder.remind_at
+        );
+        assert_eq!(Some(Recurrence::Weekly), caught_up_reminder.recurrence);
+        assert_eq!(caught_up_reminder, fetch_reminder(reminder.id).unwrap());
+    }
+
+    #[test]
+    #[serial]
+    fn it_keeps_recurring_reminders_through_a_cleanup_pass() {
+        truncate_table!();
+
+        let remind_at =
+            SimpleDate::parse_from_str("2024-04-22 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let now = SimpleDate::parse_from_str("2024-04-24 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let recurring_reminder = create_reminder(CreateReminderData {
+            remind_at,
+            note: "Water the plants".to_owned(),
+            body: None,
+            attachment: None,
+            recurrence: Some(Recurrence::Daily),
+            miss_policy: MissPolicy::Keep,
+        })
+        .unwrap();
+        let one_off_reminder = create_reminder(CreateReminderData {
+            remind_at,
+            note: "Call the bank".to_owned(),
+            body: None,
+            attachment: None,
+            recurrence: None,
+            miss_policy: MissPolicy::Keep,
+        })
+        .unwrap();
+
+        // the same pass the daemon runs on its cleanup tick
+        assert_eq!(
+            vec![recurring_reminder.id],
+            catch_up_due_recurrences(now).unwrap()
+        );
+        assert_eq!(
+            1,
+            delete_past_one_off_reminders(now.sub_duration(Duration::Day(1)).unwrap()).unwrap()
+        );
+
+        assert!(fetch_reminder(one_off_reminder.id).is_err());
+
+        let caught_up_reminder = fetch_reminder(recurring_reminder.id).unwrap();
+
+        assert_eq!(
+            SimpleDate::parse_from_str("2024-04-25 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap(),
+            caught_up_reminder.remind_at
+        );
+        assert!(!caught_up_reminder.is_completed());
+    }
+
+    #[test]
+    #[serial]
+    fn it_stops_a_recurring_reminder_after_its_count() {
+        truncate_table!();
+
+        let remind_at =
+            SimpleDate::parse_from_str("2024-04-01 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let reminder = create_reminder(CreateReminderData {
+            remind_at,
+            note: "Take a pill".to_owned(),
+            body: None,
+            attachment: None,
+            recurrence: Some(Recurrence::Daily),
+            miss_policy: MissPolicy::Keep,
+        })
+        .unwrap();
+        set_recurrence_end(reminder.id, None, Some(3)).unwrap();
+
+        let now = SimpleDate::parse_from_str("2024-04-02 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let caught_up_reminder = catch_up_recurrence(reminder.id, now).unwrap();
+
+        assert_eq!(
+            SimpleDate::parse_from_str("2024-04-03 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap(),
+            caught_up_reminder.remind_at
+        );
+        assert_eq!(Some(1), caught_up_reminder.recurrence_count);
+        assert!(!caught_up_reminder.is_completed());
+
+        // the third time was the last one
+        let now = SimpleDate::parse_from_str("2024-04-10 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let ended_reminder = catch_up_recurrence(reminder.id, now).unwrap();
+
+        assert_eq!(caught_up_reminder.remind_at, ended_reminder.remind_at);
+        assert_eq!(Some(now), ended_reminder.completed_at);
+        assert_eq!(
+            ended_reminder,
+            catch_up_recurrence(reminder.id, now).unwrap()
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn it_stops_a_recurring_reminder_after_its_end_date() {
+        truncate_table!();
+
+        let remind_at =
+            SimpleDate::parse_from_str("2024-04-01 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let until = SimpleDate::parse_from_str("2024-04-20 00:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let now = SimpleDate::parse_from_str("2024-05-24 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let reminder = create_reminder(CreateReminderData {
+            remind_at,
+            note: "Take out the trash".to_owned(),
+            body: None,
+            attachment: None,
+            recurrence: Some(Recurrence::Weekly),
+            miss_policy: MissPolicy::Keep,
+        })
+        .unwrap();
+        let reminder = set_recurrence_end(reminder.id, Some(until), None).unwrap();
+
+        assert_eq!(Some(until), reminder.recurrence_until);
+
+        let ended_reminder = catch_up_recurrence(reminder.id, now).unwrap();
+
+        assert_eq!(
+            SimpleDate::parse_from_str("2024-04-15 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap(),
+            ended_reminder.remind_at
+        );
+        assert!(ended_reminder.is_completed());
+        assert_eq!(ended_reminder, fetch_reminder(reminder.id).unwrap());
+    }
+
+    #[test]
+    #[serial]
+    fn it_does_not_catch_up_non_recurring_reminders() {
+        truncate_table!();
+
+        let reminder = create_a_reminder!();
+        let now = SimpleDate::parse_from_str("2024-04-24 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+
+        assert_eq!(reminder, catch_up_recurrence(reminder.id, now).unwrap());
+    }
+
+    #[test]
+    #[serial]
+    fn it_can_add_attachments_to_a_reminder() {
+        truncate_table!();
+
+        let reminder = create_a_reminder!();
+        let attachment = add_attachment(reminder.id, "/home/user/agenda.pdf").unwrap();
+
+        assert_eq!(reminder.id, attachment.reminder_id);
+        assert_eq!("/home/user/agenda.pdf", attachment.path);
+    }
+
+    #[test]
+    #[serial]
+    fn it_can_list_attachments_of_a_reminder() {
+        truncate_table!();
+
+        let reminder = create_a_reminder!();
+        let other_reminder = create_a_reminder!();
+        let agenda = add_attachment(reminder.id, "/home/user/agenda.pdf").unwrap();
+        let slides = add_attachment(reminder.id, "https://example.com/slides").unwrap();
+        add_attachment(other_reminder.id, "/home/user/notes.txt").unwrap();
+
+        assert_eq!(vec![agenda, slides], list_attachments(reminder.id).unwrap());
+    }
+
+    #[test]
+    #[serial]
+    fn it_removes_attachments_when_their_reminder_is_deleted() {
+        truncate_table!();
+
+        let reminder = create_a_reminder!();
+        let other_reminder = create_a_reminder!();
+        add_attachment(reminder.id, "/home/user/agenda.pdf").unwrap();
+        let notes = add_attachment(other_reminder.id, "/home/user/notes.txt").unwrap();
+
+        delete_reminder(reminder.id).unwrap();
+
+        assert!(list_attachments(reminder.id).unwrap().is_empty());
+        assert_eq!(vec![notes], list_attachments(other_reminder.id).unwrap());
+    }
+
+    #[test]
+    #[serial]
+    fn it_rejects_notes_longer_than_the_max_note_length() {
+        truncate_table!();
 
-        let create_reminder: CreateReminder = reminder.into();
-        let created_reminder = diesel::insert_into(reminders::table)
-            .values(&create_reminder)
-            .returning(Reminder::as_returning())
-            .get_result(conn)?;
+        let date = SimpleDate::parse_from_str("2024-04-05 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let reminder = create_reminder(CreateReminderData {
+            remind_at: date,
+            note: "a".repeat(DEFAULT_MAX_NOTE_LENGTH),
+            body: None,
+            attachment: None,
+            recurrence: None,
+            miss_policy: MissPolicy::Keep,
+        })
+        .unwrap();
 
-        debug!(target: "CORE_REMINDERS_SERVICE", "Reminder created. ID is: : {}", created_reminder.id);
+        assert_eq!(DEFAULT_MAX_NOTE_LENGTH, reminder.note.len());
+        assert!(matches!(
+            create_reminder(CreateReminderData {
+                remind_at: date,
+                note: "a".repeat(DEFAULT_MAX_NOTE_LENGTH + 1),
+                body: None,
+                attachment: None,
+                recurrence: None,
+                miss_policy: MissPolicy::Keep,
+            }),
+            Err(DatabaseError::NoteTooLong(DEFAULT_MAX_NOTE_LENGTH))
+        ));
+        assert!(matches!(
+            update_reminder(UpdateReminderData {
+                id: reminder.id,
+                note: Some("a".repeat(DEFAULT_MAX_NOTE_LENGTH + 1)),
+                body: None,
+                remind_at: None,
+                attachment: None,
+                recurrence: None,
+                miss_policy: None,
+            }),
+            Err(DatabaseError::NoteTooLong(DEFAULT_MAX_NOTE_LENGTH))
+        ));
+    }
 
-        Ok(created_reminder.into())
-    })
-}
+    #[test]
+    #[serial]
+    fn it_requires_confirmation_only_above_the_delete_threshold() {
+        truncate_table!();
+        set_delete_confirmation_threshold(3);
 
-pub fn update_reminder(reminder: UpdateReminderData) -> DatabaseResult<ReminderData> {
-    database::within_database(|conn| {
-        debug!(target: "CORE_REMINDERS_SERVICE", "Updating reminder: {reminder:?}");
+        let d1 = SimpleDate::parse_from_str("2024-04-05 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let d2 = SimpleDate::parse_from_str("2024-04-07 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let until = SimpleDate::parse_from_str("2024-04-06 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
 
-        let id = reminder.id;
-        let update_reminder: UpdateReminder = reminder.into();
-        let updated_reminder = diesel::update(reminders_dsl::reminders.find(id))
-            .set(&update_reminder)
-            .returning(Reminder::as_returning())
-            .get_result(conn)?;
+        for _ in 0..3 {
+            create_a_reminder!(d1);
+        }
+        create_a_reminder!(d2);
 
-        debug!(target: "CORE_REMINDERS_SERVICE", "Reminder {id} updated!");
+        let rows = preview_delete(ReminderQueryOptions::RemindAtLe { date: until }).unwrap();
+        assert_eq!(3, rows);
+        assert!(!requires_delete_confirmation(rows));
 
-        Ok(updated_reminder.into())
-    })
-}
+        create_a_reminder!(d1);
 
-pub fn delete_reminders(option: ReminderQueryOptions) -> DatabaseResult<()> {
-    database::within_database(|conn| {
-        debug!(target: "CORE_REMINDERS_SERVICE", "Deleting reminders: {option:?}");
+        let rows = preview_delete(ReminderQueryOptions::RemindAtLe { date: until }).unwrap();
+        assert_eq!(4, rows);
+        assert!(requires_delete_confirmation(rows));
 
-        match option {
-            ReminderQueryOptions::RemindAtBetween {
-                end_date,
-                start_date,
-            } => {
-                diesel::delete(
-                    reminders_dsl::reminders.filter(reminders_dsl::remind_at.between(
-                        start_date.to_string().into_sql::<SqlDateType>(),
-                        end_date.to_string().into_sql::<SqlDateType>(),
-                    )),
-                )
-                .execute(conn)?;
-            }
-            ReminderQueryOptions::RemindAtGe { date } => {
-                diesel::delete(reminders_dsl::reminders.filter(
-                    reminders_dsl::remind_at.ge(date.to_string().into_sql::<SqlDateType>()),
-                ))
-                .execute(conn)?;
-            }
-            ReminderQueryOptions::RemindAtLe { date } => {
-                diesel::delete(reminders_dsl::reminders.filter(
-                    reminders_dsl::remind_at.le(date.to_string().into_sql::<SqlDateType>()),
-                ))
-                .execute(conn)?;
-            }
-            ReminderQueryOptions::WithIds { ids } => {
-                diesel::delete(reminders_dsl::reminders.filter(reminders_dsl::id.eq_any(ids)))
-                    .execute(conn)?;
-            }
-            ReminderQueryOptions::WithoutIds { ids } => {
-                diesel::delete(
-                    reminders_dsl::reminders
-                        .filter(diesel::dsl::not(reminders_dsl::id.eq_any(ids))),
-                )
-                .execute(conn)?;
-            }
-        };
+        // previewing does not delete anything
+        assert_eq!(5, fetch_reminders(None).unwrap().len());
 
-        debug!(target: "CORE_REMINDERS_SERVICE", "Deleted Reminders.");
+        set_delete_confirmation_threshold(DEFAULT_DELETE_CONFIRMATION_THRESHOLD);
+    }
 
-        Ok(())
-    })
-}
+    #[test]
+    #[serial]
+    fn it_labels_failed_queries_with_the_operation() {
+        truncate_table!();
 
-pub fn delete_reminder(id: i64) -> DatabaseResult<()> {
-    database::within_database(|conn| {
-        debug!(target: "CORE_REMINDERS_SERVICE", "Deleting reminder: {id}");
+        let error = fetch_reminder(ReminderId(404)).unwrap_err();
 
-        diesel::delete(reminders_dsl::reminders.find(id)).execute(conn)?;
+        assert!(matches!(
+            error,
+            DatabaseError::OperationFailed {
+                operation: "fetch reminder",
+                ..
+            }
+        ));
+        assert_eq!(
+            "Failed to fetch reminder: Record not found",
+            error.to_string()
+        );
+    }
 
-        debug!(target: "CORE_REMINDERS_SERVICE", "Deleted Reminder: {id}");
+    #[test]
+    #[serial]
+    fn it_inserts_a_reminder_on_upsert_when_it_does_not_exist() {
+        truncate_table!();
 
-        Ok(())
-    })
-}
+        let mut reminder = fakes::create_reminder();
+        reminder.id = ReminderId(42);
+        reminder.remind_at =
+            SimpleDate::parse_from_str("2024-04-05 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        reminder.created_at = reminder.remind_at;
 
-#[cfg(test)]
-mod tests {
-    use self::database::{init_database, within_database};
-    use ctor::ctor;
-    use diesel::sql_query;
-    use diesel_migrations::{embed_migrations, EmbeddedMigrations};
-    use hkb_date::date::SimpleDate;
-    use hkb_date::duration::Duration;
-    use serial_test::serial;
-    pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("./migrations");
+        assert_eq!(reminder, upsert(reminder.clone()).unwrap());
+        assert_eq!(reminder, fetch_reminder(ReminderId(42)).unwrap());
+    }
 
-    use super::*;
+    #[test]
+    #[serial]
+    fn it_rejects_an_upsert_with_a_note_over_the_limit() {
+        truncate_table!();
 
-    macro_rules! create_a_reminder {
-        () => {{
-            let date =
-                SimpleDate::parse_from_str("2024-04-05 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
-            let reminder_data = CreateReminderData {
-                remind_at: date,
-                note: "Testing".to_owned(),
-            };
+        let mut reminder = create_a_reminder!();
+        let original_note = reminder.note.clone();
+        reminder.note = "a".repeat(DEFAULT_MAX_NOTE_LENGTH + 1);
 
-            create_reminder(reminder_data).unwrap()
-        }};
+        assert!(matches!(
+            upsert(reminder.clone()),
+            Err(DatabaseError::NoteTooLong(DEFAULT_MAX_NOTE_LENGTH))
+        ));
+        assert_eq!(original_note, fetch_reminder(reminder.id).unwrap().note);
 
-        ($date:expr) => {{
-            let reminder_data = CreateReminderData {
-                remind_at: $date,
-                note: "Testing".to_owned(),
-            };
+        reminder.id = ReminderId(reminder.id.0 + 100);
 
-            create_reminder(reminder_data).unwrap()
-        }};
+        assert!(upsert(reminder.clone()).is_err());
+        assert!(fetch_reminder(reminder.id).is_err());
     }
 
-    macro_rules! truncate_table {
-        () => {
-            within_database(|conn| {
-                sql_query("DELETE from reminders where 1=1")
-                    .execute(conn)
-                    .unwrap();
+    #[test]
+    #[serial]
+    fn it_can_restore_a_reminder_with_its_id() {
+        truncate_table!();
 
-                Ok(())
-            })
-            .unwrap();
-        };
+        let mut reminder = fakes::create_reminder();
+        reminder.id = ReminderId(1337);
+        reminder.remind_at =
+            SimpleDate::parse_from_str("2024-04-05 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        reminder.created_at = reminder.remind_at;
+
+        assert_eq!(reminder, restore_reminder(reminder.clone()).unwrap());
+        assert_eq!(reminder, fetch_reminder(ReminderId(1337)).unwrap());
+        assert!(matches!(
+            restore_reminder(reminder),
+            Err(DatabaseError::ReminderIdTaken(ReminderId(1337)))
+        ));
     }
 
     #[test]
-    #[ctor]
-    fn init() {
-        init_database(":memory:", vec![MIGRATIONS]).unwrap();
+    #[serial]
+    fn it_updates_a_reminder_on_upsert_when_it_exists() {
+        truncate_table!();
+
+        let mut reminder = create_a_reminder!();
+        reminder.note = "Updated from remote".to_owned();
+        reminder.attachment = Some("https://example.com".to_owned());
+        reminder.completed_at = Some(reminder.remind_at);
+
+        assert_eq!(reminder, upsert(reminder.clone()).unwrap());
+        assert_eq!(reminder, fetch_reminder(reminder.id).unwrap());
+        assert_eq!(1, fetch_reminders(None).unwrap().len());
     }
 
     #[test]
     #[serial]
-    fn it_can_fetch_a_reminder() {
-        let reminder = create_a_reminder!();
-        let fetched_reminder = fetch_reminder(reminder.id).unwrap();
+    fn it_can_fetch_upcoming_reminders() {
+        truncate_table!();
+
+        let now = SimpleDate::local();
+        let mut reminder_ids_by_offset = [7, -2, 3, 9, -5, 1, 5, -1, 2, 8]
+            .into_iter()
+            .map(|offset: i32| {
+                let date = if offset < 0 {
+                    now.sub_duration(Duration::Hour(offset.unsigned_abs()))
+                } else {
+                    now.add_duration(Duration::Hour(offset as u32))
+                };
+
+                (offset, create_a_reminder!(date.unwrap()).id)
+            })
+            .collect::<Vec<(i32, ReminderId)>>();
+        reminder_ids_by_offset.sort();
+
+        let upcoming_reminder_ids = fetch_upcoming(3)
+            .unwrap()
+            .into_iter()
+            .map(|reminder| reminder.id)
+            .collect::<Vec<ReminderId>>();
 
-        assert_eq!(reminder.id, fetched_reminder.id);
-        assert_eq!(reminder.note, fetched_reminder.note);
         assert_eq!(
-            reminder.remind_at.to_string(),
-            fetched_reminder.remind_at.to_string()
+            reminder_ids_by_offset[3..6]
+                .iter()
+                .map(|(_, id)| *id)
+                .collect::<Vec<ReminderId>>(),
+            upcoming_reminder_ids
         );
     }
 
     #[test]
     #[serial]
-    fn it_can_fetch_reminders() {
+    fn it_can_order_and_limit_reminders() {
         truncate_table!();
 
-        let reminders = [create_a_reminder!(),
-            create_a_reminder!(),
-            create_a_reminder!()];
-        let fetched_reminders = fetch_reminders(None).unwrap();
+        let first_date =
+            SimpleDate::parse_from_str("2024-04-05 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let second_date =
+            SimpleDate::parse_from_str("2024-04-06 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let second_reminder = create_a_reminder!(second_date);
+        let first_reminder = create_a_reminder!(first_date);
+        create_a_reminder!(second_date.add_duration(Duration::Day(1)).unwrap());
+
+        let reminders = fetch_reminders(Some(vec![
+            ReminderQueryOptions::OrderBy {
+                column: ReminderOrderColumn::RemindAt,
+                direction: OrderDirection::Asc,
+            },
+            ReminderQueryOptions::Limit { limit: 2 },
+        ]))
+        .unwrap();
 
-        assert_eq!(reminders.len(), fetched_reminders.len());
+        assert_eq!(vec![first_reminder, second_reminder], reminders);
 
-        for i in 0..fetched_reminders.len() {
-            let reminder = fetched_reminders.get(i).unwrap();
-            let expected_reminder = reminders.get(i).unwrap();
+        let next_page = fetch_reminders(Some(vec![
+            ReminderQueryOptions::OrderBy {
+                column: ReminderOrderColumn::RemindAt,
+                direction: OrderDirection::Asc,
+            },
+            ReminderQueryOptions::Limit { limit: 2 },
+            ReminderQueryOptions::Offset { offset: 2 },
+        ]))
+        .unwrap();
 
-            assert_eq!(expected_reminder, reminder);
-        }
+        assert_eq!(1, next_page.len());
+        assert!(next_page[0]
+            .remind_at
+            .eq_to_second(&second_date.add_duration(Duration::Day(1)).unwrap()));
+    }
+
+    macro_rules! create_a_missed_reminder {
+        ($miss_policy:expr) => {{
+            create_reminder(CreateReminderData {
+                remind_at: SimpleDate::parse_from_str("2024-04-05 08:15:00", "%Y-%m-%d %H:%M:%S")
+                    .unwrap(),
+                note: "Testing".to_owned(),
+                body: None,
+                attachment: None,
+                recurrence: None,
+                miss_policy: $miss_policy,
+            })
+            .unwrap()
+        }};
     }
 
     #[test]
     #[serial]
-    fn it_can_fetch_reminders_in_between() {
+    fn it_keeps_missed_reminders_with_the_keep_policy() {
         truncate_table!();
 
-        let d1 = SimpleDate::parse_from_str("2024-03-11 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
-        let d2 = SimpleDate::parse_from_str("2024-03-12 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
-        let start_date =
-            SimpleDate::parse_from_str("2024-03-01 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
-        let end_date =
-            SimpleDate::parse_from_str("2024-04-01 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
-
+        let now = SimpleDate::parse_from_str("2024-04-10 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
         let reminders = vec![
-            create_a_reminder!(d1),
-            create_a_reminder!(d2),
-            create_a_reminder!(),
-            create_a_reminder!(),
+            create_a_missed_reminder!(MissPolicy::Keep),
+            create_a_missed_reminder!(MissPolicy::Keep),
         ];
-        let fetched_reminders =
-            fetch_reminders(Some(vec![ReminderQueryOptions::RemindAtBetween {
-                end_date,
-                start_date,
-            }]))
-            .unwrap();
-
-        assert_eq!(2, fetched_reminders.len());
-
-        assert_eq!(reminders.first().unwrap(), fetched_reminders.first().unwrap());
-        assert_eq!(reminders.get(1).unwrap(), fetched_reminders.get(1).unwrap());
-
-        let start_date =
-            SimpleDate::parse_from_str("2024-04-01 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
-        let end_date =
-            SimpleDate::parse_from_str("2024-05-01 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
-        let fetched_reminders =
-            fetch_reminders(Some(vec![ReminderQueryOptions::RemindAtBetween {
-                end_date,
-                start_date,
-            }]))
-            .unwrap();
-
-        assert_eq!(2, fetched_reminders.len());
 
-        assert_eq!(reminders.get(2).unwrap(), fetched_reminders.first().unwrap());
-        assert_eq!(reminders.get(3).unwrap(), fetched_reminders.get(1).unwrap());
+        assert_eq!(0, reconcile_missed(now).unwrap());
+        assert_eq!(reminders, fetch_reminders(None).unwrap());
     }
 
     #[test]
     #[serial]
-    fn it_can_fetch_reminders_by_filtering_out_some_ids() {
+    fn it_snoozes_missed_reminders_to_the_next_day() {
         truncate_table!();
 
+        let now = SimpleDate::parse_from_str("2024-04-10 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
         let reminders = vec![
-            create_a_reminder!(),
-            create_a_reminder!(),
-            create_a_reminder!(),
-            create_a_reminder!(),
-            create_a_reminder!(),
+            create_a_missed_reminder!(MissPolicy::SnoozeToNextDay),
+            create_a_missed_reminder!(MissPolicy::SnoozeToNextDay),
         ];
-        let ids_to_exclude = vec![reminders[2].id, reminders[4].id];
-        let fetched_reminders = fetch_reminders(Some(vec![ReminderQueryOptions::WithoutIds {
-            ids: &ids_to_exclude,
-        }]))
+        let future_reminder = create_reminder(CreateReminderData {
+            remind_at: SimpleDate::parse_from_str("2024-04-10 18:00:00", "%Y-%m-%d %H:%M:%S")
+                .unwrap(),
+            note: "Testing".to_owned(),
+            body: None,
+            attachment: None,
+            recurrence: None,
+            miss_policy: MissPolicy::SnoozeToNextDay,
+        })
         .unwrap();
 
-        assert_eq!(
-            reminders.len() - ids_to_exclude.len(),
-            fetched_reminders.len()
-        );
+        assert_eq!(2, reconcile_missed(now).unwrap());
 
-        for id in ids_to_exclude.iter() {
-            for reminder in fetched_reminders.iter() {
-                assert_ne!(*id, reminder.id);
-            }
+        for reminder in reminders {
+            assert_eq!(
+                SimpleDate::parse_from_str("2024-04-11 08:15:00", "%Y-%m-%d %H:%M:%S").unwrap(),
+                fetch_reminder(reminder.id).unwrap().remind_at
+            );
         }
+
+        assert_eq!(future_reminder, fetch_reminder(future_reminder.id).unwrap());
+    }
+
+    #[test]
+    #[serial]
+    fn it_drops_missed_reminders_with_the_drop_policy() {
+        truncate_table!();
+
+        let now = SimpleDate::parse_from_str("2024-04-10 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        create_a_missed_reminder!(MissPolicy::Drop);
+        create_a_missed_reminder!(MissPolicy::Drop);
+        let kept_reminder = create_a_missed_reminder!(MissPolicy::Keep);
+
+        assert_eq!(2, reconcile_missed(now).unwrap());
+        assert_eq!(vec![kept_reminder], fetch_reminders(None).unwrap());
     }
 
     #[test]
@@ -409,11 +3433,119 @@ mod tests {
         let reminder_data = CreateReminderData {
             remind_at: date,
             note: "Testing".to_owned(),
+            body: None,
+            attachment: None,
+            recurrence: None,
+            miss_policy: MissPolicy::Keep,
         };
         let reminder = create_reminder(reminder_data).unwrap();
 
         assert_eq!("Testing", reminder.note);
-        assert_eq!(date.to_string(), reminder.remind_at.to_string());
+        assert!(date.eq_to_second(&reminder.remind_at));
+    }
+
+    #[test]
+    #[serial]
+    fn it_can_create_a_reminder_with_a_body() {
+        let date = SimpleDate::parse_from_str("2024-04-05 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let reminder = create_reminder(CreateReminderData {
+            remind_at: date,
+            note: "Renew passport".to_owned(),
+            body: Some("Bring two photos.\nThe office closes at 16:00.".to_owned()),
+            attachment: None,
+            recurrence: None,
+            miss_policy: MissPolicy::Keep,
+        })
+        .unwrap();
+        let fetched_reminder = fetch_reminder(reminder.id).unwrap();
+
+        assert_eq!("Renew passport", fetched_reminder.note);
+        assert_eq!(
+            Some("Bring two photos.\nThe office closes at 16:00.".to_owned()),
+            fetched_reminder.body
+        );
+        assert_eq!(None, create_a_reminder!().body);
+    }
+
+    #[test]
+    #[serial]
+    fn it_truncates_seconds_with_minute_precision() {
+        let date = SimpleDate::parse_from_str("2024-04-05 08:00:37", "%Y-%m-%d %H:%M:%S").unwrap();
+        let truncated_date =
+            SimpleDate::parse_from_str("2024-04-05 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+
+        assert_eq!(date, create_a_reminder!(date).remind_at);
+
+        set_precision(ReminderPrecision::Minute);
+
+        let reminder = create_a_reminder!(date);
+        let updated_reminder = update_reminder(UpdateReminderData {
+            id: reminder.id,
+            note: None,
+            body: None,
+            remind_at: Some(date.add_duration(Duration::Hour(1)).unwrap()),
+            attachment: None,
+            recurrence: None,
+            miss_policy: None,
+        })
+        .unwrap();
+
+        set_precision(ReminderPrecision::Second);
+
+        assert_eq!(truncated_date, reminder.remind_at);
+        assert_eq!(
+            truncated_date.add_duration(Duration::Hour(1)).unwrap(),
+            updated_reminder.remind_at
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn it_can_create_reminders_in_a_batch() {
+        truncate_table!();
+
+        let create_data = |note: &str| CreateReminderData {
+            remind_at: SimpleDate::parse_from_str("2024-04-05 08:00:00", "%Y-%m-%d %H:%M:%S")
+                .unwrap(),
+            note: note.to_owned(),
+            body: None,
+            attachment: None,
+            recurrence: None,
+            miss_policy: MissPolicy::Keep,
+        };
+
+        let reminders =
+            create_reminders(vec![create_data("Buy milk"), create_data("Call mom")]).unwrap();
+
+        assert_eq!(2, reminders.len());
+        assert_eq!(reminders, fetch_reminders(None).unwrap());
+        assert!(create_reminders(vec![
+            create_data("Water plants"),
+            create_data(&"a".repeat(get_max_note_length() + 1))
+        ])
+        .is_err());
+        assert_eq!(2, fetch_reminders(None).unwrap().len());
+    }
+
+    #[test]
+    #[serial]
+    fn it_can_create_a_reminder_with_an_attachment() {
+        let reminder = create_reminder(CreateReminderData {
+            remind_at: SimpleDate::local(),
+            note: "Meeting".to_owned(),
+            body: None,
+            attachment: Some("https://example.com/agenda.pdf".to_owned()),
+            recurrence: None,
+            miss_policy: MissPolicy::Keep,
+        })
+        .unwrap();
+        let fetched_reminder = fetch_reminder(reminder.id).unwrap();
+
+        assert_eq!(
+            Some("https://example.com/agenda.pdf".to_owned()),
+            fetched_reminder.attachment
+        );
+        assert_eq!(reminder, fetched_reminder);
     }
 
     #[test]
@@ -423,16 +3555,17 @@ mod tests {
         let updated_reminder = update_reminder(UpdateReminderData {
             id: reminder.id,
             note: Some("Testing a new".to_owned()),
+            body: None,
             remind_at: None,
+            attachment: None,
+            recurrence: None,
+            miss_policy: None,
         })
         .unwrap();
 
         assert_eq!("Testing a new", updated_reminder.note);
         assert_ne!(reminder.note, updated_reminder.note);
-        assert_eq!(
-            reminder.remind_at.to_string(),
-            updated_reminder.remind_at.to_string()
-        );
+        assert!(reminder.remind_at.eq_to_second(&updated_reminder.remind_at));
     }
 
     #[test]
@@ -443,17 +3576,20 @@ mod tests {
             .add_duration(Duration::Month(1))
             .unwrap();
 
-        let expected_date = date.to_string();
         let updated_reminder = update_reminder(UpdateReminderData {
             id: reminder.id,
             note: None,
+            body: None,
             remind_at: Some(date),
+            attachment: None,
+            recurrence: None,
+            miss_policy: None,
         })
         .unwrap();
 
         assert_eq!(reminder.note, updated_reminder.note);
-        assert_ne!(reminder.remind_at.to_string(), expected_date);
-        assert_eq!(expected_date, updated_reminder.remind_at.to_string());
+        assert!(!reminder.remind_at.eq_to_second(&date));
+        assert!(date.eq_to_second(&updated_reminder.remind_at));
     }
 
     #[test]
@@ -484,4 +3620,63 @@ mod tests {
         assert!(fetch_reminder(reminder2.id).is_err());
         assert!(fetch_reminder(reminder3.id).is_ok());
     }
+
+    #[test]
+    #[serial]
+    fn it_keeps_consistent_counts_when_creating_and_deleting_from_many_threads() {
+        truncate_table!();
+
+        const THREADS: usize = 4;
+        const CREATES_PER_THREAD: usize = 20;
+
+        let kept_ids = std::thread::scope(|scope| {
+            let handles = (0..THREADS)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut kept_ids = vec![];
+
+                        for i in 0..CREATES_PER_THREAD {
+                            let reminder = create_a_reminder!();
+
+                            // every other reminder is deleted right away
+                            if i % 2 == 0 {
+                                delete_reminder(reminder.id).unwrap();
+                            } else {
+                                kept_ids.push(reminder.id);
+                            }
+                        }
+
+                        kept_ids
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect::<Vec<ReminderId>>()
+        });
+
+        let mut fetched_ids = fetch_reminders(None)
+            .unwrap()
+            .iter()
+            .map(|reminder| reminder.id)
+            .collect::<Vec<ReminderId>>();
+        let mut kept_ids = kept_ids;
+        fetched_ids.sort_by_key(|id| id.0);
+        kept_ids.sort_by_key(|id| id.0);
+
+        assert_eq!(THREADS * CREATES_PER_THREAD / 2, fetched_ids.len());
+        assert_eq!(kept_ids, fetched_ids);
+
+        // the deleted ids are reused by later tests, their tombstones would be mistaken for new ones
+        within_database(|conn| {
+            sql_query("DELETE from tombstones where 1=1")
+                .execute(conn)
+                .unwrap();
+
+            Ok(())
+        })
+        .unwrap();
+    }
 }
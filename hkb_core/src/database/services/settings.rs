@@ -0,0 +1,72 @@
+use diesel::{upsert::excluded, ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use log::debug;
+
+use crate::database::{
+    self,
+    models::settings::Setting,
+    schema::settings::{self, dsl as settings_dsl},
+    DatabaseResult,
+};
+
+/// Stores the value of a setting, overwriting the previous one
+pub fn set_setting(key: impl Into<String>, value: impl Into<String>) -> DatabaseResult<()> {
+    database::within_database(|conn| {
+        let setting = Setting {
+            key: key.into(),
+            value: value.into(),
+        };
+
+        debug!(target: "CORE_SETTINGS_SERVICE", "Setting {} to {}", setting.key, setting.value);
+
+        diesel::insert_into(settings::table)
+            .values(&setting)
+            .on_conflict(settings_dsl::key)
+            .do_update()
+            .set(settings_dsl::value.eq(excluded(settings_dsl::value)))
+            .execute(conn)?;
+
+        Ok(())
+    })
+}
+
+pub fn get_setting(key: &str) -> DatabaseResult<Option<String>> {
+    database::within_database(|conn| {
+        let value = settings_dsl::settings
+            .find(key)
+            .select(settings_dsl::value)
+            .first::<String>(conn)
+            .optional()?;
+
+        Ok(value)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use self::database::init_database;
+    use ctor::ctor;
+    use diesel_migrations::{embed_migrations, EmbeddedMigrations};
+    use serial_test::serial;
+    pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("./migrations");
+
+    use super::*;
+
+    #[ctor]
+    fn init() {
+        init_database(":memory:", vec![MIGRATIONS]).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn it_can_set_and_get_a_setting() {
+        assert_eq!(None, get_setting("last_opened_at").unwrap());
+
+        set_setting("last_opened_at", "2024-04-05T08:00:00Z").unwrap();
+        set_setting("last_opened_at", "2024-04-06T08:00:00Z").unwrap();
+
+        assert_eq!(
+            Some("2024-04-06T08:00:00Z".to_owned()),
+            get_setting("last_opened_at").unwrap()
+        );
+    }
+}
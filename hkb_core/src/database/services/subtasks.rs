@@ -0,0 +1,242 @@
+pub use crate::dtos::subtasks::*;
+use diesel::{
+    dsl::{max, not},
+    Connection, ExpressionMethods, QueryDsl, QueryResult, RunQueryDsl, SelectableHelper,
+};
+use log::debug;
+
+use crate::database::{
+    self,
+    models::subtasks::{CreateSubtask, Subtask},
+    schema::subtasks::{self, dsl as subtasks_dsl},
+    DatabaseConnection, DatabaseError, DatabaseResult, DatabaseResultExt,
+};
+use crate::dtos::reminders::ReminderId;
+
+impl From<Subtask> for SubtaskData {
+    fn from(val: Subtask) -> Self {
+        SubtaskData {
+            id: val.id,
+            reminder_id: val.reminder_id.into(),
+            text: val.text,
+            done: val.done,
+            position: val.position,
+        }
+    }
+}
+
+fn load_subtasks(
+    conn: &mut DatabaseConnection,
+    reminder_id: ReminderId,
+) -> QueryResult<Vec<Subtask>> {
+    subtasks_dsl::subtasks
+        .filter(subtasks_dsl::reminder_id.eq(reminder_id.0))
+        .order((subtasks_dsl::position.asc(), subtasks_dsl::id.asc()))
+        .select(Subtask::as_select())
+        .load(conn)
+}
+
+/// Adds the subtask after the existing ones of the reminder
+pub fn add_subtask(
+    reminder_id: ReminderId,
+    text: impl Into<String>,
+) -> DatabaseResult<SubtaskData> {
+    database::within_database(|conn| {
+        let text = text.into();
+
+        debug!(target: "CORE_SUBTASKS_SERVICE", "Adding subtask {text} to reminder {reminder_id}");
+
+        conn.transaction::<_, DatabaseError, _>(|conn| {
+            let last_position: Option<i64> = subtasks_dsl::subtasks
+                .filter(subtasks_dsl::reminder_id.eq(reminder_id.0))
+                .select(max(subtasks_dsl::position))
+                .first(conn)?;
+
+            let subtask = diesel::insert_into(subtasks::table)
+                .values(CreateSubtask {
+                    reminder_id: reminder_id.0,
+                    text,
+                    position: last_position.map_or(0, |position| position + 1),
+                })
+                .returning(Subtask::as_returning())
+                .get_result(conn)?;
+
+            Ok(subtask.into())
+        })
+    })
+    .context("add subtask")
+}
+
+/// Flips the subtask between done and not done
+pub fn toggle_subtask(id: i64) -> DatabaseResult<SubtaskData> {
+    database::within_database(|conn| {
+        debug!(target: "CORE_SUBTASKS_SERVICE", "Toggling subtask {id}");
+
+        let subtask = diesel::update(subtasks_dsl::subtasks.find(id))
+            .set(subtasks_dsl::done.eq(not(subtasks_dsl::done)))
+            .returning(Subtask::as_returning())
+            .get_result(conn)?;
+
+        Ok(subtask.into())
+    })
+    .context("toggle subtask")
+}
+
+/// Moves the subtask to the position, counted from 0, and shifts the rest of the reminder's
+/// subtasks around it. Positions past the last subtask move it to the end.
+/// Returns the subtasks of the reminder in their new order.
+pub fn reorder_subtask(id: i64, position: usize) -> DatabaseResult<Vec<SubtaskData>> {
+    database::within_database(|conn| {
+        debug!(target: "CORE_SUBTASKS_SERVICE", "Moving subtask {id} to position {position}");
+
+        conn.transaction::<_, DatabaseError, _>(|conn| {
+            let reminder_id = subtasks_dsl::subtasks
+                .find(id)
+                .select(subtasks_dsl::reminder_id)
+                .first::<i64>(conn)?;
+            let mut subtasks = load_subtasks(conn, reminder_id.into())?;
+            let current_position = subtasks
+                .iter()
+                .position(|subtask| subtask.id == id)
+                .ok_or(diesel::result::Error::NotFound)?;
+
+            let subtask = subtasks.remove(current_position);
+            subtasks.insert(position.min(subtasks.len()), subtask);
+
+            // positions are rewritten from 0, which also closes gaps left by deleted subtasks
+            for (position, subtask) in subtasks.iter_mut().enumerate() {
+                let position = position as i64;
+
+                if subtask.position != position {
+                    diesel::update(subtasks_dsl::subtasks.find(subtask.id))
+                        .set(subtasks_dsl::position.eq(position))
+                        .execute(conn)?;
+                    subtask.position = position;
+                }
+            }
+
+            Ok(subtasks.into_iter().map(SubtaskData::from).collect())
+        })
+    })
+    .context("reorder subtask")
+}
+
+/// Lists the subtasks of the reminder by their position
+pub fn fetch_subtasks(reminder_id: ReminderId) -> DatabaseResult<Vec<SubtaskData>> {
+    database::within_database(|conn| {
+        let subtasks = load_subtasks(conn, reminder_id)?;
+
+        Ok(subtasks.into_iter().map(SubtaskData::from).collect())
+    })
+    .context("fetch subtasks")
+}
+
+#[cfg(test)]
+mod tests {
+    use self::database::init_database;
+    use crate::database::services::reminders::{
+        create_reminder, delete_reminder, CreateReminderData, MissPolicy,
+    };
+    use ctor::ctor;
+    use diesel_migrations::{embed_migrations, EmbeddedMigrations};
+    use hkb_date::date::SimpleDate;
+    use serial_test::serial;
+    pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("./migrations");
+
+    use super::*;
+
+    #[ctor]
+    fn init() {
+        init_database(":memory:", vec![MIGRATIONS]).unwrap();
+    }
+
+    fn create_a_reminder() -> ReminderId {
+        create_reminder(CreateReminderData {
+            note: "Pack for the trip".to_owned(),
+            body: None,
+            remind_at: SimpleDate::parse_from_str("2024-04-05 08:00:00", "%Y-%m-%d %H:%M:%S")
+                .unwrap(),
+            attachment: None,
+            recurrence: None,
+            miss_policy: MissPolicy::Keep,
+        })
+        .unwrap()
+        .id
+    }
+
+    fn texts(subtasks: &[SubtaskData]) -> Vec<&str> {
+        subtasks
+            .iter()
+            .map(|subtask| subtask.text.as_str())
+            .collect()
+    }
+
+    #[test]
+    #[serial]
+    fn it_adds_subtasks_after_the_existing_ones() {
+        let reminder_id = create_a_reminder();
+        let other_reminder_id = create_a_reminder();
+
+        add_subtask(reminder_id, "Passport").unwrap();
+        add_subtask(other_reminder_id, "Tickets").unwrap();
+        let subtask = add_subtask(reminder_id, "Charger").unwrap();
+
+        assert_eq!(1, subtask.position);
+        assert!(!subtask.done);
+        assert_eq!(
+            vec!["Passport", "Charger"],
+            texts(&fetch_subtasks(reminder_id).unwrap())
+        );
+        assert!(add_subtask(ReminderId(reminder_id.0 + 100), "Socks").is_err());
+
+        delete_reminder(reminder_id).unwrap();
+        delete_reminder(other_reminder_id).unwrap();
+
+        assert!(fetch_subtasks(reminder_id).unwrap().is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn it_toggles_a_subtask() {
+        let reminder_id = create_a_reminder();
+        let subtask = add_subtask(reminder_id, "Passport").unwrap();
+
+        assert!(toggle_subtask(subtask.id).unwrap().done);
+        assert!(fetch_subtasks(reminder_id).unwrap()[0].done);
+        assert!(!toggle_subtask(subtask.id).unwrap().done);
+        assert!(toggle_subtask(subtask.id + 100).is_err());
+
+        delete_reminder(reminder_id).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn it_persists_the_order_of_reordered_subtasks() {
+        let reminder_id = create_a_reminder();
+        let passport = add_subtask(reminder_id, "Passport").unwrap();
+        add_subtask(reminder_id, "Tickets").unwrap();
+        let charger = add_subtask(reminder_id, "Charger").unwrap();
+
+        let reordered = reorder_subtask(charger.id, 0).unwrap();
+
+        assert_eq!(vec!["Charger", "Passport", "Tickets"], texts(&reordered));
+        assert_eq!(
+            vec![0, 1, 2],
+            reordered
+                .iter()
+                .map(|subtask| subtask.position)
+                .collect::<Vec<i64>>()
+        );
+        assert_eq!(reordered, fetch_subtasks(reminder_id).unwrap());
+
+        reorder_subtask(passport.id, 10).unwrap();
+
+        assert_eq!(
+            vec!["Charger", "Tickets", "Passport"],
+            texts(&fetch_subtasks(reminder_id).unwrap())
+        );
+        assert!(reorder_subtask(passport.id + 100, 0).is_err());
+
+        delete_reminder(reminder_id).unwrap();
+    }
+}
@@ -0,0 +1,263 @@
+use diesel::{
+    Connection, ExpressionMethods, OptionalExtension, QueryDsl, QueryResult, RunQueryDsl,
+};
+use log::debug;
+
+use crate::database::{
+    self,
+    schema::{
+        reminder_tags::{self, dsl as reminder_tags_dsl},
+        tags::{self, dsl as tags_dsl},
+    },
+    DatabaseConnection, DatabaseError, DatabaseResult, DatabaseResultExt,
+};
+use crate::dtos::reminders::ReminderId;
+
+fn find_tag(conn: &mut DatabaseConnection, name: &str) -> QueryResult<Option<i64>> {
+    tags_dsl::tags
+        .filter(tags_dsl::name.eq(name))
+        .select(tags_dsl::id)
+        .first(conn)
+        .optional()
+}
+
+fn find_or_create_tag(conn: &mut DatabaseConnection, name: &str) -> QueryResult<i64> {
+    diesel::insert_or_ignore_into(tags::table)
+        .values(tags_dsl::name.eq(name))
+        .execute(conn)?;
+
+    tags_dsl::tags
+        .filter(tags_dsl::name.eq(name))
+        .select(tags_dsl::id)
+        .first(conn)
+}
+
+fn attach_tag(
+    conn: &mut DatabaseConnection,
+    reminder_id: ReminderId,
+    name: &str,
+) -> QueryResult<()> {
+    let tag_id = find_or_create_tag(conn, name)?;
+
+    diesel::insert_or_ignore_into(reminder_tags::table)
+        .values((
+            reminder_tags_dsl::reminder_id.eq(reminder_id.0),
+            reminder_tags_dsl::tag_id.eq(tag_id),
+        ))
+        .execute(conn)?;
+
+    Ok(())
+}
+
+/// Returns whether the reminder had the tag
+fn detach_tag(
+    conn: &mut DatabaseConnection,
+    reminder_id: ReminderId,
+    name: &str,
+) -> QueryResult<bool> {
+    let Some(tag_id) = find_tag(conn, name)? else {
+        return Ok(false);
+    };
+
+    let detached = diesel::delete(
+        reminder_tags_dsl::reminder_tags
+            .filter(reminder_tags_dsl::reminder_id.eq(reminder_id.0))
+            .filter(reminder_tags_dsl::tag_id.eq(tag_id)),
+    )
+    .execute(conn)?;
+
+    Ok(detached > 0)
+}
+
+/// Tags the reminder, creating the tag if it does not exist yet
+pub fn tag_reminder(reminder_id: ReminderId, name: &str) -> DatabaseResult<()> {
+    database::within_database(|conn| {
+        debug!(target: "CORE_TAGS_SERVICE", "Tagging reminder {reminder_id} with {name}");
+
+        attach_tag(conn, reminder_id, name)?;
+
+        Ok(())
+    })
+    .context("tag reminder")
+}
+
+/// Tags every reminder in `ids` at once, creating the tag if it does not exist yet.
+/// Reminders that already have the tag keep it. Returns the amount of reminders newly tagged.
+pub fn tag_reminders(ids: &[ReminderId], name: &str) -> DatabaseResult<usize> {
+    if ids.is_empty() {
+        return Ok(0);
+    }
+
+    database::within_database(|conn| {
+        debug!(target: "CORE_TAGS_SERVICE", "Tagging reminders {ids:?} with {name}");
+
+        conn.transaction::<_, DatabaseError, _>(|conn| {
+            let tag_id = find_or_create_tag(conn, name)?;
+            let reminder_tags: Vec<_> = ids
+                .iter()
+                .map(|reminder_id| {
+                    (
+                        reminder_tags_dsl::reminder_id.eq(reminder_id.0),
+                        reminder_tags_dsl::tag_id.eq(tag_id),
+                    )
+                })
+                .collect();
+
+            Ok(diesel::insert_or_ignore_into(reminder_tags::table)
+                .values(reminder_tags)
+                .execute(conn)?)
+        })
+    })
+    .context("tag reminders")
+}
+
+/// Removes the tag from the reminder. Returns whether the reminder had it.
+pub fn untag_reminder(reminder_id: ReminderId, name: &str) -> DatabaseResult<bool> {
+    database::within_database(|conn| {
+        debug!(target: "CORE_TAGS_SERVICE", "Removing tag {name} from reminder {reminder_id}");
+
+        Ok(detach_tag(conn, reminder_id, name)?)
+    })
+    .context("untag reminder")
+}
+
+/// Moves the reminder from the `from` tag to the `to` tag in one transaction.
+/// The `to` tag is created if it does not exist yet.
+/// A reminder without the `from` tag still gets the `to` tag, as the outcome is the same.
+pub fn retag_reminder(reminder_id: ReminderId, from: &str, to: &str) -> DatabaseResult<()> {
+    database::within_database(|conn| {
+        debug!(target: "CORE_TAGS_SERVICE", "Moving reminder {reminder_id} from tag {from} to {to}");
+
+        conn.transaction::<_, DatabaseError, _>(|conn| {
+            if !detach_tag(conn, reminder_id, from)? {
+                debug!(target: "CORE_TAGS_SERVICE", "Reminder {reminder_id} was not tagged with {from}");
+            }
+
+            attach_tag(conn, reminder_id, to)?;
+
+            Ok(())
+        })
+    })
+    .context("retag reminder")
+}
+
+/// Lists the tags of the reminder, sorted by name
+pub fn list_tags(reminder_id: ReminderId) -> DatabaseResult<Vec<String>> {
+    database::within_database(|conn| {
+        let tags = reminder_tags_dsl::reminder_tags
+            .inner_join(tags::table)
+            .filter(reminder_tags_dsl::reminder_id.eq(reminder_id.0))
+            .select(tags_dsl::name)
+            .order(tags_dsl::name.asc())
+            .load(conn)?;
+
+        Ok(tags)
+    })
+    .context("list tags")
+}
+
+#[cfg(test)]
+mod tests {
+    use self::database::init_database;
+    use crate::database::services::reminders::{
+        create_reminder, delete_reminder, CreateReminderData, MissPolicy,
+    };
+    use ctor::ctor;
+    use diesel_migrations::{embed_migrations, EmbeddedMigrations};
+    use hkb_date::date::SimpleDate;
+    use serial_test::serial;
+    pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("./migrations");
+
+    use super::*;
+
+    #[ctor]
+    fn init() {
+        init_database(":memory:", vec![MIGRATIONS]).unwrap();
+    }
+
+    fn create_a_reminder() -> ReminderId {
+        create_reminder(CreateReminderData {
+            note: "Testing".to_owned(),
+            body: None,
+            remind_at: SimpleDate::parse_from_str("2024-04-05 08:00:00", "%Y-%m-%d %H:%M:%S")
+                .unwrap(),
+            attachment: None,
+            recurrence: None,
+            miss_policy: MissPolicy::Keep,
+        })
+        .unwrap()
+        .id
+    }
+
+    #[test]
+    #[serial]
+    fn it_can_tag_and_untag_a_reminder() {
+        let reminder_id = create_a_reminder();
+
+        tag_reminder(reminder_id, "work").unwrap();
+        tag_reminder(reminder_id, "errands").unwrap();
+        tag_reminder(reminder_id, "work").unwrap();
+
+        assert_eq!(vec!["errands", "work"], list_tags(reminder_id).unwrap());
+        assert!(untag_reminder(reminder_id, "work").unwrap());
+        assert!(!untag_reminder(reminder_id, "work").unwrap());
+        assert_eq!(vec!["errands"], list_tags(reminder_id).unwrap());
+
+        delete_reminder(reminder_id).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn it_tags_many_reminders_at_once() {
+        let reminder_ids = [
+            create_a_reminder(),
+            create_a_reminder(),
+            create_a_reminder(),
+        ];
+        let untouched_reminder_id = create_a_reminder();
+        tag_reminder(reminder_ids[0], "work").unwrap();
+
+        assert_eq!(2, tag_reminders(&reminder_ids, "work").unwrap());
+        assert_eq!(0, tag_reminders(&[], "work").unwrap());
+
+        for reminder_id in reminder_ids {
+            assert_eq!(vec!["work"], list_tags(reminder_id).unwrap());
+            delete_reminder(reminder_id).unwrap();
+        }
+
+        assert!(list_tags(untouched_reminder_id).unwrap().is_empty());
+
+        delete_reminder(untouched_reminder_id).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn it_moves_a_reminder_between_tags() {
+        let reminder_id = create_a_reminder();
+        let other_reminder_id = create_a_reminder();
+        tag_reminder(reminder_id, "inbox").unwrap();
+        tag_reminder(reminder_id, "home").unwrap();
+        tag_reminder(other_reminder_id, "inbox").unwrap();
+
+        retag_reminder(reminder_id, "inbox", "someday").unwrap();
+
+        assert_eq!(vec!["home", "someday"], list_tags(reminder_id).unwrap());
+        assert_eq!(vec!["inbox"], list_tags(other_reminder_id).unwrap());
+
+        delete_reminder(reminder_id).unwrap();
+        delete_reminder(other_reminder_id).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn it_still_tags_a_reminder_without_the_source_tag() {
+        let reminder_id = create_a_reminder();
+
+        retag_reminder(reminder_id, "inbox", "work").unwrap();
+
+        assert_eq!(vec!["work"], list_tags(reminder_id).unwrap());
+        assert!(retag_reminder(ReminderId(reminder_id.0 + 100), "inbox", "work").is_err());
+
+        delete_reminder(reminder_id).unwrap();
+    }
+}
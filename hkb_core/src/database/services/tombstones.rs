@@ -0,0 +1,124 @@
+use diesel::sql_types::Date as SqlDateType;
+use diesel::{ExpressionMethods, IntoSql, QueryDsl, QueryResult, RunQueryDsl};
+use hkb_date::date::SimpleDate;
+use log::debug;
+
+use crate::database::{
+    self,
+    schema::tombstones::{self, dsl as tombstones_dsl},
+    DatabaseConnection, DatabaseResult, DatabaseResultExt,
+};
+use crate::dtos::reminders::ReminderId;
+
+/// Records that the reminders were deleted, so the deletion can be synced to other devices
+pub(crate) fn record_tombstones(
+    conn: &mut DatabaseConnection,
+    reminder_ids: &[i64],
+) -> QueryResult<()> {
+    if reminder_ids.is_empty() {
+        return Ok(());
+    }
+
+    debug!(target: "CORE_TOMBSTONES_SERVICE", "Recording tombstones of reminders {reminder_ids:?}");
+
+    let deleted_at = SimpleDate::local().to_string();
+    let tombstones = reminder_ids
+        .iter()
+        .map(|reminder_id| {
+            (
+                tombstones_dsl::reminder_id.eq(*reminder_id),
+                tombstones_dsl::deleted_at.eq(deleted_at.clone()),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    diesel::insert_into(tombstones::table)
+        .values(&tombstones)
+        .execute(conn)?;
+
+    Ok(())
+}
+
+/// Ids of the reminders deleted after `since`, in the order they were deleted
+pub fn deleted_since(since: SimpleDate) -> DatabaseResult<Vec<ReminderId>> {
+    database::within_database(|conn| {
+        let reminder_ids = tombstones_dsl::tombstones
+            .filter(tombstones_dsl::deleted_at.gt(since.to_string().into_sql::<SqlDateType>()))
+            .order(tombstones_dsl::id.asc())
+            .select(tombstones_dsl::reminder_id)
+            .load::<i64>(conn)?;
+
+        Ok(reminder_ids.into_iter().map(ReminderId).collect())
+    })
+    .context("fetch deleted reminders")
+}
+
+#[cfg(test)]
+mod tests {
+    use self::database::init_database;
+    use crate::database::services::reminders::{
+        create_reminder, delete_reminder, delete_reminders, CreateReminderData, MissPolicy,
+        ReminderQueryOptions,
+    };
+    use ctor::ctor;
+    use diesel_migrations::{embed_migrations, EmbeddedMigrations};
+    use hkb_date::duration::Duration;
+    use serial_test::serial;
+    pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("./migrations");
+
+    use super::*;
+
+    #[ctor]
+    fn init() {
+        init_database(":memory:", vec![MIGRATIONS]).unwrap();
+    }
+
+    fn create_a_reminder(note: &str) -> ReminderId {
+        create_reminder(CreateReminderData {
+            note: note.to_owned(),
+            body: None,
+            remind_at: SimpleDate::parse_from_str("2024-04-05 08:00:00", "%Y-%m-%d %H:%M:%S")
+                .unwrap(),
+            attachment: None,
+            recurrence: None,
+            miss_policy: MissPolicy::Keep,
+        })
+        .unwrap()
+        .id
+    }
+
+    #[test]
+    #[serial]
+    fn it_records_a_tombstone_for_deleted_reminders() {
+        let before_delete = SimpleDate::local()
+            .sub_duration(Duration::Minute(1))
+            .unwrap();
+        let reminder_id = create_a_reminder("Testing");
+        let other_reminder_id = create_a_reminder("Tombstone");
+        let kept_reminder_id = create_a_reminder("Kept");
+
+        // other tests share the database, only the tombstones recorded here are checked
+        let previously_deleted = deleted_since(before_delete).unwrap().len();
+
+        delete_reminder(reminder_id).unwrap();
+        delete_reminders(ReminderQueryOptions::NoteLike { text: "Tombstone" }).unwrap();
+
+        let deleted = deleted_since(before_delete).unwrap();
+
+        assert_eq!(
+            vec![reminder_id, other_reminder_id],
+            deleted[previously_deleted..]
+        );
+        // ids of deleted reminders are reused, so earlier tombstones can have the same id
+        assert!(!deleted[previously_deleted..].contains(&kept_reminder_id));
+        assert!(deleted_since(
+            SimpleDate::local()
+                .add_duration(Duration::Minute(1))
+                .unwrap()
+        )
+        .unwrap()
+        .is_empty());
+
+        delete_reminder(kept_reminder_id).unwrap();
+    }
+}
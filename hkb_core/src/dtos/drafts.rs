@@ -0,0 +1,12 @@
+use hkb_date::date::SimpleDate;
+use serde::{Deserialize, Serialize};
+
+/// An in-progress reminder, kept as typed so it can be restored after a restart.
+/// The date is the raw input and might not be parseable yet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DraftData {
+    pub view: String,
+    pub note: String,
+    pub date: String,
+    pub updated_at: SimpleDate,
+}
@@ -1 +1,3 @@
+pub mod drafts;
 pub mod reminders;
+pub mod subtasks;
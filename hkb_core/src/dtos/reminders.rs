@@ -1,38 +1,602 @@
-use hkb_date::date::SimpleDate;
+use hkb_date::{
+    date::{DateResult, SimpleDate, WeekOfMonth, Weekday},
+    duration::Duration,
+};
 use serde::{Deserialize, Serialize};
+use std::{borrow::Cow, fmt::Display, str::FromStr, time::Duration as StdDuration};
+
+/// Reminders are referenced from outside the app, ex: in scripts or issues, as `hkb://reminder/{id}`
+pub const REMINDER_URI_PREFIX: &str = "hkb://reminder/";
+
+/// Notes longer than this are cut when displayed, so a corrupted note cannot flood the screen
+pub const MAX_DISPLAYED_NOTE_LENGTH: usize = 1000;
+
+/// Id of a reminder, so it cannot be mixed up with the ids of subtasks, tags or attachments
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ReminderId(pub i64);
+
+impl From<i64> for ReminderId {
+    fn from(id: i64) -> Self {
+        ReminderId(id)
+    }
+}
+
+impl From<ReminderId> for i64 {
+    fn from(id: ReminderId) -> Self {
+        id.0
+    }
+}
+
+impl Display for ReminderId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Recurrence {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+    /// Ex: the last Friday of each month
+    MonthlyOnWeekday(WeekOfMonth, Weekday),
+}
+
+impl Recurrence {
+    /// First occurrence after the date, keeping its time of day
+    pub fn next_occurrence(&self, date: SimpleDate) -> DateResult<SimpleDate> {
+        match self {
+            Recurrence::Daily => date.add_duration(Duration::Day(1)),
+            Recurrence::Weekly => date.add_duration(Duration::Week(1)),
+            Recurrence::Monthly => date.add_duration(Duration::Month(1)),
+            Recurrence::Yearly => date.add_duration(Duration::Year(1)),
+            Recurrence::MonthlyOnWeekday(week, weekday) => {
+                let this_month = date.weekday_of_month(*week, *weekday)?;
+
+                if !(this_month - date).is_zero() {
+                    return Ok(this_month);
+                }
+
+                // the first day of the month is moved, so shorter months are not skipped
+                let mut next_month = date;
+                next_month.set_day(1)?;
+
+                next_month
+                    .add_duration(Duration::Month(1))?
+                    .weekday_of_month(*week, *weekday)
+            }
+        }
+    }
+}
+
+impl Display for Recurrence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Recurrence::Daily => "daily",
+            Recurrence::Weekly => "weekly",
+            Recurrence::Monthly => "monthly",
+            Recurrence::Yearly => "yearly",
+            Recurrence::MonthlyOnWeekday(week, weekday) => {
+                let weekday = weekday.to_string().to_lowercase();
+
+                return write!(f, "monthly_on_{week}_{weekday}");
+            }
+        };
+
+        write!(f, "{}", value)
+    }
+}
+
+impl FromStr for Recurrence {
+    type Err = String;
+
+    fn from_str(recurrence: &str) -> Result<Self, Self::Err> {
+        match recurrence {
+            "daily" => Ok(Recurrence::Daily),
+            "weekly" => Ok(Recurrence::Weekly),
+            "monthly" => Ok(Recurrence::Monthly),
+            "yearly" => Ok(Recurrence::Yearly),
+            // Ex: monthly_on_last_fri
+            _ => recurrence
+                .strip_prefix("monthly_on_")
+                .and_then(|rule| rule.split_once('_'))
+                .and_then(|(week, weekday)| Some((week.parse().ok()?, weekday.parse().ok()?)))
+                .map(|(week, weekday)| Recurrence::MonthlyOnWeekday(week, weekday))
+                .ok_or_else(|| format!("Unknown recurrence: {recurrence}")),
+        }
+    }
+}
+
+/// What happens to a reminder that was missed, because the daemon was not running
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MissPolicy {
+    #[default]
+    Keep,
+    SnoozeToNextDay,
+    Drop,
+}
+
+impl Display for MissPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            MissPolicy::Keep => "keep",
+            MissPolicy::SnoozeToNextDay => "snooze_to_next_day",
+            MissPolicy::Drop => "drop",
+        };
+
+        write!(f, "{}", value)
+    }
+}
+
+impl FromStr for MissPolicy {
+    type Err = String;
+
+    fn from_str(miss_policy: &str) -> Result<Self, Self::Err> {
+        match miss_policy {
+            "keep" => Ok(MissPolicy::Keep),
+            "snooze_to_next_day" => Ok(MissPolicy::SnoozeToNextDay),
+            "drop" => Ok(MissPolicy::Drop),
+            _ => Err(format!("Unknown miss policy: {miss_policy}")),
+        }
+    }
+}
+
+/// Label to tell reminders apart at a glance, like the colors of calendar events
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReminderColor {
+    Red,
+    Orange,
+    Yellow,
+    Green,
+    Blue,
+    Purple,
+}
+
+impl Display for ReminderColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            ReminderColor::Red => "red",
+            ReminderColor::Orange => "orange",
+            ReminderColor::Yellow => "yellow",
+            ReminderColor::Green => "green",
+            ReminderColor::Blue => "blue",
+            ReminderColor::Purple => "purple",
+        };
+
+        write!(f, "{}", value)
+    }
+}
+
+impl FromStr for ReminderColor {
+    type Err = String;
+
+    fn from_str(color: &str) -> Result<Self, Self::Err> {
+        match color.trim().to_lowercase().as_str() {
+            "red" => Ok(ReminderColor::Red),
+            "orange" => Ok(ReminderColor::Orange),
+            "yellow" => Ok(ReminderColor::Yellow),
+            "green" => Ok(ReminderColor::Green),
+            "blue" => Ok(ReminderColor::Blue),
+            "purple" => Ok(ReminderColor::Purple),
+            _ => Err(format!("Unknown reminder color: {color}")),
+        }
+    }
+}
+
+/// Smallest unit of time a reminder's `remind_at` is kept with
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReminderPrecision {
+    Minute,
+    #[default]
+    Second,
+}
+
+impl FromStr for ReminderPrecision {
+    type Err = String;
+
+    fn from_str(precision: &str) -> Result<Self, Self::Err> {
+        match precision.trim().to_lowercase().as_str() {
+            "minute" => Ok(ReminderPrecision::Minute),
+            "second" => Ok(ReminderPrecision::Second),
+            _ => Err(format!("Unknown reminder precision: {precision}")),
+        }
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CreateReminderData {
     pub note: String,
+    /// Longer text shown in the detail view, the note is used as the title
+    pub body: Option<String>,
     pub remind_at: SimpleDate,
+    pub attachment: Option<String>,
+    pub recurrence: Option<Recurrence>,
+    pub miss_policy: MissPolicy,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct UpdateReminderData {
-    pub id: i64,
+    pub id: ReminderId,
     pub note: Option<String>,
+    pub body: Option<String>,
     pub remind_at: Option<SimpleDate>,
+    pub attachment: Option<String>,
+    pub recurrence: Option<Recurrence>,
+    pub miss_policy: Option<MissPolicy>,
+}
+
+/// A single field that differs between two reminders. Values are formatted for display.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+impl FieldChange {
+    fn compare<T: PartialEq + ToString>(
+        field: &'static str,
+        old: Option<&T>,
+        new: Option<&T>,
+    ) -> Option<Self> {
+        if old == new {
+            return None;
+        }
+
+        Some(Self {
+            field,
+            old: old.map(|value| value.to_string()),
+            new: new.map(|value| value.to_string()),
+        })
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ReminderData {
-    pub id: i64,
+    pub id: ReminderId,
     pub note: String,
+    pub body: Option<String>,
     pub remind_at: SimpleDate,
     pub created_at: SimpleDate,
+    pub attachment: Option<String>,
+    pub recurrence: Option<Recurrence>,
+    pub completed_at: Option<SimpleDate>,
+    /// Set once the reminder was seen after it became due, ex: by clearing notifications
+    pub acknowledged_at: Option<SimpleDate>,
+    pub miss_policy: MissPolicy,
+    pub pinned: bool,
+    pub category: Option<String>,
+    /// Lasts the whole day, the time of `remind_at` is not meaningful
+    pub all_day: bool,
+    /// A recurring reminder does not recur past this date. Ex: every week until June
+    pub recurrence_until: Option<SimpleDate>,
+    /// How many more times a recurring reminder comes up, counting the current `remind_at`
+    pub recurrence_count: Option<i64>,
+    pub color: Option<ReminderColor>,
+}
+
+impl ReminderData {
+    pub fn is_completed(&self) -> bool {
+        self.completed_at.is_some()
+    }
+
+    /// Copy of the note that is safe to render, the stored note is left as it is.
+    /// Control characters (ex: escape sequences) are replaced, except for new lines,
+    /// and the note is cut after `MAX_DISPLAYED_NOTE_LENGTH` characters.
+    /// Invalid UTF-8 is already replaced when the note is read from the database.
+    pub fn display_note(&self) -> Cow<'_, str> {
+        let is_unsafe = |c: char| c.is_control() && c != '\n';
+        let is_clean = !self.note.contains(is_unsafe)
+            && self.note.chars().nth(MAX_DISPLAYED_NOTE_LENGTH).is_none();
+
+        if is_clean {
+            return Cow::Borrowed(&self.note);
+        }
+
+        let mut note = self
+            .note
+            .chars()
+            .take(MAX_DISPLAYED_NOTE_LENGTH)
+            .map(|c| {
+                if is_unsafe(c) {
+                    char::REPLACEMENT_CHARACTER
+                } else {
+                    c
+                }
+            })
+            .collect::<String>();
+
+        if self.note.chars().nth(MAX_DISPLAYED_NOTE_LENGTH).is_some() {
+            note.push('…');
+        }
+
+        Cow::Owned(note)
+    }
+
+    /// Stable reference to the reminder. Ex: `hkb://reminder/42`
+    pub fn uri(&self) -> String {
+        format!("{REMINDER_URI_PREFIX}{}", self.id)
+    }
+
+    /// When the reminder becomes due, all day reminders are due at the start of their day
+    pub fn due_at(&self) -> SimpleDate {
+        if self.all_day {
+            self.remind_at.start_of_day().unwrap_or(self.remind_at)
+        } else {
+            self.remind_at
+        }
+    }
+
+    /// Lists the fields that changed from `self` to `other`
+    pub fn diff(&self, other: &Self) -> Vec<FieldChange> {
+        [
+            FieldChange::compare("id", Some(&self.id), Some(&other.id)),
+            FieldChange::compare("note", Some(&self.note), Some(&other.note)),
+            FieldChange::compare("body", self.body.as_ref(), other.body.as_ref()),
+            FieldChange::compare("remind_at", Some(&self.remind_at), Some(&other.remind_at)),
+            FieldChange::compare(
+                "created_at",
+                Some(&self.created_at),
+                Some(&other.created_at),
+            ),
+            FieldChange::compare(
+                "attachment",
+                self.attachment.as_ref(),
+                other.attachment.as_ref(),
+            ),
+            FieldChange::compare(
+                "recurrence",
+                self.recurrence.as_ref(),
+                other.recurrence.as_ref(),
+            ),
+            FieldChange::compare(
+                "completed_at",
+                self.completed_at.as_ref(),
+                other.completed_at.as_ref(),
+            ),
+            FieldChange::compare(
+                "acknowledged_at",
+                self.acknowledged_at.as_ref(),
+                other.acknowledged_at.as_ref(),
+            ),
+            FieldChange::compare(
+                "miss_policy",
+                Some(&self.miss_policy),
+                Some(&other.miss_policy),
+            ),
+            FieldChange::compare("pinned", Some(&self.pinned), Some(&other.pinned)),
+            FieldChange::compare("category", self.category.as_ref(), other.category.as_ref()),
+            FieldChange::compare("all_day", Some(&self.all_day), Some(&other.all_day)),
+            FieldChange::compare(
+                "recurrence_until",
+                self.recurrence_until.as_ref(),
+                other.recurrence_until.as_ref(),
+            ),
+            FieldChange::compare(
+                "recurrence_count",
+                self.recurrence_count.as_ref(),
+                other.recurrence_count.as_ref(),
+            ),
+            FieldChange::compare("color", self.color.as_ref(), other.color.as_ref()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Formats the reminder as a markdown task. Ex: `- [ ] Buy milk (due Apr 5 08:00)`
+    pub fn to_markdown(&self) -> String {
+        let checkbox = if self.is_completed() { "x" } else { " " };
+        let due_format = if self.all_day {
+            "%b %-d"
+        } else {
+            "%b %-d %H:%M"
+        };
+
+        format!(
+            "- [{}] {} (due {})",
+            checkbox,
+            self.note,
+            self.remind_at.format(due_format)
+        )
+    }
+}
+
+/// How long the reminder waited from its creation until it was completed.
+/// `None` while it is not completed.
+pub fn time_to_complete(reminder: &ReminderData) -> Option<StdDuration> {
+    reminder
+        .completed_at
+        .map(|completed_at| completed_at - reminder.created_at)
+}
+
+pub fn reminders_to_markdown(reminders: &[ReminderData]) -> String {
+    reminders
+        .iter()
+        .map(|reminder| reminder.to_markdown())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttachmentData {
+    pub id: i64,
+    pub reminder_id: ReminderId,
+    pub path: String,
+    pub created_at: SimpleDate,
 }
 
 pub mod fakes {
     use hkb_date::date::SimpleDate;
 
-    use super::ReminderData;
+    use super::{MissPolicy, ReminderData, ReminderId};
 
     pub fn create_reminder() -> ReminderData {
         ReminderData {
-            id: 1,
+            id: ReminderId(1),
             note: "Testing".to_owned(),
+            body: None,
             remind_at: SimpleDate::local(),
             created_at: SimpleDate::local(),
+            attachment: None,
+            recurrence: None,
+            completed_at: None,
+            acknowledged_at: None,
+            miss_policy: MissPolicy::Keep,
+            pinned: false,
+            category: None,
+            all_day: false,
+            recurrence_until: None,
+            recurrence_count: None,
+            color: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_lists_the_changed_fields_of_a_reminder() {
+        let old_reminder = fakes::create_reminder();
+        let mut new_reminder = old_reminder.clone();
+        new_reminder.note = "Buy milk".to_owned();
+        new_reminder.remind_at =
+            SimpleDate::parse_from_str("2024-04-05 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+
+        let changes = old_reminder.diff(&new_reminder);
+
+        assert_eq!(2, changes.len());
+        assert_eq!(
+            FieldChange {
+                field: "note",
+                old: Some("Testing".to_owned()),
+                new: Some("Buy milk".to_owned()),
+            },
+            changes[0]
+        );
+        assert_eq!("remind_at", changes[1].field);
+        assert_eq!(Some("2024-04-05T08:00:00Z".to_owned()), changes[1].new);
+        assert!(old_reminder.diff(&old_reminder).is_empty());
+    }
+
+    #[test]
+    fn it_converts_reminder_ids_to_and_from_integers() {
+        let id = ReminderId::from(42);
+
+        assert_eq!(ReminderId(42), id);
+        assert_eq!(42, i64::from(id));
+        assert_eq!("42", id.to_string());
+        // serialized like the plain integer, so stored backups keep working
+        assert_eq!("42", serde_json::to_string(&id).unwrap());
+        assert_eq!(id, serde_json::from_str::<ReminderId>("42").unwrap());
+    }
+
+    #[test]
+    fn it_formats_an_incomplete_reminder_as_markdown() {
+        let mut reminder = fakes::create_reminder();
+        reminder.note = "Buy milk".to_owned();
+        reminder.remind_at =
+            SimpleDate::parse_from_str("2024-04-05 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+
+        assert_eq!("- [ ] Buy milk (due Apr 5 08:00)", reminder.to_markdown());
+    }
+
+    #[test]
+    fn it_formats_an_all_day_reminder_without_a_time() {
+        let mut reminder = fakes::create_reminder();
+        reminder.note = "Mom's birthday".to_owned();
+        reminder.remind_at =
+            SimpleDate::parse_from_str("2024-04-05 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        reminder.all_day = true;
+
+        assert_eq!("- [ ] Mom's birthday (due Apr 5)", reminder.to_markdown());
+        assert_eq!(
+            SimpleDate::parse_from_str("2024-04-05 00:00:00", "%Y-%m-%d %H:%M:%S").unwrap(),
+            reminder.due_at()
+        );
+    }
+
+    #[test]
+    fn it_formats_a_completed_reminder_as_markdown() {
+        let mut reminder = fakes::create_reminder();
+        reminder.note = "Call the bank".to_owned();
+        reminder.remind_at =
+            SimpleDate::parse_from_str("2024-04-15 17:30:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        reminder.completed_at = Some(reminder.remind_at);
+
+        assert_eq!(
+            "- [x] Call the bank (due Apr 15 17:30)",
+            reminder.to_markdown()
+        );
+    }
+
+    #[test]
+    fn it_formats_multiple_reminders_as_markdown_lines() {
+        let mut first_reminder = fakes::create_reminder();
+        first_reminder.remind_at =
+            SimpleDate::parse_from_str("2024-04-05 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let mut second_reminder = first_reminder.clone();
+        second_reminder.completed_at = Some(second_reminder.remind_at);
+
+        assert_eq!(
+            "- [ ] Testing (due Apr 5 08:00)\n- [x] Testing (due Apr 5 08:00)",
+            reminders_to_markdown(&[first_reminder, second_reminder])
+        );
+    }
+
+    #[test]
+    fn it_recurs_on_the_last_friday_of_each_month() {
+        let parse = |date| SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap();
+        let recurrence = Recurrence::MonthlyOnWeekday(WeekOfMonth::Last, Weekday::Fri);
+        let mut remind_at = parse("2024-01-10 09:00:00");
+        let mut occurrences = vec![];
+
+        for _ in 0..6 {
+            remind_at = recurrence.next_occurrence(remind_at).unwrap();
+            occurrences.push(remind_at);
+        }
+
+        assert_eq!(
+            vec![
+                parse("2024-01-26 09:00:00"),
+                parse("2024-02-23 09:00:00"),
+                parse("2024-03-29 09:00:00"),
+                parse("2024-04-26 09:00:00"),
+                parse("2024-05-31 09:00:00"),
+                parse("2024-06-28 09:00:00"),
+            ],
+            occurrences
+        );
+        assert_eq!("monthly_on_last_fri", recurrence.to_string());
+        assert_eq!(Ok(recurrence), "monthly_on_last_fri".parse());
+        assert!("monthly_on_fifth_fri".parse::<Recurrence>().is_err());
+    }
+
+    #[test]
+    fn it_formats_the_uri_of_a_reminder() {
+        let mut reminder = fakes::create_reminder();
+        reminder.id = ReminderId(42);
+
+        assert_eq!("hkb://reminder/42", reminder.uri());
+    }
+
+    #[test]
+    fn it_sanitizes_a_pathological_note_for_display() {
+        let mut reminder = fakes::create_reminder();
+        reminder.note = "Pay rent\nby Friday".to_owned();
+
+        assert!(matches!(reminder.display_note(), Cow::Borrowed(_)));
+
+        reminder.note = format!("\x1b[2J\u{fffd}{}", "a".repeat(100_000));
+        let display_note = reminder.display_note();
+
+        assert_eq!(MAX_DISPLAYED_NOTE_LENGTH + 1, display_note.chars().count());
+        assert!(display_note.starts_with("\u{fffd}[2J\u{fffd}aaa"));
+        assert!(display_note.ends_with("a…"));
+        assert_eq!(100_005, reminder.note.chars().count());
+    }
+}
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+use super::reminders::ReminderId;
+
+/// A checklist item of a reminder, listed by its position
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubtaskData {
+    pub id: i64,
+    pub reminder_id: ReminderId,
+    pub text: String,
+    pub done: bool,
+    pub position: i64,
+}
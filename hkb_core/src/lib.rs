@@ -3,6 +3,7 @@ pub mod data_structures;
 pub mod decoders;
 pub mod dtos;
 pub mod logger;
+pub mod paths;
 
 #[cfg(any(feature = "sqlite-database", feature = "mysql-database",))]
 pub mod database;
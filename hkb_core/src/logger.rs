@@ -1,6 +1,7 @@
 use std::collections::HashSet;
 use std::fmt::Display;
 use std::str::FromStr;
+use std::sync::Once;
 
 use log::LevelFilter;
 pub use log::{debug, error, info, log, trace, warn};
@@ -9,9 +10,13 @@ use log4rs::append::console::ConsoleAppender;
 use log4rs::append::file::FileAppender;
 use log4rs::append::Append;
 use log4rs::config::{Appender, Root};
+use log4rs::encode::json::JsonEncoder;
 use log4rs::encode::pattern::PatternEncoder;
+use log4rs::encode::Encode;
 use log4rs::Config;
 
+static LOGGER_INIT: Once = Once::new();
+
 #[derive(PartialEq, Eq, Hash)]
 pub enum AppenderType {
     FILE,
@@ -30,11 +35,37 @@ impl Display for AppenderType {
     }
 }
 
-fn init_file_appender(pattern: &str) -> FileAppender {
+#[derive(Default, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum LogFormat {
+    #[default]
+    Plain,
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format.to_lowercase().as_str() {
+            "plain" => Ok(LogFormat::Plain),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(format!("Unknown log format: {format}")),
+        }
+    }
+}
+
+fn create_encoder(format: LogFormat, pattern: &str) -> Box<dyn Encode> {
+    match format {
+        LogFormat::Plain => Box::new(PatternEncoder::new(pattern)),
+        LogFormat::Json => Box::new(JsonEncoder::new()),
+    }
+}
+
+fn init_file_appender(encoder: Box<dyn Encode>) -> FileAppender {
     #[cfg(debug_assertions)]
     {
         FileAppender::builder()
-            .encoder(Box::new(PatternEncoder::new(pattern)))
+            .encoder(encoder)
             .build("__log/main.log")
             .unwrap()
     }
@@ -44,19 +75,27 @@ fn init_file_appender(pattern: &str) -> FileAppender {
         let cache_dir = dirs::cache_dir().unwrap();
         // TODO: support rotation logs
         FileAppender::builder()
-            .encoder(Box::new(PatternEncoder::new(pattern)))
+            .encoder(encoder)
             .build(cache_dir.join("hkb/main.log"))
             .unwrap()
     }
 }
 
-fn init_stdout_appender(pattern: &str) -> ConsoleAppender {
-    ConsoleAppender::builder()
-        .encoder(Box::new(PatternEncoder::new(pattern)))
-        .build()
+fn init_stdout_appender(encoder: Box<dyn Encode>) -> ConsoleAppender {
+    ConsoleAppender::builder().encoder(encoder).build()
 }
 
+/// Initializes the global logger.
+/// Only the first call installs a logger, any following calls are no-ops.
 pub fn init(appenders: Option<Vec<AppenderType>>) {
+    LOGGER_INIT.call_once(|| init_logger(appenders));
+}
+
+pub fn is_initialized() -> bool {
+    LOGGER_INIT.is_completed()
+}
+
+fn init_logger(appenders: Option<Vec<AppenderType>>) {
     let appenders = appenders
         .unwrap_or_else(|| vec![AppenderType::FILE])
         .into_iter()
@@ -88,13 +127,20 @@ pub fn init(appenders: Option<Vec<AppenderType>>) {
         Ok(level) => LevelFilter::from_str(&level).unwrap_or(default_level),
         Err(_) => default_level,
     };
+    // Json logs contain the timestamp, level, target and message of each record
+    // so they can be parsed by other tools
+    let log_format: LogFormat = match std::env::var("HKB_LOG_FORMAT") {
+        Ok(format) => LogFormat::from_str(&format).unwrap_or_default(),
+        Err(_) => LogFormat::default(),
+    };
     let mut config = Config::builder();
     let mut root = Root::builder();
 
     for appender in appenders {
+        let encoder = create_encoder(log_format, log_line_pattern);
         let appender_to_build: Box<dyn Append> = match appender {
-            AppenderType::FILE => Box::new(init_file_appender(log_line_pattern)),
-            AppenderType::STDOUT => Box::new(init_stdout_appender(log_line_pattern)),
+            AppenderType::FILE => Box::new(init_file_appender(encoder)),
+            AppenderType::STDOUT => Box::new(init_stdout_appender(encoder)),
         };
 
         config =
@@ -106,3 +152,68 @@ pub fn init(appenders: Option<Vec<AppenderType>>) {
 
     info!("Logger Initialized");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+
+    struct BufferWriter(Vec<u8>);
+
+    impl io::Write for BufferWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl log4rs::encode::Write for BufferWriter {}
+
+    #[test]
+    fn it_can_be_initialized_multiple_times() {
+        init(Some(vec![AppenderType::STDOUT]));
+        init(Some(vec![AppenderType::STDOUT]));
+
+        assert!(is_initialized());
+        assert!(log4rs::init_config(
+            Config::builder()
+                .build(Root::builder().build(LevelFilter::Debug))
+                .unwrap()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn it_can_encode_log_lines_as_json() {
+        let mut writer = BufferWriter(Vec::new());
+        let encoder = create_encoder(LogFormat::Json, "{m}{n}");
+
+        encoder
+            .encode(
+                &mut writer,
+                &log::Record::builder()
+                    .level(log::Level::Info)
+                    .target("CORE_REMINDERS_SERVICE")
+                    .args(format_args!("Creating a reminder"))
+                    .build(),
+            )
+            .unwrap();
+
+        let line: serde_json::Value = serde_json::from_slice(&writer.0).unwrap();
+
+        assert!(line["time"].is_string());
+        assert_eq!("INFO", line["level"]);
+        assert_eq!("CORE_REMINDERS_SERVICE", line["target"]);
+        assert_eq!("Creating a reminder", line["message"]);
+    }
+
+    #[test]
+    fn it_parses_log_format_from_string() {
+        assert_eq!(LogFormat::Json, LogFormat::from_str("json").unwrap());
+        assert_eq!(LogFormat::Plain, LogFormat::from_str("Plain").unwrap());
+        assert!(LogFormat::from_str("xml").is_err());
+    }
+}
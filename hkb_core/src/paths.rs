@@ -0,0 +1,103 @@
+use parking_lot::Mutex;
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+/// Environment variable overriding the base directory of the app data
+pub const DATA_DIR_ENV: &str = "HKB_DATA_DIR";
+/// Command line flag overriding the base directory of the app data, ex: `--data-dir /tmp/hkb`
+pub const DATA_DIR_FLAG: &str = "--data-dir";
+
+static DATA_DIR_OVERRIDE: Mutex<Option<PathBuf>> = parking_lot::const_mutex(None);
+
+fn resolve_data_dir(overridden: Option<PathBuf>, env_value: Option<OsString>) -> PathBuf {
+    overridden
+        .or_else(|| {
+            env_value
+                .filter(|value| !value.is_empty())
+                .map(PathBuf::from)
+        })
+        .unwrap_or_else(|| dirs::data_local_dir().unwrap().join("hkb"))
+}
+
+/// Overrides the data directory, taking precedence over `HKB_DATA_DIR`.
+/// Has to be set before the database is initialized.
+pub fn set_data_dir(path: impl Into<PathBuf>) {
+    *DATA_DIR_OVERRIDE.lock() = Some(path.into());
+}
+
+/// Base directory of the app data, the platform data directory unless overridden
+pub fn data_dir() -> PathBuf {
+    resolve_data_dir(
+        DATA_DIR_OVERRIDE.lock().clone(),
+        std::env::var_os(DATA_DIR_ENV),
+    )
+}
+
+pub fn database_path() -> PathBuf {
+    data_dir().join("db")
+}
+
+/// Reads the value of the data dir flag from the command line arguments
+pub fn data_dir_from_args(args: impl IntoIterator<Item = String>) -> Option<PathBuf> {
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        if arg == DATA_DIR_FLAG {
+            return args.next().map(PathBuf::from);
+        }
+
+        if let Some(path) = arg.strip_prefix(&format!("{DATA_DIR_FLAG}=")) {
+            return Some(PathBuf::from(path));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn it_resolves_the_data_dir_from_the_env_var() {
+        let default_dir = dirs::data_local_dir().unwrap().join("hkb");
+
+        std::env::remove_var(DATA_DIR_ENV);
+        assert_eq!(default_dir, data_dir());
+
+        std::env::set_var(DATA_DIR_ENV, "/tmp/hkb-portable");
+        assert_eq!(PathBuf::from("/tmp/hkb-portable/db"), database_path());
+
+        std::env::remove_var(DATA_DIR_ENV);
+    }
+
+    #[test]
+    fn it_prefers_the_flag_over_the_env_var() {
+        assert_eq!(
+            PathBuf::from("/tmp/flag"),
+            resolve_data_dir(Some("/tmp/flag".into()), Some("/tmp/env".into()))
+        );
+        assert_eq!(
+            PathBuf::from("/tmp/env"),
+            resolve_data_dir(None, Some("/tmp/env".into()))
+        );
+        assert_eq!(
+            Some(PathBuf::from("/tmp/hkb")),
+            data_dir_from_args([
+                "hkb".to_owned(),
+                "--data-dir".to_owned(),
+                "/tmp/hkb".to_owned()
+            ])
+        );
+        assert_eq!(
+            Some(PathBuf::from("/tmp/hkb")),
+            data_dir_from_args(["hkb".to_owned(), "--data-dir=/tmp/hkb".to_owned()])
+        );
+        assert_eq!(
+            None,
+            data_dir_from_args(["hkb".to_owned(), "import".to_owned()])
+        );
+    }
+}
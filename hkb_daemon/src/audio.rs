@@ -84,7 +84,7 @@ async fn run_audio(path: &str) -> Result<(), AudioError> {
 }
 
 pub async fn handle(mut rx: mpsc::Receiver<String>) {
-    let sound_directory = dirs::data_local_dir().unwrap().join("hkb/sound");
+    let sound_directory = hkb_core::paths::data_dir().join("sound");
 
     while let Some(path) = rx.recv().await {
         let mut file_path = std::path::PathBuf::from(&path);
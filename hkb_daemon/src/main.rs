@@ -1,16 +1,23 @@
 use std::collections::HashMap;
 
 use diesel_migrations::{embed_migrations, EmbeddedMigrations};
-use hkb_core::database::init_database;
 use hkb_core::database::services::reminders::*;
-use hkb_core::logger::{self, debug, error, info, AppenderType};
+use hkb_core::database::{init_database_with_tuning, DatabaseTuning};
+use hkb_core::logger::{self, debug, error, info, warn, AppenderType};
+use hkb_core::paths;
 use hkb_daemon_core::client::{Client, ClientError};
+use hkb_daemon_core::frame::Event as FrameEvent;
+use hkb_daemon_core::query::{answer_query, ReminderQuery};
 use hkb_daemon_core::server::Server;
+use hkb_date::clock::SteadyClock;
 use hkb_date::date::SimpleDate;
 use notify_rust::{Notification, Timeout};
+use quiet_hours::{should_defer, QuietHours};
 use tokio::net::UnixStream;
+use tokio::sync::broadcast;
 
 mod audio;
+mod quiet_hours;
 
 const INTERVALS: [(
     hkb_date::duration::Duration,
@@ -38,14 +45,43 @@ const INTERVALS: [(
         "30 minutes",
     ),
 ];
+// the window of reminders that are about to be due, clients only hear about those as fired
+const DUE_INTERVAL_INDEX: usize = 0;
 const CORE_MIGRATIONS: EmbeddedMigrations = embed_migrations!("../hkb_core/migrations");
+// fired reminders a slow client can fall behind on, older ones are dropped for it
+const FIRED_EVENTS_CAPACITY: usize = 16;
 
-async fn process_connection(stream: UnixStream) {
+fn fetch_queried_reminders(query: &ReminderQuery) -> Vec<ReminderData> {
+    let mut options = vec![];
+
+    if let Some(text) = query.note.as_deref() {
+        options.push(ReminderQueryOptions::NoteLike { text });
+    }
+
+    if let Some((start_date, end_date)) = query.remind_at_between {
+        options.push(ReminderQueryOptions::RemindAtBetween {
+            start_date,
+            end_date,
+        });
+    }
+
+    fetch_reminders(Some(options)).unwrap_or_else(|e| {
+        error!(target: "DAEMON", "Failed to answer a query: {e}");
+
+        vec![]
+    })
+}
+
+async fn process_connection(stream: UnixStream, mut fired: broadcast::Receiver<FrameEvent>) {
     let mut client = Client::from_stream(stream);
     let mut alternate_interval = tokio::time::interval(std::time::Duration::from_millis(500));
 
     loop {
         tokio::select! {
+            Ok(event) = fired.recv() => {
+                client.queue_event(event);
+            }
+
             _ = alternate_interval.tick() => {
                 if let Err(ClientError::ConnectionClosed(e)) = client.flush().await {
                     debug!(target: "DAEMON", "Client disconnected: {e:?}");
@@ -57,6 +93,10 @@ async fn process_connection(stream: UnixStream) {
                 match result {
                     Ok(event) => {
                         debug!(target: "DAEMON", "Received an event: {event:?}");
+
+                        if let Some(result) = answer_query(&event, fetch_queried_reminders) {
+                            client.queue_event(result);
+                        }
                     }
                     Err(ClientError::ConnectionClosed(e)) => {
                         debug!(target: "DAEMON", "Client disconnected: {e:?}");
@@ -69,14 +109,48 @@ async fn process_connection(stream: UnixStream) {
     }
 }
 
-async fn handle_reminding(already_reminded: &mut HashMap<String, Vec<i64>>) {
+/// A notification held back until the quiet hours end
+struct DeferredNotification {
+    notify_at: SimpleDate,
+    reminder_id: ReminderId,
+    note: String,
+}
+
+fn notify(summary: &str, note: &str) {
+    Notification::new()
+        .summary(summary)
+        .body(note)
+        .auto_icon()
+        .timeout(Timeout::Milliseconds(3000))
+        .show()
+        .unwrap();
+}
+
+async fn handle_reminding(
+    now: SimpleDate,
+    already_reminded: &mut HashMap<String, Vec<ReminderId>>,
+    quiet_hours: Option<&QuietHours>,
+    deferred: &mut Vec<DeferredNotification>,
+    fired: &broadcast::Sender<FrameEvent>,
+) {
     debug!(target: "DAEMON", "Checking reminders to notify!");
 
     let mut has_reminded = false;
 
-    for (start, end, humanized_timeframe) in INTERVALS.iter() {
-        let start_date = SimpleDate::local().add_duration(start).unwrap();
-        let end_date = SimpleDate::local().add_duration(end).unwrap();
+    deferred.retain(|notification| {
+        if !(notification.notify_at - now).is_zero() {
+            return true;
+        }
+
+        notify("You had a reminder during quiet hours", &notification.note);
+        has_reminded = true;
+
+        false
+    });
+
+    for (index, (start, end, humanized_timeframe)) in INTERVALS.iter().enumerate() {
+        let start_date = now.add_duration(start).unwrap();
+        let end_date = now.add_duration(end).unwrap();
         let reminded = already_reminded
             .entry(end.to_string())
             .or_insert_with(|| Vec::with_capacity(16));
@@ -91,22 +165,51 @@ async fn handle_reminding(already_reminded: &mut HashMap<String, Vec<i64>>) {
 
         debug!(target: "DAEMON", "Found {} reminders to notify!", reminders.len());
 
-        if !has_reminded {
-            has_reminded = !reminders.is_empty();
-        }
-
         for reminder in reminders {
-            debug!(target: "DAEMON", "Reminder at: {} - current time: {}", reminder.remind_at.to_string(), SimpleDate::local().to_string());
-
-            Notification::new()
-                .summary(format!("You have a reminder in: {}", humanized_timeframe).as_str())
-                .body(reminder.note.as_str())
-                .auto_icon()
-                .timeout(Timeout::Milliseconds(3000))
-                .show()
-                .unwrap();
+            debug!(target: "DAEMON", "Reminder at: {} - current time: {}", reminder.remind_at.to_string(), now.to_string());
 
             reminded.push(reminder.id);
+
+            if let Some(notify_at) =
+                quiet_hours.and_then(|quiet| should_defer(reminder.due_at(), now, quiet))
+            {
+                if !deferred.iter().any(|n| n.reminder_id == reminder.id) {
+                    debug!(target: "DAEMON", "Reminder {} is within quiet hours, deferring it to {}", reminder.id, notify_at);
+
+                    deferred.push(DeferredNotification {
+                        notify_at,
+                        reminder_id: reminder.id,
+                        note: reminder.note,
+                    });
+                }
+
+                continue;
+            }
+
+            notify(
+                format!("You have a reminder in: {}", humanized_timeframe).as_str(),
+                reminder.note.as_str(),
+            );
+            has_reminded = true;
+
+            // the other windows only notify ahead of time, the reminder has not fired yet
+            if index == DUE_INTERVAL_INDEX {
+                // sending only fails when no client is connected
+                let _ = fired.send(FrameEvent::ReminderFired(reminder));
+            }
+        }
+    }
+
+    // recurring reminders that went by move on to their next occurrence,
+    // which has to be notified again
+    match catch_up_due_recurrences(now) {
+        Ok(caught_up) => {
+            for reminded in already_reminded.values_mut() {
+                reminded.retain(|id| !caught_up.contains(id));
+            }
+        }
+        Err(e) => {
+            error!(target: "DAEMON", "Failed to catch up recurring reminders! {}", e.to_string());
         }
     }
 
@@ -116,14 +219,18 @@ async fn handle_reminding(already_reminded: &mut HashMap<String, Vec<i64>>) {
     }
 }
 
-async fn handle_cleaning_reminders() {
+async fn handle_cleaning_reminders(now: SimpleDate) {
     debug!(target: "DAEMON", "Checking if we should cleanup old reminders.");
 
-    let result = delete_reminders(ReminderQueryOptions::RemindAtLe {
-        date: SimpleDate::local()
-            .sub_duration(hkb_date::duration::Duration::Day(1))
+    // recurring reminders are never cleaned up, they are caught up to their next occurrence
+    if let Err(e) = catch_up_due_recurrences(now) {
+        error!(target: "DAEMON", "Failed to catch up recurring reminders! {}", e.to_string());
+    }
+
+    let result = delete_past_one_off_reminders(
+        now.sub_duration(hkb_date::duration::Duration::Day(1))
             .unwrap(),
-    });
+    );
 
     match result {
         Ok(_) => {
@@ -135,19 +242,41 @@ async fn handle_cleaning_reminders() {
     }
 }
 
-async fn handle_reminders() {
-    let mut already_reminded: HashMap<String, Vec<i64>> = HashMap::new();
+// the system clock can be moved backwards (ex: by NTP),
+// we keep using the last time we saw, so reminders are not notified twice
+fn read_clock(clock: &mut SteadyClock) -> SimpleDate {
+    let reading = clock.read();
+
+    if let Some(jump) = reading.jumped_back_by {
+        warn!(target: "DAEMON", "System clock went backwards by {}s, holding the time at {}", jump.as_secs(), reading.now);
+    }
+
+    reading.now
+}
+
+async fn handle_reminders(fired: broadcast::Sender<FrameEvent>) {
+    let mut already_reminded: HashMap<String, Vec<ReminderId>> = HashMap::new();
     let mut cleanup_reminders_interval =
         tokio::time::interval(tokio::time::Duration::from_secs(60 * 5));
     let mut reminder_interval = tokio::time::interval(tokio::time::Duration::from_secs(10));
+    let mut clock = SteadyClock::default();
+    let quiet_hours = QuietHours::from_env();
+    let mut deferred = Vec::new();
 
     loop {
         tokio::select! {
             _ = reminder_interval.tick() => {
-                handle_reminding(&mut already_reminded).await;
+                handle_reminding(
+                    read_clock(&mut clock),
+                    &mut already_reminded,
+                    quiet_hours.as_ref(),
+                    &mut deferred,
+                    &fired,
+                )
+                .await;
             }
             _ = cleanup_reminders_interval.tick() => {
-                handle_cleaning_reminders().await;
+                handle_cleaning_reminders(read_clock(&mut clock)).await;
             }
         }
     }
@@ -155,23 +284,43 @@ async fn handle_reminders() {
 
 #[tokio::main]
 async fn main() {
-    let database_file_path = dirs::data_local_dir().unwrap().join("hkb/db");
-    init_database(database_file_path.to_str().unwrap(), vec![CORE_MIGRATIONS]).unwrap();
+    if let Some(data_dir) = paths::data_dir_from_args(std::env::args()) {
+        paths::set_data_dir(data_dir);
+    }
+
+    std::fs::create_dir_all(paths::data_dir()).unwrap();
+
+    let database_file_path = paths::database_path();
+    init_database_with_tuning(
+        database_file_path.to_str().unwrap(),
+        vec![CORE_MIGRATIONS],
+        DatabaseTuning::from_env(),
+    )
+    .unwrap();
 
     logger::init(Some(vec![AppenderType::FILE, AppenderType::STDOUT]));
 
+    // apply the miss policy of reminders we missed while the daemon was not running
+    if let Err(e) = reconcile_missed(SimpleDate::local()) {
+        error!(target: "DAEMON", "Failed to reconcile missed reminders! {e}");
+    }
+
     let server = Server::bind();
 
     info!("Listening: {}", server.get_addr().to_str().unwrap());
 
     tokio::spawn(async move { audio::init().await });
-    tokio::spawn(async move { handle_reminders().await });
+    let (fired, _) = broadcast::channel(FIRED_EVENTS_CAPACITY);
+    let reminders_fired = fired.clone();
+    tokio::spawn(async move { handle_reminders(reminders_fired).await });
 
     loop {
         match server.accept().await {
             Ok((socket, _)) => {
+                let fired = fired.subscribe();
+
                 tokio::spawn(async {
-                    process_connection(socket).await;
+                    process_connection(socket, fired).await;
                 });
             }
             Err(_) => error!("Failed to accept a connection ;("),
@@ -0,0 +1,139 @@
+use std::str::FromStr;
+
+use hkb_date::date::SimpleDate;
+use hkb_date::duration::Duration;
+
+const MINUTES_PER_HOUR: u32 = 60;
+
+/// Hours in which no notifications are shown. The range can wrap around midnight. Ex: 22:00-07:00
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuietHours {
+    /// Hour and minute the quiet hours start at
+    pub start: (u32, u32),
+    /// Hour and minute the quiet hours end at
+    pub end: (u32, u32),
+}
+
+impl QuietHours {
+    /// Reads the quiet hours from `HKB_QUIET_HOURS`. Ex: HKB_QUIET_HOURS=22:00-07:00
+    pub fn from_env() -> Option<Self> {
+        std::env::var("HKB_QUIET_HOURS")
+            .ok()
+            .and_then(|quiet_hours| quiet_hours.parse().ok())
+    }
+
+    fn contains(&self, date: &SimpleDate) -> bool {
+        let minute_of_day = date.hour() * MINUTES_PER_HOUR + date.minute();
+        let start = self.start.0 * MINUTES_PER_HOUR + self.start.1;
+        let end = self.end.0 * MINUTES_PER_HOUR + self.end.1;
+
+        if start <= end {
+            minute_of_day >= start && minute_of_day < end
+        } else {
+            minute_of_day >= start || minute_of_day < end
+        }
+    }
+
+    /// End of the quiet hours that contain the date
+    fn end_after(&self, date: SimpleDate) -> Option<SimpleDate> {
+        let mut end = date;
+        end.set_hms(self.end.0, self.end.1, 0).ok()?;
+
+        // the end is on the next day, when the quiet hours wrap around midnight
+        if (end - date).is_zero() {
+            end = end.add_duration(Duration::Day(1)).ok()?;
+        }
+
+        Some(end)
+    }
+}
+
+fn parse_time(time: &str) -> Result<(u32, u32), String> {
+    let (hour, minute) = time
+        .trim()
+        .split_once(':')
+        .ok_or_else(|| format!("Invalid time: {time}"))?;
+    let hour = hour.parse::<u32>().map_err(|e| e.to_string())?;
+    let minute = minute.parse::<u32>().map_err(|e| e.to_string())?;
+
+    if hour > 23 || minute > 59 {
+        return Err(format!("Invalid time: {time}"));
+    }
+
+    Ok((hour, minute))
+}
+
+impl FromStr for QuietHours {
+    type Err = String;
+
+    fn from_str(quiet_hours: &str) -> Result<Self, Self::Err> {
+        let (start, end) = quiet_hours
+            .split_once('-')
+            .ok_or_else(|| format!("Invalid quiet hours: {quiet_hours}"))?;
+
+        Ok(Self {
+            start: parse_time(start)?,
+            end: parse_time(end)?,
+        })
+    }
+}
+
+/// Returns when to notify about a reminder due at `remind_at`, if that is within quiet hours.
+/// The notification is deferred to the end of the quiet hours, but never before `now`.
+pub fn should_defer(
+    remind_at: SimpleDate,
+    now: SimpleDate,
+    quiet: &QuietHours,
+) -> Option<SimpleDate> {
+    if !quiet.contains(&remind_at) {
+        return None;
+    }
+
+    let deferred_at = quiet.end_after(remind_at)?;
+
+    if (deferred_at - now).is_zero() {
+        Some(now)
+    } else {
+        Some(deferred_at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(date: &str) -> SimpleDate {
+        SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn it_defers_reminders_inside_quiet_hours() {
+        let quiet: QuietHours = "22:00-07:00".parse().unwrap();
+        let now = date("2024-04-05 21:50:00");
+
+        assert_eq!(
+            Some(date("2024-04-06 07:00:00")),
+            should_defer(date("2024-04-05 23:30:00"), now, &quiet)
+        );
+        assert_eq!(
+            Some(date("2024-04-06 07:00:00")),
+            should_defer(date("2024-04-06 06:59:00"), now, &quiet)
+        );
+    }
+
+    #[test]
+    fn it_does_not_defer_reminders_outside_quiet_hours() {
+        let quiet: QuietHours = "22:00-07:00".parse().unwrap();
+        let now = date("2024-04-05 12:00:00");
+
+        assert_eq!(None, should_defer(date("2024-04-05 21:59:00"), now, &quiet));
+        assert_eq!(None, should_defer(date("2024-04-06 07:00:00"), now, &quiet));
+    }
+
+    #[test]
+    fn it_rejects_invalid_quiet_hours() {
+        assert!("22:00".parse::<QuietHours>().is_err());
+        assert!("25:00-07:00".parse::<QuietHours>().is_err());
+        assert!("22:00-seven".parse::<QuietHours>().is_err());
+    }
+}
@@ -3,6 +3,8 @@ use std::mem::size_of;
 use hkb_core::dtos::reminders::ReminderData;
 use serde::{Deserialize, Serialize};
 
+use crate::query::ReminderQuery;
+
 pub type FrameSequence = Vec<Frame>;
 
 pub const FRAME_SIZE: usize = 16384;
@@ -13,6 +15,17 @@ const FRAME_DATA_SIZE: usize = FRAME_SIZE - FRAME_METADATA_SIZE;
 pub enum Event {
     ReminderDeleted(i64),
     ReminderCreated(ReminderData),
+    /// Asks the daemon for reminders, answered with a `QueryResult` with the same id
+    Query {
+        id: u64,
+        query: ReminderQuery,
+    },
+    QueryResult {
+        id: u64,
+        reminders: Vec<ReminderData>,
+    },
+    /// Sent to the connected clients when the daemon notifies about a reminder
+    ReminderFired(ReminderData),
 }
 
 impl AsRef<Event> for Event {
@@ -0,0 +1,74 @@
+use hkb_core::dtos::reminders::ReminderData;
+use hkb_date::date::SimpleDate;
+use serde::{Deserialize, Serialize};
+
+use crate::frame::Event;
+
+/// Filters of a reminders query sent to the daemon, all of them are optional
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReminderQuery {
+    pub note: Option<String>,
+    pub remind_at_between: Option<(SimpleDate, SimpleDate)>,
+}
+
+/// Answers a query event with the reminders returned by the fetcher.
+/// The result carries the id of the query, so the requester can match them.
+/// Other events are not answered.
+pub fn answer_query<F>(event: &Event, fetch: F) -> Option<Event>
+where
+    F: FnOnce(&ReminderQuery) -> Vec<ReminderData>,
+{
+    match event {
+        Event::Query { id, query } => Some(Event::QueryResult {
+            id: *id,
+            reminders: fetch(query),
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame::{create_frame_from_bytes, FrameSequence, FRAME_SIZE};
+    use hkb_core::dtos::reminders::fakes;
+
+    // sends the event through the frame codec, as it would go through the socket
+    fn round_trip(event: &Event) -> Event {
+        let frames: FrameSequence = event.into();
+        let mut buffer = [0; FRAME_SIZE];
+        buffer.copy_from_slice(frames[0].convert_to_bytes());
+
+        create_frame_from_bytes(buffer).get_event().unwrap()
+    }
+
+    #[test]
+    fn it_answers_a_query_with_its_id() {
+        let query = Event::Query {
+            id: 7,
+            query: ReminderQuery {
+                note: Some("milk".to_owned()),
+                remind_at_between: None,
+            },
+        };
+        let reminder = fakes::create_reminder();
+
+        let received_query = round_trip(&query);
+        let result = answer_query(&received_query, |query| {
+            assert_eq!(Some("milk".to_owned()), query.note);
+
+            vec![reminder.clone()]
+        })
+        .unwrap();
+
+        assert_eq!(query, received_query);
+        assert_eq!(
+            Event::QueryResult {
+                id: 7,
+                reminders: vec![reminder]
+            },
+            round_trip(&result)
+        );
+        assert_eq!(None, answer_query(&Event::ReminderDeleted(1), |_| vec![]));
+    }
+}
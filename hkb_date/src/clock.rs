@@ -0,0 +1,189 @@
+use std::cell::Cell;
+use std::time::{Duration as STDDuration, Instant};
+
+use crate::date::SimpleDate;
+use crate::duration::Duration;
+
+/// Backward jumps smaller than this are treated as regular clock drift
+pub const DEFAULT_BACKWARD_JUMP_TOLERANCE: STDDuration = STDDuration::from_secs(5);
+
+/// A source of the current time, so code depending on "now" can be driven by tests
+pub trait Clock {
+    fn now(&self) -> SimpleDate;
+}
+
+/// Reads the local wall clock
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SimpleDate {
+        SimpleDate::local()
+    }
+}
+
+/// A clock that stays at the time it was set to, until moved by hand
+#[derive(Debug)]
+pub struct MockClock {
+    now: Cell<SimpleDate>,
+}
+
+impl MockClock {
+    pub fn new(now: SimpleDate) -> Self {
+        Self {
+            now: Cell::new(now),
+        }
+    }
+
+    pub fn set(&self, now: SimpleDate) {
+        self.now.set(now);
+    }
+
+    pub fn advance(&self, duration: impl AsRef<Duration>) {
+        if let Ok(now) = self.now.get().add_duration(duration) {
+            self.now.set(now);
+        }
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> SimpleDate {
+        self.now.get()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockReading {
+    pub now: SimpleDate,
+    /// Set when the wall clock went backwards by more than the tolerance since the last reading
+    pub jumped_back_by: Option<STDDuration>,
+}
+
+/// A "now" that never goes backwards.
+/// Readings are compared against a monotonic clock, so a wall clock going backwards
+/// (ex: an NTP correction) is detected. Until the wall clock catches up,
+/// the last reading is returned again, so nothing is handled twice.
+pub struct SteadyClock {
+    tolerance: STDDuration,
+    last_wall_reading: Option<(SimpleDate, Instant)>,
+    last_now: Option<SimpleDate>,
+}
+
+impl Default for SteadyClock {
+    fn default() -> Self {
+        Self::new(DEFAULT_BACKWARD_JUMP_TOLERANCE)
+    }
+}
+
+impl SteadyClock {
+    pub fn new(tolerance: STDDuration) -> Self {
+        Self {
+            tolerance,
+            last_wall_reading: None,
+            last_now: None,
+        }
+    }
+
+    pub fn read(&mut self) -> ClockReading {
+        self.read_at(SimpleDate::local(), Instant::now())
+    }
+
+    /// Reads the clock, given the current wall clock and monotonic time
+    pub fn read_at(&mut self, wall: SimpleDate, instant: Instant) -> ClockReading {
+        let jumped_back_by = self
+            .last_wall_reading
+            .map(|(last_wall, last_instant)| {
+                let elapsed = instant.saturating_duration_since(last_instant);
+                let went_back = last_wall - wall;
+
+                // the wall clock should have moved forward by as much as the monotonic one
+                if went_back.is_zero() {
+                    elapsed.saturating_sub(wall - last_wall)
+                } else {
+                    elapsed + went_back
+                }
+            })
+            .filter(|jump| *jump > self.tolerance);
+
+        let now = match self.last_now {
+            Some(last_now) if (wall - last_now).is_zero() => last_now,
+            _ => wall,
+        };
+
+        self.last_wall_reading = Some((wall, instant));
+        self.last_now = Some(now);
+
+        ClockReading {
+            now,
+            jumped_back_by,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(date: &str) -> SimpleDate {
+        SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn it_moves_the_mock_clock_only_by_hand() {
+        let clock = MockClock::new(date("2024-05-01 08:00:00"));
+
+        assert_eq!(date("2024-05-01 08:00:00"), clock.now());
+
+        clock.advance(Duration::Minute(90));
+        assert_eq!(date("2024-05-01 09:30:00"), clock.now());
+
+        clock.set(date("2024-04-01 08:00:00"));
+        assert_eq!(date("2024-04-01 08:00:00"), clock.now());
+    }
+
+    #[test]
+    fn it_reads_the_wall_clock_while_it_moves_forward() {
+        let mut clock = SteadyClock::default();
+        let start = Instant::now();
+
+        clock.read_at(date("2024-05-01 08:00:00"), start);
+        let reading = clock.read_at(
+            date("2024-05-01 08:00:10"),
+            start + STDDuration::from_secs(10),
+        );
+
+        assert_eq!(date("2024-05-01 08:00:10"), reading.now);
+        assert_eq!(None, reading.jumped_back_by);
+    }
+
+    #[test]
+    fn it_does_not_go_backwards_when_the_wall_clock_jumps_back() {
+        let mut clock = SteadyClock::default();
+        let start = Instant::now();
+
+        clock.read_at(date("2024-05-01 08:00:00"), start);
+        let reading = clock.read_at(
+            date("2024-05-01 07:00:10"),
+            start + STDDuration::from_secs(10),
+        );
+
+        assert_eq!(date("2024-05-01 08:00:00"), reading.now);
+        assert_eq!(Some(STDDuration::from_secs(3600)), reading.jumped_back_by);
+
+        // the jump is only reported once, but time stands still until the wall clock catches up
+        let reading = clock.read_at(
+            date("2024-05-01 07:00:20"),
+            start + STDDuration::from_secs(20),
+        );
+
+        assert_eq!(date("2024-05-01 08:00:00"), reading.now);
+        assert_eq!(None, reading.jumped_back_by);
+
+        let reading = clock.read_at(
+            date("2024-05-01 08:00:20"),
+            start + STDDuration::from_secs(3620),
+        );
+
+        assert_eq!(date("2024-05-01 08:00:20"), reading.now);
+    }
+}
@@ -1,22 +1,30 @@
 use crate::duration::*;
-use chrono::{Datelike, Local, NaiveDate, NaiveDateTime, NaiveTime, ParseError, Timelike, Utc};
+use chrono::{
+    DateTime, Datelike, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, ParseError,
+    TimeZone as ChronoTimeZone, Timelike, Utc,
+};
 use serde::{Deserialize, Serialize};
-use std::{fmt::Display, time::Duration as STDDuration};
+use std::{fmt::Display, str::FromStr, time::Duration as STDDuration};
 use thiserror::Error as ThisError;
 
+pub use chrono::Weekday;
+
 #[derive(ThisError, Debug)]
 pub enum DateError {
     #[error("Failed to parse date string")]
-    FailedToParseDateString(#[from] ParseError),
+    ParseError(#[from] ParseError),
+
+    #[error("{0} is out of range")]
+    OutOfRange(String),
 
-    #[error("Failed to set {0} with {1}")]
-    FailedToSetTimeUnit(String, DateUnit),
+    #[error("Moving the date by {0} overflows its range")]
+    Overflow(String),
 
-    #[error("Failed to set year with {0}")]
-    FailedToSetYear(i32),
+    #[error("Invalid timezone specified: {0}")]
+    InvalidTimezone(String),
 
-    #[error("Failed to set time")]
-    FailedToSetTime,
+    #[error("Invalid first day of the week specified: {0}")]
+    InvalidFirstDayOfWeek(String),
 }
 
 #[derive(PartialEq, Eq, Debug, Copy, Clone, Serialize, Deserialize)]
@@ -25,8 +33,98 @@ pub enum Timezone {
     Local,
 }
 
+/// Timezone in which a date is shown to the user.
+/// It can differ from the system one, ex: when traveling.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum DisplayTimezone {
+    UTC,
+    Local,
+    Offset(FixedOffset),
+}
+
+impl FromStr for DisplayTimezone {
+    type Err = DateError;
+
+    /// Accepts "utc", "local" or an offset like "+02:00"
+    fn from_str(timezone: &str) -> Result<Self, Self::Err> {
+        match timezone.trim().to_lowercase().as_str() {
+            "utc" => Ok(Self::UTC),
+            "local" => Ok(Self::Local),
+            offset => FixedOffset::from_str(offset)
+                .map(Self::Offset)
+                .map_err(|_| DateError::InvalidTimezone(timezone.to_string())),
+        }
+    }
+}
+
+/// Day a week starts on, when dates are grouped by week
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
+pub enum FirstDayOfWeek {
+    Sunday,
+    #[default]
+    Monday,
+}
+
+impl FromStr for FirstDayOfWeek {
+    type Err = DateError;
+
+    fn from_str(day: &str) -> Result<Self, Self::Err> {
+        match day.trim().to_lowercase().as_str() {
+            "sunday" => Ok(Self::Sunday),
+            "monday" => Ok(Self::Monday),
+            _ => Err(DateError::InvalidFirstDayOfWeek(day.to_string())),
+        }
+    }
+}
+
+/// Which of the month's weekdays a date falls on. Ex: the `Last` Friday of the month
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum WeekOfMonth {
+    First,
+    Second,
+    Third,
+    Fourth,
+    Last,
+}
+
+impl Display for WeekOfMonth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            WeekOfMonth::First => "first",
+            WeekOfMonth::Second => "second",
+            WeekOfMonth::Third => "third",
+            WeekOfMonth::Fourth => "fourth",
+            WeekOfMonth::Last => "last",
+        };
+
+        write!(f, "{}", value)
+    }
+}
+
+impl FromStr for WeekOfMonth {
+    type Err = String;
+
+    fn from_str(week: &str) -> Result<Self, Self::Err> {
+        match week.trim().to_lowercase().as_str() {
+            "first" => Ok(WeekOfMonth::First),
+            "second" => Ok(WeekOfMonth::Second),
+            "third" => Ok(WeekOfMonth::Third),
+            "fourth" => Ok(WeekOfMonth::Fourth),
+            "last" => Ok(WeekOfMonth::Last),
+            _ => Err(format!("Unknown week of month: {week}")),
+        }
+    }
+}
+
 pub type DateResult<T> = Result<T, DateError>;
 
+const FLEXIBLE_DATE_FORMATS: [&str; 4] = [
+    "%Y-%m-%d %H:%M",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%dT%H:%M:%S",
+];
+
 type DateUnit = u32;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
@@ -71,14 +169,39 @@ impl SimpleDate {
         })
     }
 
+    /// Parses a date written in any of the common formats.
+    /// Ex: "2024-04-05 08:00", "2024-04-05 08:00:30" or "2024-04-05T08:00:00Z"
+    pub fn parse_flexible(date: impl AsRef<str>) -> Result<Self, DateError> {
+        let date = date.as_ref().trim();
+        let mut result = Self::parse_from_rfc3339(date);
+
+        for format in FLEXIBLE_DATE_FORMATS {
+            if result.is_ok() {
+                break;
+            }
+
+            result = Self::parse_from_str(date, format);
+        }
+
+        result
+    }
+
+    /// Fails with `DateError::Overflow` when the result does not fit in a date
     pub fn add_duration(mut self, duration: impl AsRef<Duration>) -> DateResult<Self> {
-        self.date = self.date + duration.as_ref();
+        let duration = duration.as_ref();
+        self.date = duration
+            .checked_add_to(self.date)
+            .ok_or_else(|| DateError::Overflow(duration.to_string()))?;
 
         Ok(self)
     }
 
+    /// Fails with `DateError::Overflow` when the result does not fit in a date
     pub fn sub_duration(mut self, duration: impl AsRef<Duration>) -> DateResult<Self> {
-        self.date = self.date - duration.as_ref();
+        let duration = duration.as_ref();
+        self.date = duration
+            .checked_sub_from(self.date)
+            .ok_or_else(|| DateError::Overflow(duration.to_string()))?;
 
         Ok(self)
     }
@@ -121,9 +244,9 @@ impl SimpleDate {
 
     pub fn set_ymd(&mut self, year: i32, month: DateUnit, date: DateUnit) -> DateResult<()> {
         self.date = NaiveDate::from_ymd_opt(year, month, date)
-            .ok_or(DateError::FailedToSetTime)?
+            .ok_or_else(|| DateError::OutOfRange(format!("date {year}-{month}-{date}")))?
             .and_hms_opt(self.date.hour(), self.date.minute(), self.date.second())
-            .ok_or(DateError::FailedToSetTime)?;
+            .ok_or_else(|| DateError::OutOfRange(format!("time {}", self.date.time())))?;
 
         Ok(())
     }
@@ -134,8 +257,8 @@ impl SimpleDate {
         minute: DateUnit,
         second: DateUnit,
     ) -> DateResult<()> {
-        let time =
-            NaiveTime::from_hms_opt(hour, minute, second).ok_or(DateError::FailedToSetTime)?;
+        let time = NaiveTime::from_hms_opt(hour, minute, second)
+            .ok_or_else(|| DateError::OutOfRange(format!("time {hour}:{minute}:{second}")))?;
 
         self.date = NaiveDateTime::new(self.date.date(), time);
 
@@ -152,13 +275,21 @@ impl SimpleDate {
         second: DateUnit,
     ) -> DateResult<()> {
         self.date = NaiveDate::from_ymd_opt(year, month, date)
-            .ok_or(DateError::FailedToSetTime)?
+            .ok_or_else(|| DateError::OutOfRange(format!("date {year}-{month}-{date}")))?
             .and_hms_opt(hour, minute, second)
-            .ok_or(DateError::FailedToSetTime)?;
+            .ok_or_else(|| DateError::OutOfRange(format!("time {hour}:{minute}:{second}")))?;
 
         Ok(())
     }
 
+    /// Iterates each day from start (inclusive) to end (exclusive) at the start of the day
+    pub fn iter_days(start: SimpleDate, end: SimpleDate) -> DayIterator {
+        let mut current = start;
+        current.date = start.date.date().and_time(NaiveTime::MIN);
+
+        DayIterator { current, end }
+    }
+
     pub fn start_of_day(mut self) -> DateResult<Self> {
         self.set_start_of_day()?;
 
@@ -171,6 +302,109 @@ impl SimpleDate {
         Ok(self)
     }
 
+    /// Returns the date moved to the day of `day`, keeping its time of day
+    pub fn moved_to_day(mut self, day: SimpleDate) -> DateResult<Self> {
+        self.set_ymd(day.year(), day.month(), day.day())?;
+
+        Ok(self)
+    }
+
+    /// Start of the first day of the week the date is in
+    pub fn start_of_week(&self, first_day: FirstDayOfWeek) -> DateResult<Self> {
+        let weekday = self.date.weekday();
+        let days_since_start = match first_day {
+            FirstDayOfWeek::Sunday => weekday.num_days_from_sunday(),
+            FirstDayOfWeek::Monday => weekday.num_days_from_monday(),
+        };
+
+        self.sub_duration(Duration::Day(days_since_start))?
+            .start_of_day()
+    }
+
+    /// Last moment of the last day of the week the date is in
+    pub fn end_of_week(&self, first_day: FirstDayOfWeek) -> DateResult<Self> {
+        self.start_of_week(first_day)?
+            .add_duration(Duration::Day(6))?
+            .end_of_day()
+    }
+
+    /// Number of days in the month the date is in
+    pub fn days_in_month(&self) -> DateUnit {
+        let (year, month) = (self.year(), self.month());
+        let first_of_next_month = if month == 12 {
+            NaiveDate::from_ymd_opt(year + 1, 1, 1)
+        } else {
+            NaiveDate::from_ymd_opt(year, month + 1, 1)
+        };
+
+        // the last representable month is assumed to be a long one
+        first_of_next_month
+            .and_then(|date| date.pred_opt())
+            .map_or(31, |last_day| last_day.day())
+    }
+
+    /// Last moment of the last day of the month the date is in
+    pub fn end_of_month(&self) -> DateResult<Self> {
+        let mut date = *self;
+        date.set_day(self.days_in_month())?;
+
+        date.end_of_day()
+    }
+
+    pub fn weekday(&self) -> Weekday {
+        self.date.weekday()
+    }
+
+    /// Same time on the next `weekday` after the date, a week later if it already is that weekday
+    pub fn next_weekday(&self, weekday: Weekday) -> DateResult<Self> {
+        let days = match weekday.days_since(self.weekday()) {
+            0 => 7,
+            days => days,
+        };
+
+        self.add_duration(Duration::Day(days))
+    }
+
+    /// Next time the clock shows `hour`:`minute`, at or after the date.
+    /// Today if that time is still upcoming, otherwise tomorrow. Ex: "remind me at 18:00"
+    pub fn next_at_time(&self, hour: DateUnit, minute: DateUnit) -> DateResult<Self> {
+        let mut next = *self;
+        next.set_hms(hour, minute, 0)?;
+
+        if next.date < self.date {
+            next = next.add_duration(Duration::Day(1))?;
+        }
+
+        Ok(next)
+    }
+
+    /// Returns the date moved to the weekday of the month it is in, keeping its time of day.
+    /// Ex: the last Friday of April 2024 is the 26th
+    pub fn weekday_of_month(self, week: WeekOfMonth, weekday: Weekday) -> DateResult<Self> {
+        let mut first_day = self;
+        first_day.set_day(1)?;
+
+        let first_weekday = first_day.date.weekday();
+        // every month has at least 4 of each weekday, so only the last one needs the month length
+        let day = match week {
+            WeekOfMonth::First => 1 + weekday.days_since(first_weekday),
+            WeekOfMonth::Second => 8 + weekday.days_since(first_weekday),
+            WeekOfMonth::Third => 15 + weekday.days_since(first_weekday),
+            WeekOfMonth::Fourth => 22 + weekday.days_since(first_weekday),
+            WeekOfMonth::Last => {
+                let last_day = self.days_in_month();
+                let mut last_date = self;
+                last_date.set_day(last_day)?;
+
+                last_day - last_date.date.weekday().days_since(weekday)
+            }
+        };
+
+        first_day.set_day(day)?;
+
+        Ok(first_day)
+    }
+
     pub fn set_start_of_day(&mut self) -> DateResult<()> {
         self.set_hms(0, 0, 0)?;
 
@@ -183,6 +417,53 @@ impl SimpleDate {
         Ok(())
     }
 
+    /// Compares the dates down to the second, ignoring anything finer and the timezone label.
+    /// Prefer it over comparing `to_string()` output in tests, as that only holds
+    /// as long as the storage format keeps exactly what was created.
+    pub fn eq_to_second(&self, other: &Self) -> bool {
+        self.date.with_nanosecond(0) == other.date.with_nanosecond(0)
+    }
+
+    /// Whether both dates fall on the same calendar day.
+    /// Like `eq_to_second`, it compares the stored dates and ignores the timezone label.
+    pub fn is_same_day(&self, other: &Self) -> bool {
+        self.date.date() == other.date.date()
+    }
+
+    /// Whether the date comes strictly before the other one, ignoring the timezone label
+    pub fn is_before(&self, other: &Self) -> bool {
+        self.date < other.date
+    }
+
+    /// How far apart the dates are, no matter which one comes first.
+    /// Unlike subtracting, a date in the past is not clamped to zero.
+    pub fn diff(&self, other: &Self) -> STDDuration {
+        (self.date - other.date)
+            .abs()
+            .to_std()
+            .unwrap_or(STDDuration::MAX)
+    }
+
+    /// Returns a copy of the date with seconds (and anything finer) set to zero
+    pub fn truncate_to_minute(&self) -> Self {
+        let time = NaiveTime::from_hms_opt(self.hour(), self.minute(), 0).unwrap_or_default();
+
+        Self {
+            date: self.date.date().and_time(time),
+            timezone: self.timezone,
+        }
+    }
+
+    /// Returns a copy of the date with minutes and seconds (and anything finer) set to zero
+    pub fn truncate_to_hour(&self) -> Self {
+        let time = NaiveTime::from_hms_opt(self.hour(), 0, 0).unwrap_or_default();
+
+        Self {
+            date: self.date.date().and_time(time),
+            timezone: self.timezone,
+        }
+    }
+
     pub fn year(&self) -> i32 {
         self.date.year()
     }
@@ -215,6 +496,49 @@ impl SimpleDate {
         self.date.format(format.as_ref()).to_string()
     }
 
+    /// Reads the date and time as they are, as local wall-clock time.
+    /// Ex: dates stored without the offset they were entered in.
+    pub fn as_local(mut self) -> Self {
+        self.timezone = Timezone::Local;
+
+        self
+    }
+
+    pub fn format_in_tz(&self, tz: DisplayTimezone, format: impl AsRef<str>) -> String {
+        self.to_date_in_tz(tz).format(format.as_ref()).to_string()
+    }
+
+    pub fn to_string_in_tz(&self, tz: DisplayTimezone) -> String {
+        self.to_date_in_tz(tz)
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+    }
+
+    fn to_date_in_tz(self, tz: DisplayTimezone) -> DateTime<FixedOffset> {
+        self.to_date_in_tz_with_local(tz, &Local)
+    }
+
+    // the local timezone is passed in, so it can be pinned in the tests
+    fn to_date_in_tz_with_local<Tz: ChronoTimeZone>(
+        self,
+        tz: DisplayTimezone,
+        local: &Tz,
+    ) -> DateTime<FixedOffset> {
+        let date = match self.timezone {
+            Timezone::UTC => self.date.and_utc(),
+            Timezone::Local => local
+                .from_local_datetime(&self.date)
+                .earliest()
+                .map(|date| date.to_utc())
+                .unwrap_or_else(|| self.date.and_utc()),
+        };
+
+        match tz {
+            DisplayTimezone::UTC => date.fixed_offset(),
+            DisplayTimezone::Local => date.with_timezone(local).fixed_offset(),
+            DisplayTimezone::Offset(offset) => date.with_timezone(&offset),
+        }
+    }
+
     #[cfg(not(feature = "chrono"))]
     #[allow(clippy::wrong_self_convention)]
     pub(crate) fn to_chrono_date(&self) -> chrono::NaiveDateTime {
@@ -239,6 +563,27 @@ impl Display for SimpleDate {
     }
 }
 
+pub struct DayIterator {
+    current: SimpleDate,
+    end: SimpleDate,
+}
+
+impl Iterator for DayIterator {
+    type Item = SimpleDate;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // subtraction saturates to zero, once we reach the end date
+        if (self.end - self.current).is_zero() {
+            return None;
+        }
+
+        let day = self.current;
+        self.current = self.current.add_duration(Duration::Day(1)).ok()?;
+
+        Some(day)
+    }
+}
+
 impl std::ops::Sub<SimpleDate> for SimpleDate {
     type Output = STDDuration;
 
@@ -253,7 +598,7 @@ fn set_year(date: NaiveDateTime, year: i32) -> Result<NaiveDateTime, DateError>
     if let Some(date) = date.with_year(year) {
         Ok(date)
     } else {
-        Err(DateError::FailedToSetYear(year))
+        Err(DateError::OutOfRange(format!("year {year}")))
     }
 }
 
@@ -261,7 +606,7 @@ fn set_month(date: NaiveDateTime, month: DateUnit) -> Result<NaiveDateTime, Date
     if let Some(date) = date.with_month(month) {
         Ok(date)
     } else {
-        Err(DateError::FailedToSetTimeUnit("month".to_string(), month))
+        Err(DateError::OutOfRange(format!("month {month}")))
     }
 }
 
@@ -269,7 +614,7 @@ fn set_day(date: NaiveDateTime, day: DateUnit) -> Result<NaiveDateTime, DateErro
     if let Some(date) = date.with_day(day) {
         Ok(date)
     } else {
-        Err(DateError::FailedToSetTimeUnit("day".to_string(), day))
+        Err(DateError::OutOfRange(format!("day {day}")))
     }
 }
 
@@ -277,7 +622,7 @@ fn set_hour(date: NaiveDateTime, hour: DateUnit) -> Result<NaiveDateTime, DateEr
     if let Some(date) = date.with_hour(hour) {
         Ok(date)
     } else {
-        Err(DateError::FailedToSetTimeUnit("hour".to_string(), hour))
+        Err(DateError::OutOfRange(format!("hour {hour}")))
     }
 }
 
@@ -285,7 +630,7 @@ fn set_minute(date: NaiveDateTime, minute: DateUnit) -> Result<NaiveDateTime, Da
     if let Some(date) = date.with_minute(minute) {
         Ok(date)
     } else {
-        Err(DateError::FailedToSetTimeUnit("minute".to_string(), minute))
+        Err(DateError::OutOfRange(format!("minute {minute}")))
     }
 }
 
@@ -293,7 +638,7 @@ fn set_second(date: NaiveDateTime, second: DateUnit) -> Result<NaiveDateTime, Da
     if let Some(date) = date.with_second(second) {
         Ok(date)
     } else {
-        Err(DateError::FailedToSetTimeUnit("second".to_string(), second))
+        Err(DateError::OutOfRange(format!("second {second}")))
     }
 }
 
@@ -302,6 +647,132 @@ mod tests {
     use super::*;
     use chrono::NaiveDateTime;
 
+    #[test]
+    fn it_displays_a_utc_date_in_another_timezone() {
+        let date = SimpleDate::parse_from_str("2024-04-14 22:30:00", "%Y-%m-%d %H:%M:%S").unwrap();
+
+        assert_eq!(
+            "2024-04-15T01:30:00+03:00",
+            date.to_string_in_tz(DisplayTimezone::from_str("+03:00").unwrap())
+        );
+        assert_eq!(
+            "2024-04-14T17:30:00-05:00",
+            date.to_string_in_tz(DisplayTimezone::from_str("-05:00").unwrap())
+        );
+        assert_eq!(
+            "2024-04-14T22:30:00Z",
+            date.to_string_in_tz(DisplayTimezone::UTC)
+        );
+        assert_eq!(
+            "15-04-2024 01:30",
+            date.format_in_tz(
+                DisplayTimezone::from_str("+03:00").unwrap(),
+                "%d-%m-%Y %H:%M"
+            )
+        );
+    }
+
+    #[test]
+    fn it_displays_a_local_date_in_another_timezone() {
+        // a local timezone three hours ahead of UTC, without daylight saving
+        let local = FixedOffset::east_opt(3 * 3600).unwrap();
+        let date = SimpleDate::parse_from_str("2024-04-14 22:30:00", "%Y-%m-%d %H:%M:%S")
+            .unwrap()
+            .as_local();
+        let in_tz = |tz: DisplayTimezone| {
+            date.to_date_in_tz_with_local(tz, &local)
+                .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+        };
+
+        assert_eq!("2024-04-14T22:30:00+03:00", in_tz(DisplayTimezone::Local));
+        assert_eq!("2024-04-14T19:30:00Z", in_tz(DisplayTimezone::UTC));
+        assert_eq!(
+            "2024-04-15T00:30:00+05:00",
+            in_tz(DisplayTimezone::from_str("+05:00").unwrap())
+        );
+    }
+
+    #[test]
+    fn it_parses_dates_in_flexible_formats() {
+        let expected =
+            SimpleDate::parse_from_str("2024-04-05 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+
+        assert_eq!(
+            expected,
+            SimpleDate::parse_flexible("2024-04-05 08:00").unwrap()
+        );
+        assert_eq!(
+            expected,
+            SimpleDate::parse_flexible(" 2024-04-05 08:00:00 ").unwrap()
+        );
+        assert_eq!(
+            expected,
+            SimpleDate::parse_flexible("2024-04-05T08:00").unwrap()
+        );
+        assert_eq!(
+            expected,
+            SimpleDate::parse_flexible("2024-04-05T08:00:00Z").unwrap()
+        );
+        assert!(SimpleDate::parse_flexible("tomorrow").is_err());
+    }
+
+    #[test]
+    fn it_returns_a_parse_error_for_a_bad_date() {
+        assert!(matches!(
+            SimpleDate::parse_from_str("2024-13-05 08:00:00", "%Y-%m-%d %H:%M:%S"),
+            Err(DateError::ParseError(_))
+        ));
+        assert!(matches!(
+            SimpleDate::parse_flexible("tomorrow-ish"),
+            Err(DateError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn it_returns_an_error_when_the_date_overflows() {
+        let date = SimpleDate::parse_from_str("2024-04-14 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+
+        assert!(matches!(
+            date.add_duration(Duration::Year((i32::MAX - 2025) as u32)),
+            Err(DateError::Overflow(_))
+        ));
+        assert!(matches!(
+            date.sub_duration(Duration::Day(u32::MAX)),
+            Err(DateError::Overflow(_))
+        ));
+
+        let mut date = date;
+
+        assert!(matches!(date.set_month(13), Err(DateError::OutOfRange(_))));
+    }
+
+    #[test]
+    fn it_ends_on_the_last_day_of_a_shorter_month() {
+        let date = SimpleDate::parse_from_str("2024-01-31 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+
+        assert_eq!(
+            "2024-02-29T08:00:00Z",
+            date.add_duration(Duration::Month(1)).unwrap().to_string()
+        );
+        assert_eq!(
+            "2023-02-28T08:00:00Z",
+            date.add_duration(Duration::Month(1))
+                .unwrap()
+                .sub_duration(Duration::Year(1))
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn it_fails_to_parse_an_invalid_display_timezone() {
+        assert!(DisplayTimezone::from_str("somewhere").is_err());
+        assert_eq!(
+            DisplayTimezone::Local,
+            DisplayTimezone::from_str("Local").unwrap()
+        );
+    }
+
     macro_rules! assert_correct_date_time_from_duration {
         ($expected_date:literal, $duration:expr) => {
             let date =
@@ -342,6 +813,285 @@ mod tests {
         assert_eq!(59, date.second());
     }
 
+    #[test]
+    fn it_can_be_truncated_to_minute() {
+        let date = SimpleDate::parse_from_str("2024-04-14 08:25:43", "%Y-%m-%d %H:%M:%S").unwrap();
+
+        assert_eq!(
+            SimpleDate::parse_from_str("2024-04-14 08:25:00", "%Y-%m-%d %H:%M:%S").unwrap(),
+            date.truncate_to_minute()
+        );
+        assert_eq!(43, date.second());
+    }
+
+    #[test]
+    fn it_can_be_truncated_to_hour() {
+        let date = SimpleDate::parse_from_str("2024-04-14 08:25:43", "%Y-%m-%d %H:%M:%S").unwrap();
+
+        assert_eq!(
+            SimpleDate::parse_from_str("2024-04-14 08:00:00", "%Y-%m-%d %H:%M:%S").unwrap(),
+            date.truncate_to_hour()
+        );
+    }
+
+    #[test]
+    fn it_drops_sub_second_precision_when_truncating() {
+        let date = SimpleDate::local();
+        let truncated_date = date.truncate_to_minute();
+
+        assert_eq!(0, truncated_date.second());
+        assert_eq!(0, truncated_date.to_chrono_date().nanosecond());
+        assert_eq!(date.get_timezone(), truncated_date.get_timezone());
+    }
+
+    #[test]
+    fn it_compares_dates_down_to_the_second() {
+        let date =
+            SimpleDate::parse_from_str("2024-04-05 08:00:00.250", "%Y-%m-%d %H:%M:%S%.f").unwrap();
+        let other_date =
+            SimpleDate::parse_from_str("2024-04-05 08:00:00.750", "%Y-%m-%d %H:%M:%S%.f").unwrap();
+        let next_second =
+            SimpleDate::parse_from_str("2024-04-05 08:00:01.250", "%Y-%m-%d %H:%M:%S%.f").unwrap();
+
+        assert_ne!(date, other_date);
+        assert!(date.eq_to_second(&other_date));
+        assert!(!date.eq_to_second(&next_second));
+    }
+
+    #[test]
+    fn it_moves_a_date_to_another_day_keeping_the_time() {
+        let parse = |date| SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap();
+        let date = parse("2024-04-10 15:30:45");
+
+        assert_eq!(
+            parse("2024-05-01 15:30:45"),
+            date.moved_to_day(parse("2024-05-01 08:00:00")).unwrap()
+        );
+        assert_eq!(
+            parse("2024-02-29 15:30:45"),
+            date.moved_to_day(parse("2024-02-29 23:59:59")).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_starts_the_week_on_the_configured_day() {
+        let parse = |date| SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap();
+        let wednesday = parse("2024-04-10 15:30:00");
+        let sunday = parse("2024-04-14 15:30:00");
+
+        assert_eq!(
+            parse("2024-04-08 00:00:00"),
+            wednesday.start_of_week(FirstDayOfWeek::Monday).unwrap()
+        );
+        assert_eq!(
+            parse("2024-04-07 00:00:00"),
+            wednesday.start_of_week(FirstDayOfWeek::Sunday).unwrap()
+        );
+        assert_eq!(
+            parse("2024-04-08 00:00:00"),
+            sunday.start_of_week(FirstDayOfWeek::Monday).unwrap()
+        );
+        assert_eq!(
+            parse("2024-04-14 00:00:00"),
+            sunday.start_of_week(FirstDayOfWeek::Sunday).unwrap()
+        );
+        assert_eq!(
+            FirstDayOfWeek::Sunday,
+            FirstDayOfWeek::from_str(" Sunday").unwrap()
+        );
+    }
+
+    #[test]
+    fn it_ends_the_week_on_the_day_before_the_configured_first_day() {
+        let parse = |date| SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap();
+        let wednesday = parse("2024-04-10 15:30:00");
+        let sunday = parse("2024-04-14 15:30:00");
+
+        assert_eq!(
+            parse("2024-04-14 23:59:59"),
+            wednesday.end_of_week(FirstDayOfWeek::Monday).unwrap()
+        );
+        assert_eq!(
+            parse("2024-04-13 23:59:59"),
+            wednesday.end_of_week(FirstDayOfWeek::Sunday).unwrap()
+        );
+        assert_eq!(
+            parse("2024-04-14 23:59:59"),
+            sunday.end_of_week(FirstDayOfWeek::Monday).unwrap()
+        );
+        assert_eq!(
+            parse("2024-04-20 23:59:59"),
+            sunday.end_of_week(FirstDayOfWeek::Sunday).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_ends_the_month_on_its_last_day() {
+        let parse = |date| SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap();
+
+        assert_eq!(
+            parse("2023-02-28 23:59:59"),
+            parse("2023-02-10 08:00:00").end_of_month().unwrap()
+        );
+        assert_eq!(
+            parse("2024-02-29 23:59:59"),
+            parse("2024-02-10 08:00:00").end_of_month().unwrap()
+        );
+        assert_eq!(
+            parse("2024-04-30 23:59:59"),
+            parse("2024-04-30 08:00:00").end_of_month().unwrap()
+        );
+        assert_eq!(
+            parse("2024-12-31 23:59:59"),
+            parse("2024-12-01 00:00:00").end_of_month().unwrap()
+        );
+        assert_eq!(
+            vec![31, 29, 31, 30],
+            [
+                "2024-01-05 08:00:00",
+                "2024-02-05 08:00:00",
+                "2024-03-05 08:00:00",
+                "2024-04-05 08:00:00"
+            ]
+            .into_iter()
+            .map(|date| parse(date).days_in_month())
+            .collect::<Vec<u32>>()
+        );
+    }
+
+    #[test]
+    fn it_finds_the_next_weekday() {
+        let parse = |date| SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap();
+        let friday = parse("2024-04-12 15:30:00");
+
+        assert_eq!(Weekday::Fri, friday.weekday());
+        assert_eq!(
+            parse("2024-04-15 15:30:00"),
+            friday.next_weekday(Weekday::Mon).unwrap()
+        );
+        assert_eq!(
+            parse("2024-04-19 15:30:00"),
+            friday.next_weekday(Weekday::Fri).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_finds_the_next_time_of_day() {
+        let parse = |date| SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap();
+        let now = parse("2024-04-30 15:30:20");
+
+        assert_eq!(
+            parse("2024-04-30 18:00:00"),
+            now.next_at_time(18, 0).unwrap()
+        );
+        assert_eq!(
+            parse("2024-05-01 09:00:00"),
+            now.next_at_time(9, 0).unwrap()
+        );
+        // the current minute has already started
+        assert_eq!(
+            parse("2024-05-01 15:30:00"),
+            now.next_at_time(15, 30).unwrap()
+        );
+        assert_eq!(
+            parse("2024-04-30 15:30:00"),
+            parse("2024-04-30 15:30:00").next_at_time(15, 30).unwrap()
+        );
+        assert!(matches!(
+            now.next_at_time(24, 0),
+            Err(DateError::OutOfRange(_))
+        ));
+    }
+
+    #[test]
+    fn it_moves_a_date_to_a_weekday_of_its_month() {
+        let parse = |date| SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap();
+        let date = parse("2024-04-10 09:30:00");
+
+        assert_eq!(
+            parse("2024-04-05 09:30:00"),
+            date.weekday_of_month(WeekOfMonth::First, Weekday::Fri)
+                .unwrap()
+        );
+        assert_eq!(
+            parse("2024-04-01 09:30:00"),
+            date.weekday_of_month(WeekOfMonth::First, Weekday::Mon)
+                .unwrap()
+        );
+        assert_eq!(
+            parse("2024-04-22 09:30:00"),
+            date.weekday_of_month(WeekOfMonth::Fourth, Weekday::Mon)
+                .unwrap()
+        );
+        assert_eq!(
+            parse("2024-04-26 09:30:00"),
+            date.weekday_of_month(WeekOfMonth::Last, Weekday::Fri)
+                .unwrap()
+        );
+        assert_eq!(
+            parse("2024-04-30 09:30:00"),
+            date.weekday_of_month(WeekOfMonth::Last, Weekday::Tue)
+                .unwrap()
+        );
+        assert_eq!(WeekOfMonth::Last, WeekOfMonth::from_str(" Last").unwrap());
+        assert!(WeekOfMonth::from_str("fifth").is_err());
+    }
+
+    #[test]
+    fn it_checks_if_dates_are_on_the_same_day() {
+        let parse = |date| SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap();
+        let morning = parse("2024-04-05 00:00:00");
+
+        assert!(morning.is_same_day(&parse("2024-04-05 23:59:59")));
+        assert!(!morning.is_same_day(&parse("2024-04-04 23:59:59")));
+        assert!(!morning.is_same_day(&parse("2024-04-06 00:00:00")));
+        assert!(!morning.is_same_day(&parse("2025-04-05 00:00:00")));
+    }
+
+    #[test]
+    fn it_checks_if_a_date_comes_before_another() {
+        let parse = |date| SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap();
+        let now = parse("2024-04-05 12:00:00");
+
+        assert!(now.is_before(&parse("2024-04-05 12:00:01")));
+        assert!(!now.is_before(&now));
+        assert!(!now.is_before(&parse("2024-04-04 23:59:59")));
+    }
+
+    #[test]
+    fn it_diffs_dates_in_either_direction() {
+        let parse = |date| SimpleDate::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap();
+        let now = parse("2024-04-05 12:00:00");
+
+        assert_eq!(
+            STDDuration::from_secs(90 * 60),
+            now.diff(&parse("2024-04-05 13:30:00"))
+        );
+        assert_eq!(
+            STDDuration::from_secs(90 * 60),
+            now.diff(&parse("2024-04-05 10:30:00"))
+        );
+        assert_eq!(STDDuration::ZERO, now.diff(&now));
+    }
+
+    #[test]
+    fn it_can_iterate_days_across_a_month_boundary() {
+        let start = SimpleDate::parse_from_str("2024-04-28 15:30:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let end = SimpleDate::parse_from_str("2024-05-03 00:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let days = SimpleDate::iter_days(start, end).collect::<Vec<SimpleDate>>();
+
+        assert_eq!(5, days.len());
+        assert_eq!(
+            SimpleDate::parse_from_str("2024-04-28 00:00:00", "%Y-%m-%d %H:%M:%S").unwrap(),
+            days[0]
+        );
+        assert_eq!(
+            SimpleDate::parse_from_str("2024-05-02 00:00:00", "%Y-%m-%d %H:%M:%S").unwrap(),
+            days[4]
+        );
+        assert_eq!(0, SimpleDate::iter_days(end, start).count());
+    }
+
     #[test]
     fn minute_duration_can_be_added_to_date_time() {
         assert_correct_date_time_from_duration!("2024-04-14 08:01:00", Duration::Minute(1));
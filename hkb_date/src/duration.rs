@@ -1,4 +1,4 @@
-use chrono::{Datelike, NaiveDateTime, TimeDelta};
+use chrono::{Datelike, Months, NaiveDateTime, TimeDelta};
 use std::{fmt::Display, time::Duration as STDDuration};
 use thiserror::Error as ThisError;
 
@@ -18,6 +18,7 @@ pub enum DurationError {
 
 // TODO: do we need negative values here?
 // For me a duration is forwards in time
+#[derive(Debug)]
 pub enum Duration {
     Minute(DateUnit),
     Hour(DateUnit),
@@ -52,6 +53,42 @@ impl Duration {
             Duration::Year(v) => v,
         }
     }
+
+    /// Adds the duration to the date, `None` if the result does not fit in a date.
+    /// Months and years that land past the end of a month end on its last day.
+    pub fn checked_add_to(&self, date: NaiveDateTime) -> Option<NaiveDateTime> {
+        match self {
+            Duration::Month(_) | Duration::Year(_) => date.checked_add_months(self.months()?),
+            _ => date.checked_add_signed(self.time_delta()?),
+        }
+    }
+
+    /// Subtracts the duration from the date, `None` if the result does not fit in a date.
+    /// Months and years that land past the end of a month end on its last day.
+    pub fn checked_sub_from(&self, date: NaiveDateTime) -> Option<NaiveDateTime> {
+        match self {
+            Duration::Month(_) | Duration::Year(_) => date.checked_sub_months(self.months()?),
+            _ => date.checked_sub_signed(self.time_delta()?),
+        }
+    }
+
+    fn time_delta(&self) -> Option<TimeDelta> {
+        match self {
+            Duration::Minute(v) => TimeDelta::try_minutes(*v as i64),
+            Duration::Hour(v) => TimeDelta::try_hours(*v as i64),
+            Duration::Day(v) => TimeDelta::try_days(*v as i64),
+            Duration::Week(v) => TimeDelta::try_weeks(*v as i64),
+            Duration::Month(_) | Duration::Year(_) => None,
+        }
+    }
+
+    fn months(&self) -> Option<Months> {
+        match self {
+            Duration::Month(v) => Some(Months::new(*v)),
+            Duration::Year(v) => v.checked_mul(MONTHS_IN_A_YEAR).map(Months::new),
+            _ => None,
+        }
+    }
 }
 
 impl Display for Duration {
@@ -1,3 +1,4 @@
+pub mod clock;
 pub mod date;
 pub mod duration;
 mod human_date_parsing;